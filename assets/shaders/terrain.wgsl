@@ -6,20 +6,51 @@
     view_transformations::position_world_to_clip,
 }
 
-// Vertex morphing is disabled - it caused visual artifacts with quadtree-based LOD
-// where chunks are swapped rather than morphed. Skirts hide the LOD seams instead.
-// To re-enable, set these to match your LOD distance thresholds.
-const MORPH_START: f32 = 999999.0;
-const MORPH_END: f32 = 999999.0;
+
+// Per-chunk reconstruction parameters for quantized positions
+struct PositionQuantization {
+    origin: vec3<f32>,
+    scale: vec3<f32>,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> quantization: PositionQuantization;
+
+// Aerial perspective tint toward a horizon color (max_strength is 0 when disabled)
+struct AerialPerspective {
+    color: vec4<f32>,
+    start_distance: f32,
+    end_distance: f32,
+    max_strength: f32,
+    height_falloff: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var<uniform> aerial: AerialPerspective;
+
+// Per-LOD geomorph bands derived from TerrainConfig::lod_distances (finest LOD first, four per vector)
+struct MorphDistances {
+    start: array<vec4<f32>, 4>,
+    end: array<vec4<f32>, 4>,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(107) var<uniform> morph_bands: MorphDistances;
 
 // Custom vertex input with morph_height attribute
 struct TerrainVertex {
     @builtin(instance_index) instance_index: u32,
+#ifdef TERRAIN_QUANTIZED
+    @location(0) quantized_position: vec4<f32>,
+#else
     @location(0) position: vec3<f32>,
+#endif
     @location(1) normal: vec3<f32>,
     @location(2) uv: vec2<f32>,
+#ifdef VERTEX_UVS_B
+    @location(3) uv_b: vec2<f32>,
+#endif
     @location(5) color: vec4<f32>,
+#ifdef TERRAIN_MORPH
     @location(17) morph_height: f32,
+#endif
 }
 
 @vertex
@@ -28,8 +59,18 @@ fn vertex(vertex: TerrainVertex) -> VertexOutput {
 
     let mesh_world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
 
+#ifdef TERRAIN_QUANTIZED
+    // Rebuild the chunk-local position from its normalized 16-bit form
+    let position = quantization.origin + vertex.quantized_position.xyz * quantization.scale;
+#else
+    let position = vertex.position;
+#endif
+
+    var morphed_position = position;
+
+#ifdef TERRAIN_MORPH
     // Calculate world position of the vertex (before morphing, for distance calc)
-    let world_pos = (mesh_world_from_local * vec4<f32>(vertex.position, 1.0)).xyz;
+    let world_pos = (mesh_world_from_local * vec4<f32>(position, 1.0)).xyz;
 
     // Get camera position from view uniform
     let camera_position = view.world_position;
@@ -37,13 +78,16 @@ fn vertex(vertex: TerrainVertex) -> VertexOutput {
     // Calculate distance from camera to vertex
     let distance = length(world_pos - camera_position);
 
-    // Calculate morph factor: 0 at MORPH_START, 1 at MORPH_END
-    let morph_range = max(MORPH_END - MORPH_START, 0.001);
-    let morph_factor = clamp((distance - MORPH_START) / morph_range, 0.0, 1.0);
+    // Morph within the band of the chunk's own LOD (its mesh tag): 0 at its start, 1 at its end
+    let lod = min(mesh_functions::get_tag(vertex.instance_index), 15u);
+    let morph_start = morph_bands.start[lod / 4u][lod % 4u];
+    let morph_end = morph_bands.end[lod / 4u][lod % 4u];
+    let morph_range = max(morph_end - morph_start, 0.001);
+    let morph_factor = clamp((distance - morph_start) / morph_range, 0.0, 1.0);
 
     // Interpolate between actual height and morph height
-    var morphed_position = vertex.position;
-    morphed_position.y = mix(vertex.position.y, vertex.morph_height, morph_factor);
+    morphed_position.y = mix(position.y, vertex.morph_height, morph_factor);
+#endif
 
     // Transform normal to world space
     out.world_normal = mesh_functions::mesh_normal_local_to_world(
@@ -59,7 +103,17 @@ fn vertex(vertex: TerrainVertex) -> VertexOutput {
     out.position = position_world_to_clip(out.world_position.xyz);
 
     out.uv = vertex.uv;
-    out.color = vertex.color;
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+    // Blend toward the aerial color with camera distance, thinning with altitude
+    let view_distance = length(out.world_position.xyz - view.world_position);
+    let aerial_range = max(aerial.end_distance - aerial.start_distance, 0.001);
+    let aerial_distance = clamp((view_distance - aerial.start_distance) / aerial_range, 0.0, 1.0);
+    let aerial_height = exp(-max(out.world_position.y, 0.0) * aerial.height_falloff);
+    let aerial_factor = aerial_distance * aerial.max_strength * aerial_height;
+    out.color = vec4<f32>(mix(vertex.color.rgb, aerial.color.rgb, aerial_factor), vertex.color.a);
 
 #ifdef VERTEX_OUTPUT_INSTANCE_INDEX
     out.instance_index = vertex.instance_index;
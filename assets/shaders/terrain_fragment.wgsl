@@ -0,0 +1,171 @@
+#import bevy_pbr::{
+    mesh_view_bindings::view,
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, apply_pbr_lighting, main_pass_post_lighting_processing},
+    forward_io::{VertexOutput, FragmentOutput},
+}
+
+// Layer weights looked up by (normalized height, slope), one layer per channel
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var splatmap_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(103) var splatmap_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(104) var layer_textures: texture_2d_array<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(105) var layer_sampler: sampler;
+
+// Splatting parameters (layer_count is 0 until the layer textures have loaded)
+struct TerrainSplat {
+    tiling: vec4<f32>,
+    min_height: f32,
+    max_height: f32,
+    layer_count: u32,
+    triplanar_sharpness: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(106) var<uniform> splat: TerrainSplat;
+
+// Elevation contour lines (interval is 0 when disabled)
+struct TerrainContours {
+    color: vec4<f32>,
+    interval: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(108) var<uniform> contours: TerrainContours;
+
+// Distance haze applied after lighting (end is 0 when disabled)
+struct TerrainFog {
+    color: vec4<f32>,
+    start: f32,
+    end: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(109) var<uniform> fog: TerrainFog;
+
+// Debug views replacing the shaded color (view is 0 when off)
+struct TerrainDebug {
+    lod_color: vec4<f32>,
+    view: u32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(110) var<uniform> debug: TerrainDebug;
+
+const DEBUG_VIEW_LOD: u32 = 1u;
+const DEBUG_VIEW_NORMALS: u32 = 2u;
+
+// Contour line width in pixels
+const CONTOUR_WIDTH: f32 = 1.0;
+
+// World units per texture repeat at a layer tiling of 1 is 1 / TEXTURE_SCALE
+const TEXTURE_SCALE: f32 = 0.1;
+
+// Sample a splat layer, projecting along all three axes when triplanar is enabled
+fn sample_layer(layer: u32, world_position: vec3<f32>, blend: vec3<f32>) -> vec3<f32> {
+    let scale = TEXTURE_SCALE * splat.tiling[layer];
+    let y = textureSample(layer_textures, layer_sampler, world_position.xz * scale, layer).rgb;
+    if splat.triplanar_sharpness <= 0.0 {
+        return y;
+    }
+    let x = textureSample(layer_textures, layer_sampler, world_position.zy * scale, layer).rgb;
+    let z = textureSample(layer_textures, layer_sampler, world_position.xy * scale, layer).rgb;
+    return x * blend.x + y * blend.y + z * blend.z;
+}
+
+// Brightness variation applied on top of the base color through UV1
+const DETAIL_STRENGTH: f32 = 0.08;
+
+fn hash2(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Smooth value noise in [0, 1]
+fn value_noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash2(cell);
+    let b = hash2(cell + vec2<f32>(1.0, 0.0));
+    let c = hash2(cell + vec2<f32>(0.0, 1.0));
+    let d = hash2(cell + vec2<f32>(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    if debug.view == DEBUG_VIEW_LOD {
+        var out: FragmentOutput;
+        out.color = debug.lod_color;
+        return out;
+    }
+    if debug.view == DEBUG_VIEW_NORMALS {
+        var out: FragmentOutput;
+        out.color = vec4<f32>(normalize(in.world_normal) * 0.5 + 0.5, 1.0);
+        return out;
+    }
+
+    // Vertex colors (biome rules) are applied by the standard material input
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    if splat.layer_count > 0u {
+        let height_range = max(splat.max_height - splat.min_height, 0.001);
+        let height = clamp((in.world_position.y - splat.min_height) / height_range, 0.0, 1.0);
+        let normal = normalize(in.world_normal);
+        let slope = clamp(1.0 - normal.y, 0.0, 1.0);
+        let weights = textureSample(splatmap_texture, splatmap_sampler, vec2<f32>(height, slope));
+
+        // Projection weights favor the axis the surface faces
+        var blend = pow(abs(normal), vec3<f32>(max(splat.triplanar_sharpness, 1.0)));
+        blend /= max(blend.x + blend.y + blend.z, 0.0001);
+
+        var color = vec3<f32>(0.0);
+        var total = 0.0;
+        for (var i = 0u; i < splat.layer_count; i++) {
+            color += sample_layer(i, in.world_position.xyz, blend) * weights[i];
+            total += weights[i];
+        }
+
+        // Ground no layer covers keeps its vertex color
+        if total > 0.0 {
+            let splatted = color / total;
+            let base = pbr_input.material.base_color.rgb;
+            pbr_input.material.base_color = vec4<f32>(
+                mix(base, splatted, min(total, 1.0)),
+                pbr_input.material.base_color.a,
+            );
+        }
+    }
+
+#ifdef VERTEX_UVS_B
+    // UV1 is continuous across chunks, so the overlay has no seams at chunk borders
+    let detail = value_noise(in.uv_b) * 0.67 + value_noise(in.uv_b * 4.0) * 0.33;
+    pbr_input.material.base_color = vec4<f32>(
+        pbr_input.material.base_color.rgb * (1.0 + (detail - 0.5) * 2.0 * DETAIL_STRENGTH),
+        pbr_input.material.base_color.a,
+    );
+#endif
+
+    if contours.interval > 0.0 {
+        // Distance to the nearest multiple of the interval, in pixels via fwidth
+        let level = in.world_position.y / contours.interval;
+        let pixels = abs(fract(level + 0.5) - 0.5) / max(fwidth(level), 0.0001);
+        let line = (1.0 - smoothstep(0.0, CONTOUR_WIDTH, pixels)) * contours.color.a;
+        pbr_input.material.base_color = vec4<f32>(
+            mix(pbr_input.material.base_color.rgb, contours.color.rgb, line),
+            pbr_input.material.base_color.a,
+        );
+    }
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+
+    if fog.end > 0.0 {
+        let distance = length(in.world_position.xyz - view.world_position);
+        let fog_factor = clamp((distance - fog.start) / max(fog.end - fog.start, 0.001), 0.0, 1.0);
+        out.color = vec4<f32>(mix(out.color.rgb, fog.color.rgb, fog_factor * fog.color.a), out.color.a);
+    }
+
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
@@ -0,0 +1,40 @@
+//! Chunk mesh generation benchmarks
+//!
+//! Compare the serial and row-parallel height grid with:
+//! `cargo bench --bench mesh` and `cargo bench --bench mesh --features parallel`
+
+use bevy::prelude::*;
+use bevy_stadt_terrain::config::TerrainConfig;
+use bevy_stadt_terrain::heightmap::TerrainNoise;
+use bevy_stadt_terrain::mesh::generate_chunk_mesh;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn chunk_mesh(c: &mut Criterion) {
+    let noise = TerrainNoise::default();
+    let config = TerrainConfig::default();
+    let mut group = c.benchmark_group("generate_chunk_mesh");
+
+    for &subdivisions in &config.lod_subdivisions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(subdivisions),
+            &subdivisions,
+            |b, &subdivisions| {
+                b.iter(|| {
+                    generate_chunk_mesh(
+                        black_box(IVec2::new(3, -2)),
+                        config.chunk_size,
+                        subdivisions,
+                        &noise,
+                        &config,
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, chunk_mesh);
+criterion_main!(benches);
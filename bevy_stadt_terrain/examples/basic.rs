@@ -44,9 +44,10 @@ fn setup(mut commands: Commands) {
     ));
 
     // Ambient light
-    commands.insert_resource(AmbientLight {
+    commands.insert_resource(GlobalAmbientLight {
         color: Color::srgb(0.9, 0.95, 1.0),
         brightness: 200.0,
+        ..default()
     });
 }
 
@@ -1,34 +1,130 @@
 //! Terrain configuration and builder pattern
 
 use bevy::prelude::*;
+use std::fmt;
+use std::ops::Range;
 
 /// Main configuration for the terrain system
-#[derive(Resource, Clone, Debug)]
+///
+/// Changing the resource at runtime rebuilds every chunk with the new settings.
+#[derive(Resource, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TerrainConfig {
     /// Size of each terrain chunk in world units
     pub chunk_size: f32,
     /// Number of chunks to render in each direction from camera
     pub render_distance: i32,
+    /// Terrain-local XZ area the world is limited to (None = stream endlessly)
+    pub world_bounds: Option<Rect>,
+    /// Height reported by height queries outside `world_bounds`
+    pub world_floor: f32,
+    /// Vertical offset of the whole terrain surface, applied to chunk transforms and height queries
+    pub base_elevation: f32,
     /// Maximum terrain height
     pub max_height: f32,
+    /// Multiplier on final terrain heights, e.g. 2.0 to double relief for dramatic views
+    ///
+    /// Heights are scaled about `y = 0`, so the water line stays put. Applies to
+    /// noise terrain and to closure and image sources alike.
+    pub vertical_exaggeration: f32,
     /// Sea level height (terrain below this may be considered underwater)
     pub water_level: f32,
     /// Height threshold for mountain biome (0.0-1.0 normalized)
     pub mountain_threshold: f32,
     /// Domain warp strength for organic terrain shapes
     pub warp_strength: f32,
-    /// Depth of skirts below chunk edges to hide LOD seams
+    /// Offset added to world XZ before sampling noise
+    ///
+    /// Shift it by the same amount as the world when recentering a large world,
+    /// so the terrain pattern stays put relative to everything else.
+    pub noise_origin: Vec2,
+    /// Linear map applied to the warped noise coordinates
+    ///
+    /// Scale, rotate or shear the noise domain for directional features:
+    /// shrinking one axis stretches ridges and dunes along it.
+    pub domain_transform: Mat2,
+    /// Quantize noise terrain into this many elevation bands (None = smooth terrain)
+    pub terrace_steps: Option<u32>,
+    /// Flatness of terrace bands; 1 leaves the terrain smooth, higher values give sharper steps
+    pub terrace_sharpness: f32,
+    /// Depth of skirts below chunk edges to hide LOD seams (0 = no skirts)
     pub skirt_depth: f32,
-    /// Distance thresholds for LOD transitions [near, mid, far]
-    pub lod_distances: [f32; 3],
-    /// Mesh subdivisions for each LOD level [highest, high, medium, low]
-    pub lod_subdivisions: [u32; 4],
+    /// Override color for skirt vertices (None = inherit the edge vertex color)
+    ///
+    /// Skirts always copy the normal of the edge vertex above them, so they
+    /// shade like the surface rather than as near-vertical walls.
+    pub skirt_color: Option<[f32; 4]>,
+    /// How cracks between chunks of different detail are hidden
+    pub seam_mode: SeamMode,
+    /// Generate the per-vertex morph height attribute used for geomorphing
+    pub generate_morph: bool,
+    /// Generate a second UV channel holding world-space XZ coordinates
+    pub generate_uv1: bool,
+    /// Multiplier on the world XZ stored in UV1, e.g. 0.1 for one repeat every 10 units
+    ///
+    /// The terrain shader reads UV1 for its detail noise overlay; splat layers
+    /// always tile in world space.
+    pub detail_uv_scale: f32,
+    /// Feed the forward pass 16-bit normalized chunk positions (per-chunk material)
+    pub quantize_positions: bool,
+    /// Distance-based atmospheric tint applied to terrain vertex colors (None = disabled)
+    pub aerial_perspective: Option<AerialConfig>,
+    /// Vertex color stops and blend thresholds for the built-in biome coloring
+    pub biome_palette: BiomePalette,
+    /// Triplanar blend sharpness for splatted textures on steep slopes (0 = planar XZ only)
+    pub triplanar_sharpness: f32,
+    /// Attenuate noise layers finer than each LOD's grid step to avoid aliasing
+    pub band_limit_noise: bool,
+    /// How each grid quad is split into two triangles
+    pub triangulation: TriangulationPattern,
+    /// Distance thresholds between consecutive LOD levels, nearest first
+    ///
+    /// Holds one entry fewer than `lod_subdivisions`.
+    pub lod_distances: Vec<f32>,
+    /// How node detail is chosen: distance thresholds or projected screen error
+    pub lod_metric: LodMetric,
+    /// Mesh subdivisions for each LOD level, highest detail first
+    ///
+    /// Must be powers of two: geomorphing blends every other vertex toward
+    /// its neighbors, which only lines up with the coarser grid when each
+    /// level halves cleanly. See [`TerrainConfig::validate`].
+    pub lod_subdivisions: Vec<u32>,
+    /// Multiplier on camera distance when ranking mesh requests, per LOD level
+    ///
+    /// Lower weights stream sooner, so the default `[1.0, 1.25, 1.5, 2.0]`
+    /// lets a full-detail chunk beat a coarse one up to twice as close. LODs
+    /// past the end use the last weight; an empty list ranks by distance alone.
+    pub lod_priority_bias: Vec<f32>,
+    /// Whether meshes are generated on the task pool or inline on the main thread
+    pub generation_mode: GenerationMode,
     /// Maximum number of concurrent mesh generation tasks
     pub max_concurrent_tasks: usize,
+    /// Optional cap on the projected vertex total of in-flight mesh tasks
+    pub max_concurrent_vertices: Option<usize>,
+    /// Maximum chunk entities spawned per frame; the rest wait for later frames (0 = unlimited)
+    pub max_spawns_per_frame: usize,
+    /// Radius around the camera that must be streamed in before `terrain_ready` holds (0 = everything selected)
+    pub ready_radius: f32,
+    /// Seconds a chunk that left the view lingers before it is despawned (0 = immediately)
+    ///
+    /// A chunk selected again within the delay is kept as is, so a camera
+    /// hovering at the edge of the view doesn't regenerate it over and over.
+    pub chunk_despawn_delay_secs: f32,
+    /// Seconds chunks queued by a warmup wait for the camera to select them before they unload
+    pub warmup_ttl_secs: f32,
+    /// Recently used chunk meshes kept around for reuse when a chunk returns to a LOD (0 = off)
+    pub mesh_cache_size: usize,
     /// Hysteresis buffer for LOD transitions (percentage of distance threshold)
     pub lod_hysteresis: f32,
     /// Maximum quadtree depth
     pub max_quadtree_depth: u8,
+    /// Smallest mesh chunk the quadtree creates; finer detail adds subdivisions instead (0 = off)
+    pub min_mesh_chunk_size: f32,
+    /// Stream new areas at the coarsest LOD first, then refine one LOD at a time to the target
+    pub progressive_refinement: bool,
+    /// Coarsest LOD level that still gets a physics collider
+    pub collider_max_lod: u8,
 }
 
 impl Default for TerrainConfig {
@@ -36,16 +132,47 @@ impl Default for TerrainConfig {
         Self {
             chunk_size: 100.0,
             render_distance: 50,
+            world_bounds: None,
+            world_floor: 0.0,
+            base_elevation: 0.0,
             max_height: 180.0,
+            vertical_exaggeration: 1.0,
             water_level: 15.0,
             mountain_threshold: 0.6,
             warp_strength: 60.0,
+            noise_origin: Vec2::ZERO,
+            domain_transform: Mat2::IDENTITY,
+            terrace_steps: None,
+            terrace_sharpness: 4.0,
             skirt_depth: 50.0,
-            lod_distances: [300.0, 1000.0, 2500.0],
-            lod_subdivisions: [64, 32, 16, 8],
+            skirt_color: None,
+            seam_mode: SeamMode::Skirts,
+            generate_morph: true,
+            generate_uv1: false,
+            detail_uv_scale: 1.0,
+            quantize_positions: false,
+            aerial_perspective: None,
+            biome_palette: BiomePalette::default(),
+            triplanar_sharpness: 0.0,
+            band_limit_noise: false,
+            triangulation: TriangulationPattern::Fixed,
+            lod_distances: vec![300.0, 1000.0, 2500.0],
+            lod_metric: LodMetric::Distance,
+            lod_subdivisions: vec![64, 32, 16, 8],
+            lod_priority_bias: vec![1.0, 1.25, 1.5, 2.0],
+            generation_mode: GenerationMode::Async,
             max_concurrent_tasks: 8,
+            max_spawns_per_frame: 4,
+            ready_radius: 0.0,
+            chunk_despawn_delay_secs: 0.0,
+            warmup_ttl_secs: 10.0,
+            mesh_cache_size: 64,
+            max_concurrent_vertices: None,
             lod_hysteresis: 0.15,
             max_quadtree_depth: 8,
+            min_mesh_chunk_size: 0.0,
+            progressive_refinement: false,
+            collider_max_lod: 1,
         }
     }
 }
@@ -60,6 +187,245 @@ impl TerrainConfig {
     pub fn builder() -> TerrainConfigBuilder {
         TerrainConfigBuilder::default()
     }
+
+    /// Distance multiplier for ranking requests at `lod` (see `lod_priority_bias`)
+    pub fn lod_priority_weight(&self, lod: u8) -> f32 {
+        self.lod_priority_bias
+            .get(lod as usize)
+            .or(self.lod_priority_bias.last())
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Check settings the mesh generator can't honor
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.lod_subdivisions.is_empty() {
+            return Err(ConfigError::NoLodLevels);
+        }
+        for (lod, &subdivisions) in self.lod_subdivisions.iter().enumerate() {
+            if !subdivisions.is_power_of_two() {
+                return Err(ConfigError::SubdivisionsNotPowerOfTwo { lod, subdivisions });
+            }
+        }
+        if self.vertical_exaggeration.is_nan() || self.vertical_exaggeration <= 0.0 {
+            return Err(ConfigError::NonPositiveVerticalExaggeration);
+        }
+        for (lod, &bias) in self.lod_priority_bias.iter().enumerate() {
+            if bias.is_nan() || bias < 0.0 {
+                return Err(ConfigError::InvalidLodPriorityBias { lod });
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a config from RON; omitted fields keep their default values
+    #[cfg(feature = "serde")]
+    pub fn from_ron_str(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+}
+
+/// Invalid [`TerrainConfig`] settings
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `lod_subdivisions` is empty
+    NoLodLevels,
+    /// A LOD level's subdivisions can't be halved down to the next level
+    SubdivisionsNotPowerOfTwo { lod: usize, subdivisions: u32 },
+    /// `vertical_exaggeration` is zero, negative or NaN
+    NonPositiveVerticalExaggeration,
+    /// A `lod_priority_bias` weight is negative or NaN, which would scramble request order
+    InvalidLodPriorityBias { lod: usize },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NoLodLevels => write!(f, "lod_subdivisions needs at least one level"),
+            ConfigError::SubdivisionsNotPowerOfTwo { lod, subdivisions } => write!(
+                f,
+                "lod_subdivisions[{lod}] is {subdivisions}, but geomorphing needs a power of two"
+            ),
+            ConfigError::NonPositiveVerticalExaggeration => {
+                write!(f, "vertical_exaggeration must be positive")
+            }
+            ConfigError::InvalidLodPriorityBias { lod } => {
+                write!(f, "lod_priority_bias[{lod}] must be a non-negative number")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Diagonal choice when splitting grid quads into triangles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriangulationPattern {
+    /// Every quad uses the same diagonal (deterministic, slight directional bias)
+    #[default]
+    Fixed,
+    /// Pick the diagonal whose endpoints differ least in height, following ridges and valleys
+    Adaptive,
+    /// Alternate diagonals in a checkerboard to cancel out the bias
+    Checkerboard,
+}
+
+/// Where chunk meshes are generated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GenerationMode {
+    /// Generate on the `AsyncComputeTaskPool`, up to `max_concurrent_tasks` at once
+    #[default]
+    Async,
+    /// Generate the highest-priority meshes inline each frame, up to `max_spawns_per_frame`
+    ///
+    /// For targets without worker threads (e.g. wasm), where pool tasks may
+    /// never make progress. Large chunks stall the frame they are built in.
+    Synchronous,
+}
+
+/// Metric driving quadtree subdivision and LOD selection
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LodMetric {
+    /// Compare camera distance against `lod_distances`
+    #[default]
+    Distance,
+    /// Keep each node's grid spacing, projected to the screen, under `pixel_error` pixels
+    ///
+    /// Accounts for the camera's vertical FOV and viewport height, so detail
+    /// follows what is actually visible regardless of window size. Chunks
+    /// don't geomorph, since LOD switch distances vary with node size.
+    ScreenSpaceError { pixel_error: f32 },
+}
+
+/// Aerial perspective settings blending distant terrain toward a horizon color
+///
+/// Evaluated per vertex in the terrain shader using the camera position from
+/// the view uniform.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AerialConfig {
+    /// Linear RGBA color distant terrain fades toward
+    pub color: [f32; 4],
+    /// Camera distance where the tint starts
+    pub start_distance: f32,
+    /// Camera distance where the tint reaches full strength
+    pub end_distance: f32,
+    /// Maximum blend toward the fog color (0.0-1.0)
+    pub max_strength: f32,
+    /// Exponential thinning of the tint with altitude (0 = uniform haze)
+    pub height_falloff: f32,
+}
+
+impl Default for AerialConfig {
+    fn default() -> Self {
+        Self {
+            color: [0.62, 0.72, 0.85, 1.0],
+            start_distance: 500.0,
+            end_distance: 5000.0,
+            max_strength: 0.8,
+            height_falloff: 0.0,
+        }
+    }
+}
+
+/// How cracks between neighboring chunks of different detail are hidden
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeamMode {
+    /// Hang skirts of `skirt_depth` below every chunk edge
+    #[default]
+    Skirts,
+    /// Snap edge vertices onto the coarser neighbor's edge; no skirts are generated
+    Stitch,
+}
+
+/// Biome vertex colors and the thresholds that blend between them
+///
+/// Height thresholds are normalized from the sea floor (0) to `max_height` (1),
+/// moisture runs from arid (0) to wet (1). Each range is a smoothstep from its
+/// start to its end.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BiomePalette {
+    /// Sea floor color (sRGB RGBA, like every color below)
+    pub deep_water: [f32; 4],
+    /// Seabed color near the shore
+    pub shallow_water: [f32; 4],
+    /// Beaches and arid lowlands
+    pub sand: [f32; 4],
+    /// Lowland grass in moderate moisture
+    pub grass_dry: [f32; 4],
+    /// Lowland grass in wet areas
+    pub grass_lush: [f32; 4],
+    /// Wettest lowlands
+    pub forest_tropical: [f32; 4],
+    /// Highland ground in moderate moisture
+    pub tundra: [f32; 4],
+    /// Wettest highlands
+    pub forest_boreal: [f32; 4],
+    /// Steep slopes low down, lightening toward `rock_grey` with height
+    pub rock_dark: [f32; 4],
+    /// Arid highlands, mountains below the snow line and high cliffs
+    pub rock_grey: [f32; 4],
+    /// Peaks above the snow line
+    pub snow: [f32; 4],
+    /// Height range blending deep into shallow water
+    pub water_depth: Range<f32>,
+    /// Height range blending water into land
+    pub shore: Range<f32>,
+    /// Lowland moisture ranges: sand to dry grass, dry to lush grass, lush grass to forest
+    pub lowland_moisture: [Range<f32>; 3],
+    /// Highland moisture ranges: bare rock to tundra, tundra to boreal forest
+    pub highland_moisture: [Range<f32>; 2],
+    /// Height range blending lowland into highland colors
+    pub lowland_to_highland: Range<f32>,
+    /// Height range blending highland into mountain colors
+    pub highland_to_mountain: Range<f32>,
+    /// Height range blending mountain rock into snow
+    pub snow_line: Range<f32>,
+    /// Normal Y range (flat to steep, so usually descending) blending into rock
+    pub steep_rock: Range<f32>,
+    /// Brightness variation applied from detail noise
+    pub detail_variation: f32,
+    /// Blend the (sRGB) color stops in linear light
+    ///
+    /// Mixing sRGB values directly darkens and grays out the middle of
+    /// transitions between distant hues, e.g. blue water into green grass.
+    /// The stops themselves come out unchanged either way.
+    pub linear_blend: bool,
+}
+
+impl Default for BiomePalette {
+    fn default() -> Self {
+        Self {
+            deep_water: [0.05, 0.15, 0.35, 1.0],
+            shallow_water: [0.15, 0.30, 0.50, 1.0],
+            sand: [0.82, 0.76, 0.58, 1.0],
+            grass_dry: [0.55, 0.60, 0.30, 1.0],
+            grass_lush: [0.22, 0.50, 0.12, 1.0],
+            forest_tropical: [0.08, 0.35, 0.08, 1.0],
+            tundra: [0.50, 0.53, 0.40, 1.0],
+            forest_boreal: [0.12, 0.30, 0.18, 1.0],
+            rock_dark: [0.25, 0.23, 0.21, 1.0],
+            rock_grey: [0.45, 0.45, 0.47, 1.0],
+            snow: [0.93, 0.93, 0.96, 1.0],
+            water_depth: 0.0..0.1,
+            shore: 0.08..0.14,
+            lowland_moisture: [0.2..0.4, 0.5..0.7, 0.75..0.9],
+            highland_moisture: [0.3..0.5, 0.6..0.8],
+            lowland_to_highland: 0.30..0.50,
+            highland_to_mountain: 0.60..0.80,
+            snow_line: 0.75..0.90,
+            steep_rock: 0.75..0.60,
+            detail_variation: 0.06,
+            linear_blend: true,
+        }
+    }
 }
 
 /// Builder for creating customized TerrainConfig
@@ -81,12 +447,36 @@ impl TerrainConfigBuilder {
         self
     }
 
+    /// Limit streaming to a finite area, e.g. a fixed-size island
+    pub fn world_bounds(mut self, bounds: Rect) -> Self {
+        self.config.world_bounds = Some(bounds);
+        self
+    }
+
+    /// Set the height reported outside the world bounds
+    pub fn world_floor(mut self, height: f32) -> Self {
+        self.config.world_floor = height;
+        self
+    }
+
+    /// Raise or lower the whole terrain surface without changing its shape
+    pub fn base_elevation(mut self, elevation: f32) -> Self {
+        self.config.base_elevation = elevation;
+        self
+    }
+
     /// Set the maximum terrain height
     pub fn max_height(mut self, height: f32) -> Self {
         self.config.max_height = height;
         self
     }
 
+    /// Exaggerate relief without rescaling the height data (see [`TerrainConfig::vertical_exaggeration`])
+    pub fn vertical_exaggeration(mut self, factor: f32) -> Self {
+        self.config.vertical_exaggeration = factor;
+        self
+    }
+
     /// Set the water level
     pub fn water_level(mut self, level: f32) -> Self {
         self.config.water_level = level;
@@ -99,27 +489,132 @@ impl TerrainConfigBuilder {
         self
     }
 
+    /// Offset world XZ before sampling noise (see [`TerrainConfig::noise_origin`])
+    pub fn noise_origin(mut self, origin: Vec2) -> Self {
+        self.config.noise_origin = origin;
+        self
+    }
+
+    /// Scale, rotate or shear the noise domain (see [`TerrainConfig::domain_transform`])
+    pub fn domain_transform(mut self, transform: Mat2) -> Self {
+        self.config.domain_transform = transform;
+        self
+    }
+
     /// Set the domain warp strength
     pub fn warp_strength(mut self, strength: f32) -> Self {
         self.config.warp_strength = strength;
         self
     }
 
+    /// Step the terrain into `steps` elevation bands joined by shoulders of the given sharpness
+    pub fn terraces(mut self, steps: u32, sharpness: f32) -> Self {
+        self.config.terrace_steps = Some(steps);
+        self.config.terrace_sharpness = sharpness;
+        self
+    }
+
     /// Set the skirt depth for hiding LOD seams
     pub fn skirt_depth(mut self, depth: f32) -> Self {
         self.config.skirt_depth = depth;
         self
     }
 
-    /// Set the LOD distance thresholds [near, mid, far]
-    pub fn lod_distances(mut self, distances: [f32; 3]) -> Self {
-        self.config.lod_distances = distances;
+    /// Set a fixed color for skirt vertices so visible seams read as shadow
+    pub fn skirt_color(mut self, color: [f32; 4]) -> Self {
+        self.config.skirt_color = Some(color);
+        self
+    }
+
+    /// Enable or disable the morph height attribute (disable for materials that don't geomorph)
+    pub fn generate_morph(mut self, enabled: bool) -> Self {
+        self.config.generate_morph = enabled;
+        self
+    }
+
+    /// Emit world-space XZ as UV1 for detail masks, decals, or baked lighting
+    pub fn generate_uv1(mut self, enabled: bool) -> Self {
+        self.config.generate_uv1 = enabled;
+        self
+    }
+
+    /// Scale the world XZ written to UV1 (see [`TerrainConfig::detail_uv_scale`])
+    pub fn detail_uv_scale(mut self, scale: f32) -> Self {
+        self.config.detail_uv_scale = scale;
+        self
+    }
+
+    /// Quantize chunk vertex positions read by the forward pass
+    pub fn quantize_positions(mut self, enabled: bool) -> Self {
+        self.config.quantize_positions = enabled;
         self
     }
 
-    /// Set the LOD subdivisions [highest, high, medium, low]
-    pub fn lod_subdivisions(mut self, subdivisions: [u32; 4]) -> Self {
-        self.config.lod_subdivisions = subdivisions;
+    /// Enable distance-based aerial perspective tinting
+    pub fn aerial_perspective(mut self, aerial: AerialConfig) -> Self {
+        self.config.aerial_perspective = Some(aerial);
+        self
+    }
+
+    /// Choose how LOD seams between chunks are hidden
+    pub fn seam_mode(mut self, mode: SeamMode) -> Self {
+        self.config.seam_mode = mode;
+        self
+    }
+
+    /// Replace the built-in biome vertex colors
+    pub fn biome_palette(mut self, palette: BiomePalette) -> Self {
+        self.config.biome_palette = palette;
+        self
+    }
+
+    /// Project splatted textures along all three axes, blending by `sharpness`
+    ///
+    /// Higher values narrow the transitions between projections; 4-8 works well.
+    pub fn triplanar(mut self, sharpness: f32) -> Self {
+        self.config.triplanar_sharpness = sharpness;
+        self
+    }
+
+    /// Band-limit noise per LOD so coarse meshes skip detail they can't represent
+    pub fn band_limit_noise(mut self, enabled: bool) -> Self {
+        self.config.band_limit_noise = enabled;
+        self
+    }
+
+    /// Set how grid quads are split into triangles
+    pub fn triangulation(mut self, pattern: TriangulationPattern) -> Self {
+        self.config.triangulation = pattern;
+        self
+    }
+
+    /// Set the LOD distance thresholds, nearest first (one fewer than the LOD levels)
+    pub fn lod_distances(mut self, distances: impl Into<Vec<f32>>) -> Self {
+        self.config.lod_distances = distances.into();
+        self
+    }
+
+    /// Choose between distance and screen-space error LOD selection
+    pub fn lod_metric(mut self, metric: LodMetric) -> Self {
+        self.config.lod_metric = metric;
+        self
+    }
+
+    /// Set the mesh subdivisions per LOD level, highest detail first
+    pub fn lod_subdivisions(mut self, subdivisions: impl Into<Vec<u32>>) -> Self {
+        self.config.lod_subdivisions = subdivisions.into();
+        self
+    }
+
+    /// Set the per-LOD distance multipliers used to rank mesh requests
+    pub fn lod_priority_bias(mut self, bias: impl Into<Vec<f32>>) -> Self {
+        self.config.lod_priority_bias = bias.into();
+        self
+    }
+
+    /// Choose between task pool and inline mesh generation
+    pub fn generation_mode(mut self, mode: GenerationMode) -> Self {
+        self.config.generation_mode = mode;
         self
     }
 
@@ -129,6 +624,42 @@ impl TerrainConfigBuilder {
         self
     }
 
+    /// Limit how many chunk entities are spawned per frame to spread out mesh uploads
+    pub fn max_spawns_per_frame(mut self, max: usize) -> Self {
+        self.config.max_spawns_per_frame = max;
+        self
+    }
+
+    /// Only wait for chunks within `radius` of the camera before reporting the terrain ready
+    pub fn ready_radius(mut self, radius: f32) -> Self {
+        self.config.ready_radius = radius;
+        self
+    }
+
+    /// Keep chunks that left the view around for `secs` in case they come back
+    pub fn chunk_despawn_delay_secs(mut self, secs: f32) -> Self {
+        self.config.chunk_despawn_delay_secs = secs;
+        self
+    }
+
+    /// Unload warmed chunks the camera hasn't reached after `secs`
+    pub fn warmup_ttl_secs(mut self, secs: f32) -> Self {
+        self.config.warmup_ttl_secs = secs;
+        self
+    }
+
+    /// Keep up to `size` recent chunk meshes for reuse when the camera moves back and forth
+    pub fn mesh_cache_size(mut self, size: usize) -> Self {
+        self.config.mesh_cache_size = size;
+        self
+    }
+
+    /// Limit in-flight mesh generation by projected vertex count instead of task count alone
+    pub fn max_concurrent_vertices(mut self, max: usize) -> Self {
+        self.config.max_concurrent_vertices = Some(max);
+        self
+    }
+
     /// Set the LOD hysteresis buffer (percentage of distance threshold)
     pub fn lod_hysteresis(mut self, hysteresis: f32) -> Self {
         self.config.lod_hysteresis = hysteresis;
@@ -141,10 +672,34 @@ impl TerrainConfigBuilder {
         self
     }
 
+    /// Stop creating chunk entities below this size and raise mesh subdivisions instead
+    pub fn min_mesh_chunk_size(mut self, size: f32) -> Self {
+        self.config.min_mesh_chunk_size = size;
+        self
+    }
+
+    /// Reveal new areas coarse-first and refine them over the following frames
+    pub fn progressive_refinement(mut self, enabled: bool) -> Self {
+        self.config.progressive_refinement = enabled;
+        self
+    }
+
+    /// Only give chunks at or below this LOD level a physics collider
+    pub fn collider_max_lod(mut self, lod: u8) -> Self {
+        self.config.collider_max_lod = lod;
+        self
+    }
+
     /// Build the TerrainConfig
     pub fn build(self) -> TerrainConfig {
         self.config
     }
+
+    /// Build the TerrainConfig, rejecting settings that [`TerrainConfig::validate`] flags
+    pub fn try_build(self) -> Result<TerrainConfig, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
 }
 
 #[cfg(test)]
@@ -170,4 +725,64 @@ mod tests {
         assert_eq!(config.render_distance, 100);
         assert_eq!(config.max_height, 500.0);
     }
+
+    #[test]
+    fn test_try_build_rejects_non_power_of_two_subdivisions() {
+        assert!(TerrainConfig::builder().try_build().is_ok());
+
+        let error = TerrainConfig::builder()
+            .lod_subdivisions([48, 24, 12, 6])
+            .try_build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ConfigError::SubdivisionsNotPowerOfTwo {
+                lod: 0,
+                subdivisions: 48
+            }
+        );
+        assert!(error.to_string().contains("lod_subdivisions[0]"));
+
+        assert_eq!(
+            TerrainConfig::builder()
+                .lod_subdivisions(Vec::new())
+                .try_build(),
+            Err(ConfigError::NoLodLevels)
+        );
+
+        for exaggeration in [0.0, -1.0, f32::NAN] {
+            assert_eq!(
+                TerrainConfig::builder()
+                    .vertical_exaggeration(exaggeration)
+                    .try_build(),
+                Err(ConfigError::NonPositiveVerticalExaggeration)
+            );
+        }
+
+        for bias in [-1.0, f32::NAN] {
+            assert_eq!(
+                TerrainConfig::builder()
+                    .lod_priority_bias([1.0, bias])
+                    .try_build(),
+                Err(ConfigError::InvalidLodPriorityBias { lod: 1 })
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ron_round_trip() {
+        let ron = ron::to_string(&TerrainConfig::default()).unwrap();
+        let config = TerrainConfig::from_ron_str(&ron).unwrap();
+        assert_eq!(ron::to_string(&config).unwrap(), ron);
+        assert_eq!(config.biome_palette, BiomePalette::default());
+
+        // Partial configs fill in defaults
+        let config = TerrainConfig::from_ron_str("(max_height: 320.0, seam_mode: Stitch)").unwrap();
+        assert_eq!(config.max_height, 320.0);
+        assert_eq!(config.seam_mode, SeamMode::Stitch);
+        assert_eq!(config.chunk_size, 100.0);
+
+        assert!(TerrainConfig::from_ron_str("(max_height: \"tall\")").is_err());
+    }
 }
@@ -0,0 +1,374 @@
+//! Debug overlay for the terrain quadtree
+//!
+//! Draws every selected quadtree node as a wireframe box colored by LOD level,
+//! optionally labelled with its node ID. Add [`TerrainDebugPlugin`] and toggle
+//! [`TerrainDebugSettings`] at runtime; with the overlay disabled the systems
+//! do nothing. [`TerrainDebugMode`] switches the chunks themselves to a
+//! wireframe, LOD heatmap or normal view.
+
+use crate::config::TerrainConfig;
+use crate::material::{DebugUniform, TerrainMaterial, TerrainMaterialHandle};
+use crate::quadtree::TerrainQuadtree;
+use crate::{Chunk, TerrainCamera};
+use bevy::color::palettes::css;
+use bevy::math::bounding::{Aabb3d, BoundingVolume};
+use bevy::pbr::wireframe::Wireframe;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Plugin adding the quadtree debug overlay
+#[derive(Default)]
+pub struct TerrainDebugPlugin {
+    /// Initial overlay settings
+    pub settings: TerrainDebugSettings,
+}
+
+impl Plugin for TerrainDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.settings.clone())
+            .init_resource::<TerrainDebugMode>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_quadtree_gizmos
+                        .run_if(|settings: Res<TerrainDebugSettings>| settings.enabled),
+                    update_node_labels,
+                    apply_terrain_debug_mode,
+                ),
+            );
+    }
+}
+
+/// Runtime switches for the quadtree debug overlay
+#[derive(Resource, Clone, Debug)]
+pub struct TerrainDebugSettings {
+    /// Draw selected nodes as wireframe boxes
+    pub enabled: bool,
+    /// Show each selected node's ID on screen
+    pub label_node_ids: bool,
+    /// Box colors per LOD level [highest, high, medium, low]
+    pub lod_colors: [Color; 4],
+}
+
+impl Default for TerrainDebugSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            label_node_ids: false,
+            lod_colors: [
+                css::RED.into(),
+                css::ORANGE.into(),
+                css::YELLOW.into(),
+                css::LIME.into(),
+            ],
+        }
+    }
+}
+
+/// How terrain chunks are drawn, for inspecting tessellation and LOD transitions
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TerrainDebugMode {
+    /// Regular shading
+    #[default]
+    Off,
+    /// Triangle edges drawn over the shaded terrain (needs Bevy's `WireframePlugin`)
+    Wireframe,
+    /// Unlit chunks colored by LOD level with [`TerrainDebugSettings::lod_colors`]
+    LodHeatmap,
+    /// World-space normals mapped to RGB
+    NormalView,
+}
+
+impl TerrainDebugMode {
+    /// Shader uniform for a chunk at `lod` in this mode
+    fn uniform(self, settings: &TerrainDebugSettings, lod: u32) -> DebugUniform {
+        let view = match self {
+            TerrainDebugMode::Off | TerrainDebugMode::Wireframe => 0,
+            TerrainDebugMode::LodHeatmap => 1,
+            TerrainDebugMode::NormalView => 2,
+        };
+        DebugUniform {
+            lod_color: settings.lod_colors[(lod as usize).min(3)]
+                .to_linear()
+                .to_vec4(),
+            view,
+        }
+    }
+}
+
+/// Marker for on-screen node ID labels, keyed by node ID
+#[derive(Component)]
+pub struct NodeLabel(pub u64);
+
+/// System: Draw each selected quadtree node as a box spanning the terrain height range
+pub fn draw_quadtree_gizmos(
+    settings: Res<TerrainDebugSettings>,
+    config: Res<TerrainConfig>,
+    quadtree: Res<TerrainQuadtree>,
+    mut gizmos: Gizmos,
+) {
+    let min_y = -config.water_level * config.vertical_exaggeration;
+    let max_y = config.max_height * config.vertical_exaggeration;
+
+    for node in quadtree.collect_selected_nodes() {
+        let center = node.bounds.center();
+        // Inset slightly so neighboring boxes don't draw over each other
+        let half = node.bounds.half_size() * 0.98;
+        let aabb = Aabb3d::new(
+            Vec3::new(center.x, (min_y + max_y) * 0.5, center.y),
+            Vec3::new(half.x, (max_y - min_y) * 0.5, half.y),
+        );
+        let color = settings.lod_colors[(node.lod_level as usize).min(3)];
+        gizmos.aabb_3d(aabb, Transform::IDENTITY, color);
+    }
+}
+
+/// System: Keep one screen-space ID label per selected node
+///
+/// Labels are removed when labelling or the overlay is turned off.
+pub fn update_node_labels(
+    mut commands: Commands,
+    settings: Res<TerrainDebugSettings>,
+    config: Res<TerrainConfig>,
+    quadtree: Res<TerrainQuadtree>,
+    camera_query: Query<(&Camera, &GlobalTransform, Has<TerrainCamera>)>,
+    mut labels: Query<(Entity, &NodeLabel, &mut Node, &mut Visibility)>,
+) {
+    if !(settings.enabled && settings.label_node_ids) {
+        for (entity, ..) in &labels {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let selected: HashMap<u64, Vec3> = quadtree
+        .collect_selected_nodes()
+        .iter()
+        .map(|node| {
+            let center = node.bounds.center();
+            (node.id, Vec3::new(center.x, config.max_height, center.y))
+        })
+        .collect();
+    let camera = camera_query
+        .iter()
+        .find(|(.., tagged)| *tagged)
+        .or_else(|| camera_query.single().ok());
+
+    let mut labelled = Vec::with_capacity(selected.len());
+    for (entity, label, mut node, mut visibility) in &mut labels {
+        let Some(&world_pos) = selected.get(&label.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        labelled.push(label.0);
+
+        match camera
+            .and_then(|(camera, transform, _)| camera.world_to_viewport(transform, world_pos).ok())
+        {
+            Some(screen_pos) => {
+                node.left = Val::Px(screen_pos.x);
+                node.top = Val::Px(screen_pos.y);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    for &id in selected.keys().filter(|id| !labelled.contains(id)) {
+        commands.spawn((
+            NodeLabel(id),
+            Text::new(id.to_string()),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// System: Draw the terrain chunks the way [`TerrainDebugMode`] asks for
+///
+/// Chunks sharing the terrain material move to one debug copy per LOD level
+/// and back when the mode is turned off. Quantized chunks already own their
+/// material, which is updated in place.
+pub fn apply_terrain_debug_mode(
+    mut commands: Commands,
+    mode: Res<TerrainDebugMode>,
+    settings: Res<TerrainDebugSettings>,
+    terrain_material: Res<TerrainMaterialHandle>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+    mut lod_materials: Local<HashMap<u32, Handle<TerrainMaterial>>>,
+    mut chunks: Query<(Entity, Ref<Chunk>, &mut MeshMaterial3d<TerrainMaterial>)>,
+) {
+    let Some(shared) = terrain_material.handle.clone() else {
+        return;
+    };
+    let refresh = mode.is_changed() || settings.is_changed();
+    if refresh {
+        for (&lod, handle) in lod_materials.iter() {
+            if let Some(material) = materials.get_mut(handle) {
+                material.extension.debug = mode.uniform(&settings, lod);
+            }
+        }
+    }
+
+    for (entity, chunk, mut material) in &mut chunks {
+        if !refresh && !chunk.is_added() {
+            continue;
+        }
+
+        if *mode == TerrainDebugMode::Wireframe {
+            commands.entity(entity).insert(Wireframe);
+        } else {
+            commands.entity(entity).remove::<Wireframe>();
+        }
+
+        let lod = chunk.current_lod;
+        let uniform = mode.uniform(&settings, lod);
+        let owned = material.0 != shared && !lod_materials.values().any(|h| *h == material.0);
+        if owned {
+            if let Some(own) = materials.get_mut(&material.0) {
+                own.extension.debug = uniform;
+            }
+        } else if uniform.view == 0 {
+            if material.0 != shared {
+                material.0 = shared.clone();
+            }
+        } else {
+            let handle = match lod_materials.get(&lod) {
+                Some(handle) => handle.clone(),
+                None => {
+                    let Some(mut copy) = materials.get(&shared).cloned() else {
+                        continue;
+                    };
+                    copy.extension.debug = uniform;
+                    let handle = materials.add(copy);
+                    lod_materials.insert(lod, handle.clone());
+                    handle
+                }
+            };
+            if material.0 != handle {
+                material.0 = handle;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_follow_selection_and_settings() {
+        let config = TerrainConfig::default();
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.update(Vec3::new(0.0, 50.0, 0.0), &config, |_, _| 0.0);
+        let selected = quadtree.collect_selected_nodes().len();
+        assert!(selected > 0);
+
+        let mut app = App::new();
+        app.insert_resource(config)
+            .insert_resource(quadtree)
+            .insert_resource(TerrainDebugSettings::default())
+            .add_systems(Update, update_node_labels);
+
+        let label_count = |app: &mut App| {
+            app.world_mut()
+                .query::<&NodeLabel>()
+                .iter(app.world())
+                .count()
+        };
+
+        // Labels are opt-in
+        app.update();
+        assert_eq!(label_count(&mut app), 0);
+
+        app.world_mut()
+            .resource_mut::<TerrainDebugSettings>()
+            .label_node_ids = true;
+        app.update();
+        app.update();
+        assert_eq!(label_count(&mut app), selected);
+
+        // Disabling the overlay removes them again
+        app.world_mut()
+            .resource_mut::<TerrainDebugSettings>()
+            .enabled = false;
+        app.update();
+        assert_eq!(label_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_debug_mode_swaps_chunk_materials() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<TerrainMaterial>()
+            .insert_resource(TerrainDebugSettings::default())
+            .init_resource::<TerrainDebugMode>()
+            .add_systems(Update, apply_terrain_debug_mode);
+        let shared = app
+            .world_mut()
+            .resource_mut::<Assets<TerrainMaterial>>()
+            .add(TerrainMaterial::default());
+        app.insert_resource(TerrainMaterialHandle {
+            handle: Some(shared.clone()),
+        });
+        let chunk = |lod| Chunk {
+            coords: IVec2::ZERO,
+            current_lod: lod,
+            node_id: lod as u64,
+            size: 100.0,
+        };
+        let near = app
+            .world_mut()
+            .spawn((chunk(0), MeshMaterial3d(shared.clone())))
+            .id();
+        let far = app
+            .world_mut()
+            .spawn((chunk(2), MeshMaterial3d(shared.clone())))
+            .id();
+
+        let debug_of = |app: &App, entity: Entity| {
+            let handle = &app
+                .world()
+                .get::<MeshMaterial3d<TerrainMaterial>>(entity)
+                .unwrap()
+                .0;
+            let materials = app.world().resource::<Assets<TerrainMaterial>>();
+            (
+                handle.clone(),
+                materials.get(handle).unwrap().extension.debug,
+            )
+        };
+
+        app.update();
+        assert_eq!(debug_of(&app, near).0, shared);
+
+        *app.world_mut().resource_mut::<TerrainDebugMode>() = TerrainDebugMode::LodHeatmap;
+        app.update();
+        let colors = TerrainDebugSettings::default().lod_colors;
+        let (near_handle, near_debug) = debug_of(&app, near);
+        let (far_handle, far_debug) = debug_of(&app, far);
+        assert_ne!(near_handle, shared);
+        assert_ne!(near_handle, far_handle);
+        assert_eq!(near_debug.view, 1);
+        assert_eq!(near_debug.lod_color, colors[0].to_linear().to_vec4());
+        assert_eq!(far_debug.lod_color, colors[2].to_linear().to_vec4());
+
+        *app.world_mut().resource_mut::<TerrainDebugMode>() = TerrainDebugMode::NormalView;
+        app.update();
+        assert_eq!(debug_of(&app, near).1.view, 2);
+
+        *app.world_mut().resource_mut::<TerrainDebugMode>() = TerrainDebugMode::Wireframe;
+        app.update();
+        assert_eq!(debug_of(&app, far).0, shared);
+        assert!(app.world().get::<Wireframe>(far).is_some());
+
+        *app.world_mut().resource_mut::<TerrainDebugMode>() = TerrainDebugMode::Off;
+        app.update();
+        assert_eq!(debug_of(&app, far).0, shared);
+        assert!(app.world().get::<Wireframe>(far).is_none());
+        assert_eq!(debug_of(&app, far).1, DebugUniform::default());
+    }
+}
@@ -0,0 +1,179 @@
+//! Runtime height editing
+//!
+//! [`EditableHeightmap`] layers sparse per-cell height deltas over any
+//! [`HeightmapSource`], e.g. for an in-game terrain editor. Brush strokes
+//! return a [`TerrainEdited`] message; write it so the streaming systems
+//! re-mesh the chunks under the stroke:
+//!
+//! ```ignore
+//! let terrain = Arc::new(EditableHeightmap::new(base, 1.0));
+//! commands.spawn(TerrainBundle::from_source(terrain.clone()));
+//!
+//! // Later, e.g. on mouse click
+//! edits.write(terrain.apply_brush(hit.xz(), 8.0, 2.0, Falloff::Smooth));
+//! ```
+
+use crate::heightmap::HeightmapSource;
+use crate::streaming::TerrainEdited;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// How brush strength fades from the center to the radius
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Falloff {
+    /// Full strength across the whole brush
+    Constant,
+    /// Linear fade to zero at the radius
+    Linear,
+    /// Smoothstep fade, flat at the center and the rim
+    #[default]
+    Smooth,
+}
+
+impl Falloff {
+    /// Brush weight at `t` = distance / radius (0 = center, 1 = rim)
+    pub fn weight(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Falloff::Constant => 1.0,
+            Falloff::Linear => 1.0 - t,
+            Falloff::Smooth => 1.0 - t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Heightmap source with editable height deltas on top of a base source
+///
+/// Deltas are stored on a grid of `cell_size` spacing and interpolated
+/// bilinearly, so only edited cells cost memory. Edits go through `&self`
+/// so the heightmap can be shared with in-flight mesh tasks via `Arc`.
+pub struct EditableHeightmap {
+    base: Box<dyn HeightmapSource>,
+    cell_size: f32,
+    deltas: RwLock<HashMap<IVec2, f32>>,
+}
+
+impl EditableHeightmap {
+    pub fn new(base: impl HeightmapSource, cell_size: f32) -> Self {
+        Self {
+            base: Box::new(base),
+            cell_size: cell_size.max(f32::EPSILON),
+            deltas: RwLock::default(),
+        }
+    }
+
+    /// Raise (positive `strength`) or lower the terrain around `center`
+    ///
+    /// Returns the message to write so the affected chunks are re-meshed.
+    pub fn apply_brush(
+        &self,
+        center: Vec2,
+        radius: f32,
+        strength: f32,
+        falloff: Falloff,
+    ) -> TerrainEdited {
+        let radius = radius.max(0.0);
+        let min = ((center - radius) / self.cell_size).floor().as_ivec2();
+        let max = ((center + radius) / self.cell_size).ceil().as_ivec2();
+
+        let mut deltas = self.deltas.write().unwrap();
+        for z in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, z);
+                let distance = (cell.as_vec2() * self.cell_size).distance(center);
+                if distance > radius {
+                    continue;
+                }
+                let t = if radius > 0.0 { distance / radius } else { 0.0 };
+                *deltas.entry(cell).or_default() += strength * falloff.weight(t);
+            }
+        }
+
+        // Interpolation spreads each cell's delta up to one cell further out
+        TerrainEdited {
+            center,
+            radius: radius + self.cell_size * std::f32::consts::SQRT_2,
+        }
+    }
+
+    /// Interpolated height delta at a world position
+    pub fn delta_at(&self, x: f32, z: f32) -> f32 {
+        let deltas = self.deltas.read().unwrap();
+        self.interpolate(&deltas, x, z)
+    }
+
+    /// Remove all edits
+    pub fn clear(&self) {
+        self.deltas.write().unwrap().clear();
+    }
+
+    fn interpolate(&self, deltas: &HashMap<IVec2, f32>, x: f32, z: f32) -> f32 {
+        if deltas.is_empty() {
+            return 0.0;
+        }
+        let grid = Vec2::new(x, z) / self.cell_size;
+        let cell = grid.floor();
+        let (u, v) = (grid.x - cell.x, grid.y - cell.y);
+        let cell = cell.as_ivec2();
+        let delta = |offset: IVec2| deltas.get(&(cell + offset)).copied().unwrap_or(0.0);
+
+        let top = delta(IVec2::ZERO) * (1.0 - u) + delta(IVec2::X) * u;
+        let bottom = delta(IVec2::Y) * (1.0 - u) + delta(IVec2::ONE) * u;
+        top * (1.0 - v) + bottom * v
+    }
+}
+
+impl HeightmapSource for EditableHeightmap {
+    fn sample(&self, x: f32, z: f32) -> f32 {
+        self.base.sample(x, z) + self.delta_at(x, z)
+    }
+
+    fn sample_many(&self, points: &[Vec2], out: &mut Vec<f32>) {
+        self.base.sample_many(points, out);
+        let deltas = self.deltas.read().unwrap();
+        for (height, point) in out.iter_mut().zip(points) {
+            *height += self.interpolate(&deltas, point.x, point.y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heightmap::ProceduralHeightmap;
+
+    #[test]
+    fn test_brush_changes_heights_locally() {
+        let terrain = EditableHeightmap::new(ProceduralHeightmap::new(|x, _| x * 0.1), 1.0);
+        let near = terrain.sample(10.0, 5.0);
+        let far = terrain.sample(100.0, 100.0);
+
+        let edit = terrain.apply_brush(Vec2::new(10.0, 5.0), 4.0, 3.0, Falloff::Smooth);
+        assert_eq!(edit.center, Vec2::new(10.0, 5.0));
+        assert!(edit.radius >= 4.0);
+
+        assert!((terrain.sample(10.0, 5.0) - (near + 3.0)).abs() < 1e-4);
+        assert!(terrain.sample(11.5, 5.5) > 1.0 + 11.5 * 0.1);
+        assert_eq!(terrain.sample(100.0, 100.0), far);
+
+        // Batched sampling sees the same edits
+        let points = [Vec2::new(10.0, 5.0), Vec2::new(100.0, 100.0)];
+        let mut heights = Vec::new();
+        terrain.sample_many(&points, &mut heights);
+        assert_eq!(heights, vec![terrain.sample(10.0, 5.0), far]);
+
+        terrain.clear();
+        assert_eq!(terrain.sample(10.0, 5.0), near);
+    }
+
+    #[test]
+    fn test_falloff_weights() {
+        for falloff in [Falloff::Constant, Falloff::Linear, Falloff::Smooth] {
+            assert_eq!(falloff.weight(0.0), 1.0);
+        }
+        assert_eq!(Falloff::Linear.weight(1.0), 0.0);
+        assert_eq!(Falloff::Smooth.weight(1.0), 0.0);
+        assert_eq!(Falloff::Smooth.weight(0.5), 0.5);
+    }
+}
@@ -0,0 +1,277 @@
+//! Droplet-based hydraulic erosion for heightmap grids
+//!
+//! Simulates individual rain droplets running downhill: each picks up
+//! sediment while it speeds up on slopes and drops it where it slows down or
+//! its water evaporates. Unlike the per-sample erosion approximation in the
+//! noise terrain, this carves connected drainage channels, so it runs once on
+//! a height grid (e.g. an [`ImageHeightmap`](crate::heightmap::ImageHeightmap))
+//! before the grid is sampled.
+
+use bevy::prelude::*;
+
+/// Tuning for [`hydraulic_erode`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErosionParams {
+    /// Number of droplets to simulate
+    pub droplets: u32,
+    /// Maximum steps a droplet takes before it evaporates completely
+    pub max_lifetime: u32,
+    /// How much a droplet keeps its direction instead of following the slope (0-1)
+    pub inertia: f32,
+    /// Sediment a droplet can carry per unit of slope, speed, and water
+    pub sediment_capacity: f32,
+    /// Lower bound on the capacity so droplets still erode on flat ground
+    pub min_sediment_capacity: f32,
+    /// Fraction of free capacity filled by erosion each step (0-1)
+    pub erode_speed: f32,
+    /// Fraction of excess sediment deposited each step (0-1)
+    pub deposit_speed: f32,
+    /// Fraction of water evaporating each step (0-1)
+    pub evaporate_speed: f32,
+    /// Acceleration of droplets going downhill
+    pub gravity: f32,
+    /// Water a droplet starts with
+    pub initial_water: f32,
+    /// Speed a droplet starts with
+    pub initial_speed: f32,
+    /// Seed for droplet spawn positions
+    pub seed: u64,
+}
+
+impl Default for ErosionParams {
+    fn default() -> Self {
+        Self {
+            droplets: 50_000,
+            max_lifetime: 30,
+            inertia: 0.05,
+            sediment_capacity: 4.0,
+            min_sediment_capacity: 0.01,
+            erode_speed: 0.3,
+            deposit_speed: 0.3,
+            evaporate_speed: 0.01,
+            gravity: 4.0,
+            initial_water: 1.0,
+            initial_speed: 1.0,
+            seed: 0,
+        }
+    }
+}
+
+/// Erode a row-major height grid in place with droplet simulation
+///
+/// Material is moved, not created: everything a droplet erodes is deposited
+/// again along its path or where it stops, so the total height only changes
+/// by the sediment of droplets leaving the grid.
+pub fn hydraulic_erode(heights: &mut [f32], width: u32, height: u32, params: ErosionParams) {
+    let (width, height) = (width as usize, height as usize);
+    if width < 2 || height < 2 || heights.len() < width * height {
+        return;
+    }
+
+    let mut rng = SplitMix64(params.seed);
+    let max_x = (width - 1) as f32;
+    let max_z = (height - 1) as f32;
+    let in_bounds = |pos: Vec2| pos.x >= 0.0 && pos.x < max_x && pos.y >= 0.0 && pos.y < max_z;
+
+    for _ in 0..params.droplets {
+        let mut pos = Vec2::new(rng.next_f32() * max_x, rng.next_f32() * max_z);
+        let mut dir = Vec2::ZERO;
+        let mut speed = params.initial_speed;
+        let mut water = params.initial_water;
+        let mut sediment = 0.0;
+
+        for _ in 0..params.max_lifetime {
+            let (current_height, gradient) = height_and_gradient(heights, width, pos);
+
+            // Blend the previous direction with the downhill direction
+            dir = dir * params.inertia - gradient * (1.0 - params.inertia);
+            if dir.length_squared() < f32::EPSILON {
+                break;
+            }
+            dir = dir.normalize();
+
+            let next = pos + dir;
+            if !in_bounds(next) {
+                break;
+            }
+
+            let delta = height_and_gradient(heights, width, next).0 - current_height;
+            let capacity = (-delta * speed * water * params.sediment_capacity)
+                .max(params.min_sediment_capacity);
+
+            if sediment > capacity || delta > 0.0 {
+                // Uphill: fill the pit behind us, otherwise drop the excess
+                let amount = if delta > 0.0 {
+                    delta.min(sediment)
+                } else {
+                    (sediment - capacity) * params.deposit_speed
+                };
+                sediment -= amount;
+                distribute(heights, width, pos, amount);
+            } else {
+                // Never dig deeper than the drop to the next position
+                let amount = ((capacity - sediment) * params.erode_speed).min(-delta);
+                sediment += amount;
+                distribute(heights, width, pos, -amount);
+            }
+
+            speed = accelerate(speed, delta, params.gravity);
+            water *= 1.0 - params.evaporate_speed;
+            pos = next;
+        }
+
+        // Whatever the droplet still carries settles where it stopped
+        distribute(heights, width, pos, sediment);
+    }
+}
+
+/// Droplet speed after a step changing its height by `delta`
+///
+/// Dropping (negative `delta`) speeds the droplet up, climbing slows it down.
+fn accelerate(speed: f32, delta: f32, gravity: f32) -> f32 {
+    (speed * speed - delta * gravity).max(0.0).sqrt()
+}
+
+/// Bilinear height and gradient at a position inside the grid
+fn height_and_gradient(heights: &[f32], width: usize, pos: Vec2) -> (f32, Vec2) {
+    let (x, z) = (pos.x as usize, pos.y as usize);
+    let (u, v) = (pos.x - x as f32, pos.y - z as f32);
+    let index = z * width + x;
+
+    let nw = heights[index];
+    let ne = heights[index + 1];
+    let sw = heights[index + width];
+    let se = heights[index + width + 1];
+
+    let gradient = Vec2::new(
+        (ne - nw) * (1.0 - v) + (se - sw) * v,
+        (sw - nw) * (1.0 - u) + (se - ne) * u,
+    );
+    let height = nw * (1.0 - u) * (1.0 - v) + ne * u * (1.0 - v) + sw * (1.0 - u) * v + se * u * v;
+
+    (height, gradient)
+}
+
+/// Add `amount` to the four grid points around a position, weighted bilinearly
+fn distribute(heights: &mut [f32], width: usize, pos: Vec2, amount: f32) {
+    let (x, z) = (pos.x as usize, pos.y as usize);
+    let (u, v) = (pos.x - x as f32, pos.y - z as f32);
+    let index = z * width + x;
+
+    heights[index] += amount * (1.0 - u) * (1.0 - v);
+    heights[index + 1] += amount * u * (1.0 - v);
+    heights[index + width] += amount * (1.0 - u) * v;
+    heights[index + width + 1] += amount * u * v;
+}
+
+/// Small deterministic generator for droplet positions
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1)
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_droplet_speeds_up_on_constant_slope() {
+        let gravity = ErosionParams::default().gravity;
+        let mut speed = ErosionParams::default().initial_speed;
+        for _ in 0..10 {
+            let faster = accelerate(speed, -0.5, gravity);
+            assert!(faster > speed);
+            speed = faster;
+        }
+
+        // Climbing the same slope slows it back down
+        assert!(accelerate(speed, 0.5, gravity) < speed);
+        assert_eq!(accelerate(1.0, 10.0, gravity), 0.0);
+    }
+
+    /// Cone peaking at the grid center
+    fn peak(size: u32) -> Vec<f32> {
+        let center = Vec2::splat((size - 1) as f32 / 2.0);
+        (0..size * size)
+            .map(|i| {
+                let pos = Vec2::new((i % size) as f32, (i / size) as f32);
+                (1.0 - pos.distance(center) / center.x).max(0.0) * 20.0
+            })
+            .collect()
+    }
+
+    /// Standard deviation of heights sampled on a circle around the grid center
+    fn ring_spread(heights: &[f32], size: u32, radius: f32) -> f32 {
+        let center = Vec2::splat((size - 1) as f32 / 2.0);
+        let samples: Vec<f32> = (0..180)
+            .map(|i| {
+                let angle = i as f32 / 180.0 * std::f32::consts::TAU;
+                let pos = center + Vec2::from_angle(angle) * radius;
+                height_and_gradient(heights, size as usize, pos).0
+            })
+            .collect();
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let variance =
+            samples.iter().map(|h| (h - mean).powi(2)).sum::<f32>() / samples.len() as f32;
+        variance.sqrt()
+    }
+
+    #[test]
+    fn test_erosion_conserves_mass_and_carves_channels() {
+        let size = 64;
+        let mut heights = peak(size);
+        let mass_before: f32 = heights.iter().sum();
+        let spread_before = ring_spread(&heights, size, 15.0);
+
+        hydraulic_erode(
+            &mut heights,
+            size,
+            size,
+            ErosionParams {
+                droplets: 20_000,
+                ..default()
+            },
+        );
+
+        // Only droplets stopping at the border edge could lose sediment
+        let mass_after: f32 = heights.iter().sum();
+        assert!((mass_after - mass_before).abs() / mass_before < 0.01);
+
+        // The radially symmetric slope is now cut by gullies
+        let spread_after = ring_spread(&heights, size, 15.0);
+        assert!(spread_before < 0.05);
+        assert!(spread_after > spread_before + 0.1);
+    }
+
+    #[test]
+    fn test_erosion_is_deterministic() {
+        let erode = |seed| {
+            let mut heights = peak(32);
+            hydraulic_erode(
+                &mut heights,
+                32,
+                32,
+                ErosionParams {
+                    droplets: 2_000,
+                    seed,
+                    ..default()
+                },
+            );
+            heights
+        };
+        assert_eq!(erode(7), erode(7));
+        assert_ne!(erode(7), erode(8));
+    }
+}
@@ -3,11 +3,17 @@
 //! Provides the `HeightmapSource` trait and implementations for:
 //! - Procedural generation via closures
 //! - Multi-layer noise (Stadt-style terrain)
-//! - Image-based heightmaps (16-bit PNG)
+//! - Image-based heightmaps (grayscale PNG or raw `.r16`)
+//! - Blending two sources (e.g. an authored image over procedural terrain)
 
 use crate::config::TerrainConfig;
+use crate::erosion::{ErosionParams, hydraulic_erode};
 use bevy::prelude::*;
-use fastnoise_lite::{FastNoiseLite, FractalType, NoiseType};
+use fastnoise_lite::FastNoiseLite;
+// Re-exported so layers can be configured without depending on fastnoise-lite
+pub use fastnoise_lite::{FractalType, NoiseType};
+use image::{DynamicImage, ImageFormat};
+use std::fmt;
 use std::sync::Arc;
 
 /// Trait for height sampling at any world coordinate
@@ -16,16 +22,11 @@ pub trait HeightmapSource: Send + Sync + 'static {
     fn sample(&self, x: f32, z: f32) -> f32;
 
     /// Sample the surface normal at a given position
+    ///
+    /// Defaults to central differences `step` apart; sources that know their
+    /// gradient can return it directly.
     fn sample_normal(&self, x: f32, z: f32, step: f32) -> Vec3 {
-        let left = self.sample(x - step, z);
-        let right = self.sample(x + step, z);
-        let down = self.sample(x, z - step);
-        let up = self.sample(x, z + step);
-
-        let dx = (right - left) / (2.0 * step);
-        let dz = (up - down) / (2.0 * step);
-
-        Vec3::new(-dx, 1.0, -dz).normalize()
+        finite_difference_normal(self, x, z, step)
     }
 
     /// Sample the slope angle (0 = flat, 1 = vertical)
@@ -33,20 +34,112 @@ pub trait HeightmapSource: Send + Sync + 'static {
         let normal = self.sample_normal(x, z, step);
         1.0 - normal.y
     }
+
+    /// Sample heights at many (x, z) points, replacing the contents of `out`
+    ///
+    /// Sources with per-call setup can override this to hoist it out of the loop;
+    /// results must match [`Self::sample`] exactly.
+    fn sample_many(&self, points: &[Vec2], out: &mut Vec<f32>) {
+        out.clear();
+        out.extend(points.iter().map(|p| self.sample(p.x, p.y)));
+    }
+}
+
+/// Surface normal from central height differences `step` apart
+fn finite_difference_normal<S: HeightmapSource + ?Sized>(
+    source: &S,
+    x: f32,
+    z: f32,
+    step: f32,
+) -> Vec3 {
+    let left = source.sample(x - step, z);
+    let right = source.sample(x + step, z);
+    let down = source.sample(x, z - step);
+    let up = source.sample(x, z + step);
+
+    let dx = (right - left) / (2.0 * step);
+    let dz = (up - down) / (2.0 * step);
+
+    Vec3::new(-dx, 1.0, -dz).normalize()
+}
+
+/// Scale `source` heights about `y = 0` by `factor`, passing it through unchanged at 1.0
+pub(crate) fn exaggerate(
+    source: Arc<dyn HeightmapSource>,
+    factor: f32,
+) -> Arc<dyn HeightmapSource> {
+    if factor == 1.0 {
+        source
+    } else {
+        Arc::new(ExaggeratedHeightmap { source, factor })
+    }
+}
+
+/// Height source with its relief scaled (see [`TerrainConfig::vertical_exaggeration`])
+struct ExaggeratedHeightmap {
+    source: Arc<dyn HeightmapSource>,
+    factor: f32,
+}
+
+impl HeightmapSource for ExaggeratedHeightmap {
+    fn sample(&self, x: f32, z: f32) -> f32 {
+        self.source.sample(x, z) * self.factor
+    }
+
+    /// Steepens the inner normal instead of resampling
+    fn sample_normal(&self, x: f32, z: f32, step: f32) -> Vec3 {
+        let normal = self.source.sample_normal(x, z, step);
+        Vec3::new(normal.x * self.factor, normal.y, normal.z * self.factor).normalize()
+    }
+
+    fn sample_many(&self, points: &[Vec2], out: &mut Vec<f32>) {
+        self.source.sample_many(points, out);
+        out.iter_mut().for_each(|height| *height *= self.factor);
+    }
 }
 
 /// Component/Resource for storing the active heightmap
-#[derive(Component)]
+#[derive(Component, Clone)]
+// The config stays inline so matching on `Noise` keeps its public shape
+#[allow(clippy::large_enum_variant)]
 pub enum HeightmapHandle {
     /// Procedural heightmap using a closure or struct
-    Procedural(Box<dyn HeightmapSource>),
+    ///
+    /// Shared so mesh tasks can sample it off the main thread. Code that built
+    /// this from a `Box` can convert with `HeightmapHandle::from(boxed)`.
+    Procedural(Arc<dyn HeightmapSource>),
     /// Multi-layer noise heightmap (Stadt-style)
     Noise(Box<TerrainNoise>, TerrainConfig),
     /// Image-based heightmap
     Image(Arc<ImageHeightmap>),
 }
 
+impl From<Box<dyn HeightmapSource>> for HeightmapHandle {
+    fn from(source: Box<dyn HeightmapSource>) -> Self {
+        HeightmapHandle::Procedural(source.into())
+    }
+}
+
 impl HeightmapHandle {
+    /// Shareable height source for closure- and image-based terrain
+    ///
+    /// Returns `None` for noise terrain, which is meshed with its biome noise layers instead.
+    pub fn source(&self) -> Option<Arc<dyn HeightmapSource>> {
+        match self {
+            HeightmapHandle::Procedural(source) => Some(source.clone()),
+            HeightmapHandle::Noise(..) => None,
+            HeightmapHandle::Image(img) => Some(img.clone()),
+        }
+    }
+
+    /// [`Self::source`] with `config.vertical_exaggeration` applied
+    ///
+    /// Noise terrain already applies the exaggeration of its own config.
+    pub fn exaggerated_source(&self, config: &TerrainConfig) -> Option<Arc<dyn HeightmapSource>> {
+        self.source()
+            .map(|source| exaggerate(source, config.vertical_exaggeration))
+    }
+
     pub fn sample(&self, x: f32, z: f32) -> f32 {
         match self {
             HeightmapHandle::Procedural(source) => source.sample(x, z),
@@ -77,12 +170,101 @@ impl HeightmapHandle {
     }
 }
 
+/// Approximate the closest point on a heightfield surface to an arbitrary 3D point
+///
+/// Starts straight below `point` and repeatedly projects `point` onto the local
+/// tangent plane, keeping only steps that get closer. Exact for planes and
+/// converges quickly on smooth terrain, but it is a local search: on highly
+/// folded terrain it can settle on a nearby local minimum instead of the global one.
+pub fn closest_surface_point(point: Vec3, sample: impl Fn(f32, f32) -> f32) -> Vec3 {
+    const NORMAL_STEP: f32 = 0.5;
+    const ITERATIONS: usize = 16;
+
+    let surface_at = |xz: Vec2| Vec3::new(xz.x, sample(xz.x, xz.y), xz.y);
+    let mut best = surface_at(point.xz());
+    let mut best_distance = best.distance_squared(point);
+
+    for _ in 0..ITERATIONS {
+        let dx = (sample(best.x + NORMAL_STEP, best.z) - sample(best.x - NORMAL_STEP, best.z))
+            / (2.0 * NORMAL_STEP);
+        let dz = (sample(best.x, best.z + NORMAL_STEP) - sample(best.x, best.z - NORMAL_STEP))
+            / (2.0 * NORMAL_STEP);
+        let normal = Vec3::new(-dx, 1.0, -dz).normalize();
+
+        // Closest point on the tangent plane, re-projected onto the surface
+        let on_plane = point - normal * normal.dot(point - best);
+        let candidate = surface_at(on_plane.xz());
+        let distance = candidate.distance_squared(point);
+        if distance >= best_distance - 1e-6 {
+            break;
+        }
+        best = candidate;
+        best_distance = distance;
+    }
+
+    best
+}
+
+/// March a ray against a heightfield surface, returning the first hit point
+///
+/// Steps `step` units along `dir` until the ray drops below the surface, then
+/// bisects the last step for sub-step precision. Features thinner than `step`
+/// can be stepped over. A ray starting below the surface hits at `origin`.
+pub fn raycast_heightfield(
+    origin: Vec3,
+    dir: Vec3,
+    max_dist: f32,
+    step: f32,
+    sample: impl Fn(f32, f32) -> f32,
+) -> Option<Vec3> {
+    const BISECTIONS: usize = 24;
+
+    let dir = dir.try_normalize()?;
+    let below = |t: f32| {
+        let p = origin + dir * t;
+        p.y <= sample(p.x, p.z)
+    };
+
+    if below(0.0) {
+        return Some(origin);
+    }
+
+    let mut prev = 0.0;
+    while prev < max_dist {
+        let t = (prev + step).min(max_dist);
+        if below(t) {
+            let (mut above_t, mut below_t) = (prev, t);
+            for _ in 0..BISECTIONS {
+                let mid = (above_t + below_t) * 0.5;
+                if below(mid) {
+                    below_t = mid;
+                } else {
+                    above_t = mid;
+                }
+            }
+            let hit = origin + dir * below_t;
+            return Some(Vec3::new(hit.x, sample(hit.x, hit.z), hit.z));
+        }
+        prev = t;
+    }
+
+    None
+}
+
+/// Analytic normal callback: (x, z) -> surface normal
+pub type NormalFn = fn(f32, f32) -> Vec3;
+
 /// Simple procedural heightmap using a closure
-pub struct ProceduralHeightmap<F>
+///
+/// An optional analytic normal closure (see [`Self::new_with_normal`])
+/// replaces the finite-difference [`HeightmapSource::sample_normal`].
+pub struct ProceduralHeightmap<F, N = NormalFn>
 where
     F: Fn(f32, f32) -> f32 + Send + Sync + 'static,
+    N: Fn(f32, f32) -> Vec3 + Send + Sync + 'static,
 {
     height_fn: F,
+    normal_fn: Option<N>,
 }
 
 impl<F> ProceduralHeightmap<F>
@@ -90,17 +272,147 @@ where
     F: Fn(f32, f32) -> f32 + Send + Sync + 'static,
 {
     pub fn new(height_fn: F) -> Self {
-        Self { height_fn }
+        Self {
+            height_fn,
+            normal_fn: None,
+        }
+    }
+}
+
+impl<F, N> ProceduralHeightmap<F, N>
+where
+    F: Fn(f32, f32) -> f32 + Send + Sync + 'static,
+    N: Fn(f32, f32) -> Vec3 + Send + Sync + 'static,
+{
+    /// Heightmap whose normals come from `normal_fn` instead of extra height samples
+    ///
+    /// `normal_fn` should return the upward surface normal of `height_fn`,
+    /// e.g. `(-dh/dx, 1, -dh/dz)`; it is normalized before use.
+    pub fn new_with_normal(height_fn: F, normal_fn: N) -> Self {
+        Self {
+            height_fn,
+            normal_fn: Some(normal_fn),
+        }
     }
 }
 
-impl<F> HeightmapSource for ProceduralHeightmap<F>
+impl<F, N> HeightmapSource for ProceduralHeightmap<F, N>
 where
     F: Fn(f32, f32) -> f32 + Send + Sync + 'static,
+    N: Fn(f32, f32) -> Vec3 + Send + Sync + 'static,
 {
     fn sample(&self, x: f32, z: f32) -> f32 {
         (self.height_fn)(x, z)
     }
+
+    fn sample_normal(&self, x: f32, z: f32, step: f32) -> Vec3 {
+        match &self.normal_fn {
+            Some(normal_fn) => normal_fn(x, z).normalize_or(Vec3::Y),
+            None => finite_difference_normal(self, x, z, step),
+        }
+    }
+}
+
+/// Errors from loading heightmap data
+#[derive(Debug)]
+pub enum HeightmapError {
+    /// The bytes couldn't be decoded as an image
+    Decode(image::ImageError),
+    /// The image isn't single-channel grayscale
+    UnsupportedColor(image::ColorType),
+    /// Raw data doesn't match the given dimensions
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl fmt::Display for HeightmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeightmapError::Decode(err) => write!(f, "failed to decode heightmap: {err}"),
+            HeightmapError::UnsupportedColor(color) => {
+                write!(f, "heightmap must be grayscale, got {color:?}")
+            }
+            HeightmapError::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "expected {expected} bytes of heightmap data, got {actual}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeightmapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HeightmapError::Decode(err) => Some(err),
+            HeightmapError::UnsupportedColor(_) | HeightmapError::InvalidLength { .. } => None,
+        }
+    }
+}
+
+/// How [`BlendHeightmap`] combines its two sources
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendOp {
+    /// Sum of both heights
+    Add,
+    /// Higher of the two heights
+    Max,
+    /// Lower of the two heights
+    Min,
+    /// Linear blend from base (0.0) to overlay (1.0)
+    Lerp(f32),
+}
+
+/// Heightmap combining two sources, e.g. a hand-authored image over procedural terrain
+pub struct BlendHeightmap {
+    pub base: Box<dyn HeightmapSource>,
+    pub overlay: Box<dyn HeightmapSource>,
+    pub op: BlendOp,
+}
+
+impl BlendHeightmap {
+    pub fn new(base: impl HeightmapSource, overlay: impl HeightmapSource, op: BlendOp) -> Self {
+        Self {
+            base: Box::new(base),
+            overlay: Box::new(overlay),
+            op,
+        }
+    }
+}
+
+impl HeightmapSource for BlendHeightmap {
+    fn sample(&self, x: f32, z: f32) -> f32 {
+        let base = self.base.sample(x, z);
+        let overlay = self.overlay.sample(x, z);
+        match self.op {
+            BlendOp::Add => base + overlay,
+            BlendOp::Max => base.max(overlay),
+            BlendOp::Min => base.min(overlay),
+            BlendOp::Lerp(t) => base + (overlay - base) * t,
+        }
+    }
+}
+
+/// How an [`ImageHeightmap`] is sampled outside its bounds
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EdgeMode {
+    /// Repeat the edge pixels
+    #[default]
+    Clamp,
+    /// Tile the heightmap
+    Wrap,
+    /// Return a fixed world-space height
+    Value(f32),
+}
+
+/// Filter used to reconstruct heights between [`ImageHeightmap`] pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InterpolationMode {
+    /// Linear between the 4 nearest pixels (cheap, faceted on slopes)
+    #[default]
+    Bilinear,
+    /// Catmull-Rom spline through the 16 nearest pixels (smooth slopes)
+    Bicubic,
 }
 
 /// Image-based heightmap from 16-bit PNG data
@@ -117,6 +429,18 @@ pub struct ImageHeightmap {
     pub origin: Vec2,
     /// Height scale multiplier
     pub height_scale: f32,
+    /// Sampling behavior outside the heightmap
+    pub edge_mode: EdgeMode,
+    /// Filter between pixels
+    pub interpolation: InterpolationMode,
+    /// Per-pixel height gradients precomputed for [`HeightmapSource::sample_normal`]
+    ///
+    /// In normalized height per pixel, so changing `height_scale` or
+    /// `world_size` keeps them valid; edit `heights` directly and they go
+    /// stale. Computed by the constructors; `None` (see
+    /// [`Self::with_precomputed_normals`]) computes normals from four height
+    /// samples per call instead.
+    pub gradients: Option<Vec<Vec2>>,
 }
 
 impl ImageHeightmap {
@@ -134,7 +458,66 @@ impl ImageHeightmap {
             world_size,
             origin: Vec2::ZERO,
             height_scale,
+            edge_mode: EdgeMode::default(),
+            interpolation: InterpolationMode::default(),
+            gradients: None,
         }
+        .with_precomputed_normals(true)
+    }
+
+    /// Decode an 8- or 16-bit grayscale PNG, normalizing pixel values to 0-1
+    pub fn from_png_bytes(
+        bytes: &[u8],
+        world_size: Vec2,
+        height_scale: f32,
+    ) -> Result<Self, HeightmapError> {
+        let image = image::load_from_memory_with_format(bytes, ImageFormat::Png)
+            .map_err(HeightmapError::Decode)?;
+        let heights = match &image {
+            DynamicImage::ImageLuma8(img) => img
+                .as_raw()
+                .iter()
+                .map(|&h| h as f32 / u8::MAX as f32)
+                .collect(),
+            DynamicImage::ImageLuma16(img) => img
+                .as_raw()
+                .iter()
+                .map(|&h| h as f32 / u16::MAX as f32)
+                .collect(),
+            other => return Err(HeightmapError::UnsupportedColor(other.color())),
+        };
+
+        Ok(Self::new(
+            heights,
+            image.width(),
+            image.height(),
+            world_size,
+            height_scale,
+        ))
+    }
+
+    /// Read a headerless little-endian 16-bit heightmap (`.r16`), normalizing to 0-1
+    pub fn from_r16(
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        world_size: Vec2,
+        height_scale: f32,
+    ) -> Result<Self, HeightmapError> {
+        let expected = width as usize * height as usize * 2;
+        if bytes.len() != expected {
+            return Err(HeightmapError::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let heights = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]) as f32 / u16::MAX as f32)
+            .collect();
+
+        Ok(Self::new(heights, width, height, world_size, height_scale))
     }
 
     pub fn with_origin(mut self, origin: Vec2) -> Self {
@@ -142,6 +525,130 @@ impl ImageHeightmap {
         self
     }
 
+    pub fn with_edge_mode(mut self, edge_mode: EdgeMode) -> Self {
+        self.edge_mode = edge_mode;
+        // Wrapping changes the neighbors of edge pixels
+        if self.gradients.is_some() {
+            self.gradients = Some(self.pixel_gradients());
+        }
+        self
+    }
+
+    pub fn with_interpolation(mut self, interpolation: InterpolationMode) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Keep the precomputed normal gradients (the default), or drop them
+    ///
+    /// They take two floats per pixel; without them every normal costs four
+    /// height samples.
+    pub fn with_precomputed_normals(mut self, enabled: bool) -> Self {
+        self.gradients = enabled.then(|| self.pixel_gradients());
+        self
+    }
+
+    /// Run droplet-based hydraulic erosion over the height samples once
+    pub fn with_hydraulic_erosion(mut self, params: ErosionParams) -> Self {
+        hydraulic_erode(&mut self.heights, self.width, self.height, params);
+        if self.gradients.is_some() {
+            self.gradients = Some(self.pixel_gradients());
+        }
+        self
+    }
+
+    /// Central-difference height gradient of every pixel, in normalized height per pixel
+    ///
+    /// With [`EdgeMode::Wrap`] edge pixels take their neighbors from the
+    /// opposite edge, matching how the heightmap tiles.
+    fn pixel_gradients(&self) -> Vec<Vec2> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        // The last pixel lands on uv 1, which wraps onto the first one
+        let neighbor = |i: i64, size: i64| match self.edge_mode {
+            EdgeMode::Wrap => i.rem_euclid((size - 1).max(1)),
+            EdgeMode::Clamp | EdgeMode::Value(_) => i,
+        };
+        (0..height)
+            .flat_map(|y| {
+                (0..width).map(move |x| {
+                    Vec2::new(
+                        self.pixel(neighbor(x + 1, width), y)
+                            - self.pixel(neighbor(x - 1, width), y),
+                        self.pixel(x, neighbor(y + 1, height))
+                            - self.pixel(x, neighbor(y - 1, height)),
+                    ) * 0.5
+                })
+            })
+            .collect()
+    }
+
+    /// Apply the edge mode to normalized image coordinates
+    ///
+    /// Points outside an [`EdgeMode::Value`] heightmap return its height as the error.
+    fn edge_uv(&self, uv: Vec2) -> Result<Vec2, f32> {
+        match self.edge_mode {
+            EdgeMode::Clamp => Ok(uv),
+            EdgeMode::Wrap => Ok(Vec2::new(uv.x.rem_euclid(1.0), uv.y.rem_euclid(1.0))),
+            EdgeMode::Value(height) => {
+                if !(0.0..=1.0).contains(&uv.x) || !(0.0..=1.0).contains(&uv.y) {
+                    return Err(height);
+                }
+                Ok(uv)
+            }
+        }
+    }
+
+    /// Scaled height at normalized image coordinates, applying the edge mode
+    fn sample_uv(&self, uv: Vec2) -> f32 {
+        let Vec2 { x: u, y: v } = match self.edge_uv(uv) {
+            Ok(uv) => uv,
+            Err(height) => return height,
+        };
+
+        let height = match self.interpolation {
+            InterpolationMode::Bilinear => self.sample_bilinear(u, v),
+            InterpolationMode::Bicubic => self.sample_bicubic(u, v),
+        };
+        height * self.height_scale
+    }
+
+    /// Surface normal bilinearly interpolated from the precomputed gradients
+    fn precomputed_normal(&self, gradients: &[Vec2], uv: Vec2) -> Vec3 {
+        // Flat outside a clamped or value-edged image
+        let Ok(uv) = self.edge_uv(uv) else {
+            return Vec3::Y;
+        };
+        if !(0.0..=1.0).contains(&uv.x) || !(0.0..=1.0).contains(&uv.y) {
+            return Vec3::Y;
+        }
+        let x = uv.x.clamp(0.0, 1.0) * (self.width - 1) as f32;
+        let y = uv.y.clamp(0.0, 1.0) * (self.height - 1) as f32;
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let x1 = (x0 + 1).min(self.width as usize - 1);
+        let y1 = (y0 + 1).min(self.height as usize - 1);
+        let row = |y: usize| {
+            let start = y * self.width as usize;
+            gradients[start + x0].lerp(gradients[start + x1], x.fract())
+        };
+        let gradient = row(y0).lerp(row(y1), y.fract());
+
+        // Pixels to world units, normalized heights to world heights
+        let pixel_size = self.world_size
+            / Vec2::new(
+                self.width.saturating_sub(1).max(1) as f32,
+                self.height.saturating_sub(1).max(1) as f32,
+            );
+        let slope = gradient * self.height_scale / pixel_size;
+        Vec3::new(-slope.x, 1.0, -slope.y).normalize()
+    }
+
+    /// Height of a pixel, clamping coordinates to the image
+    fn pixel(&self, x: i64, y: i64) -> f32 {
+        let x = x.clamp(0, self.width as i64 - 1) as usize;
+        let y = y.clamp(0, self.height as i64 - 1) as usize;
+        self.heights[y * self.width as usize + x]
+    }
+
     /// Sample with bilinear interpolation
     fn sample_bilinear(&self, u: f32, v: f32) -> f32 {
         let u = u.clamp(0.0, 1.0);
@@ -168,18 +675,66 @@ impl ImageHeightmap {
 
         h0 * (1.0 - fy) + h1 * fy
     }
+
+    /// Sample with Catmull-Rom bicubic interpolation
+    fn sample_bicubic(&self, u: f32, v: f32) -> f32 {
+        let u = u.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+
+        let x = u * (self.width - 1) as f32;
+        let y = v * (self.height - 1) as f32;
+
+        let x0 = x.floor() as i64;
+        let y0 = y.floor() as i64;
+        let fx = x.fract();
+        let fy = y.fract();
+
+        let rows = [-1, 0, 1, 2].map(|dy| {
+            let row = [-1, 0, 1, 2].map(|dx| self.pixel(x0 + dx, y0 + dy));
+            catmull_rom(row, fx)
+        });
+        catmull_rom(rows, fy)
+    }
+}
+
+/// Catmull-Rom spline through `p[1]` (t = 0) and `p[2]` (t = 1)
+fn catmull_rom(p: [f32; 4], t: f32) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p[1]
+        + (p[2] - p[0]) * t
+        + (2.0 * p[0] - 5.0 * p[1] + 4.0 * p[2] - p[3]) * t2
+        + (3.0 * p[1] - p[0] - 3.0 * p[2] + p[3]) * t3)
 }
 
 impl HeightmapSource for ImageHeightmap {
     fn sample(&self, x: f32, z: f32) -> f32 {
-        let u = (x - self.origin.x) / self.world_size.x;
-        let v = (z - self.origin.y) / self.world_size.y;
+        self.sample_uv((Vec2::new(x, z) - self.origin) / self.world_size)
+    }
 
-        self.sample_bilinear(u, v) * self.height_scale
+    /// Interpolates the precomputed gradients when present, ignoring `step`
+    fn sample_normal(&self, x: f32, z: f32, step: f32) -> Vec3 {
+        match &self.gradients {
+            Some(gradients) => self
+                .precomputed_normal(gradients, (Vec2::new(x, z) - self.origin) / self.world_size),
+            None => finite_difference_normal(self, x, z, step),
+        }
+    }
+
+    fn sample_many(&self, points: &[Vec2], out: &mut Vec<f32>) {
+        let (origin, world_size) = (self.origin, self.world_size);
+        out.clear();
+        out.extend(
+            points
+                .iter()
+                .map(|&p| self.sample_uv((p - origin) / world_size)),
+        );
     }
 }
 
 /// Multi-layer noise system for realistic terrain generation (Stadt-style)
+///
+/// Use [`TerrainNoise::builder`] to change individual layers.
 pub struct TerrainNoise {
     /// Continental noise - large-scale landmass shapes
     pub continental: FastNoiseLite,
@@ -193,6 +748,8 @@ pub struct TerrainNoise {
     pub moisture: FastNoiseLite,
     /// Detail noise - small-scale surface variation
     pub detail: FastNoiseLite,
+    /// Settings the layers were built from
+    settings: TerrainNoiseBuilder,
 }
 
 impl Default for TerrainNoise {
@@ -201,64 +758,173 @@ impl Default for TerrainNoise {
     }
 }
 
-impl TerrainNoise {
-    /// Create terrain noise with a specific seed
-    pub fn with_seed(seed: i32) -> Self {
-        // Continental noise - define large flat areas vs ocean/mountains
-        let mut continental = FastNoiseLite::with_seed(seed);
-        continental.set_noise_type(Some(NoiseType::OpenSimplex2S));
-        continental.set_frequency(Some(0.0004));
-        continental.set_fractal_type(Some(FractalType::FBm));
-        continental.set_fractal_octaves(Some(4));
-
-        // Erosion noise - gentle rolling hills
-        let mut erosion = FastNoiseLite::with_seed(seed + 81);
-        erosion.set_noise_type(Some(NoiseType::OpenSimplex2S));
-        erosion.set_frequency(Some(0.0015));
-        erosion.set_fractal_type(Some(FractalType::FBm));
-        erosion.set_fractal_octaves(Some(4));
-        erosion.set_fractal_lacunarity(Some(2.0));
-        erosion.set_fractal_gain(Some(0.4));
-
-        // Ridge noise - distinct mountain ranges
-        let mut ridges = FastNoiseLite::with_seed(seed + 414);
-        ridges.set_noise_type(Some(NoiseType::OpenSimplex2S));
-        ridges.set_frequency(Some(0.003));
-        ridges.set_fractal_type(Some(FractalType::Ridged));
-        ridges.set_fractal_octaves(Some(5));
-        ridges.set_fractal_lacunarity(Some(2.0));
-        ridges.set_fractal_gain(Some(0.4));
-
-        // Domain warp noise
-        let mut warp = FastNoiseLite::with_seed(seed + 747);
-        warp.set_noise_type(Some(NoiseType::OpenSimplex2S));
-        warp.set_frequency(Some(0.001));
-        warp.set_fractal_type(Some(FractalType::FBm));
-        warp.set_fractal_octaves(Some(3));
-
-        // Moisture noise
-        let mut moisture = FastNoiseLite::with_seed(seed + 957);
-        moisture.set_noise_type(Some(NoiseType::OpenSimplex2S));
-        moisture.set_frequency(Some(0.0005));
-        moisture.set_fractal_type(Some(FractalType::FBm));
-        moisture.set_fractal_octaves(Some(3));
-
-        // Detail noise
-        let mut detail = FastNoiseLite::with_seed(seed + 969);
-        detail.set_noise_type(Some(NoiseType::OpenSimplex2S));
-        detail.set_frequency(Some(0.05));
-        detail.set_fractal_type(Some(FractalType::FBm));
-        detail.set_fractal_octaves(Some(2));
+/// Settings for one [`TerrainNoise`] layer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseLayerSettings {
+    pub noise_type: NoiseType,
+    pub frequency: f32,
+    pub fractal_type: FractalType,
+    pub octaves: i32,
+    /// Frequency multiplier between octaves
+    pub lacunarity: f32,
+    /// Amplitude multiplier between octaves
+    pub gain: f32,
+}
+
+impl NoiseLayerSettings {
+    /// FBm OpenSimplex2S layer with the given frequency and octaves
+    pub fn fbm(frequency: f32, octaves: i32) -> Self {
+        Self {
+            noise_type: NoiseType::OpenSimplex2S,
+            frequency,
+            fractal_type: FractalType::FBm,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    fn build(&self, seed: i32) -> FastNoiseLite {
+        let mut noise = FastNoiseLite::with_seed(seed);
+        noise.set_noise_type(Some(self.noise_type));
+        noise.set_frequency(Some(self.frequency));
+        noise.set_fractal_type(Some(self.fractal_type));
+        noise.set_fractal_octaves(Some(self.octaves));
+        noise.set_fractal_lacunarity(Some(self.lacunarity));
+        noise.set_fractal_gain(Some(self.gain));
+        noise
+    }
+}
+
+/// Builder for a [`TerrainNoise`] with customized layers
+///
+/// Defaults match [`TerrainNoise::with_seed`]. Layer setters take a closure
+/// that adjusts the current settings:
+///
+/// ```ignore
+/// let noise = TerrainNoise::builder()
+///     .seed(7)
+///     .continental(|layer| NoiseLayerSettings { frequency: 0.0002, ..layer })
+///     .ridges(|layer| NoiseLayerSettings { noise_type: NoiseType::Perlin, ..layer })
+///     .build();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerrainNoiseBuilder {
+    /// Base seed; each layer adds a fixed offset
+    pub seed: i32,
+    pub continental: NoiseLayerSettings,
+    pub erosion: NoiseLayerSettings,
+    pub ridges: NoiseLayerSettings,
+    pub warp: NoiseLayerSettings,
+    pub moisture: NoiseLayerSettings,
+    pub detail: NoiseLayerSettings,
+}
 
+impl Default for TerrainNoiseBuilder {
+    fn default() -> Self {
         Self {
-            continental,
-            erosion,
-            ridges,
-            warp,
-            moisture,
-            detail,
+            seed: 42,
+            // Continental noise - define large flat areas vs ocean/mountains
+            continental: NoiseLayerSettings::fbm(0.0004, 4),
+            // Erosion noise - gentle rolling hills
+            erosion: NoiseLayerSettings {
+                gain: 0.4,
+                ..NoiseLayerSettings::fbm(0.0015, 4)
+            },
+            // Ridge noise - distinct mountain ranges
+            ridges: NoiseLayerSettings {
+                fractal_type: FractalType::Ridged,
+                gain: 0.4,
+                ..NoiseLayerSettings::fbm(0.003, 5)
+            },
+            // Domain warp noise
+            warp: NoiseLayerSettings::fbm(0.001, 3),
+            moisture: NoiseLayerSettings::fbm(0.0005, 3),
+            detail: NoiseLayerSettings::fbm(0.05, 2),
         }
     }
+}
+
+impl TerrainNoiseBuilder {
+    /// Set the seed all layers are derived from
+    pub fn seed(mut self, seed: i32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Adjust the continental (landmass) layer
+    pub fn continental(mut self, f: impl FnOnce(NoiseLayerSettings) -> NoiseLayerSettings) -> Self {
+        self.continental = f(self.continental);
+        self
+    }
+
+    /// Adjust the erosion (rolling hills) layer
+    pub fn erosion(mut self, f: impl FnOnce(NoiseLayerSettings) -> NoiseLayerSettings) -> Self {
+        self.erosion = f(self.erosion);
+        self
+    }
+
+    /// Adjust the mountain ridge layer
+    pub fn ridges(mut self, f: impl FnOnce(NoiseLayerSettings) -> NoiseLayerSettings) -> Self {
+        self.ridges = f(self.ridges);
+        self
+    }
+
+    /// Adjust the domain warp layer
+    pub fn warp(mut self, f: impl FnOnce(NoiseLayerSettings) -> NoiseLayerSettings) -> Self {
+        self.warp = f(self.warp);
+        self
+    }
+
+    /// Adjust the moisture layer
+    pub fn moisture(mut self, f: impl FnOnce(NoiseLayerSettings) -> NoiseLayerSettings) -> Self {
+        self.moisture = f(self.moisture);
+        self
+    }
+
+    /// Adjust the surface detail layer
+    pub fn detail(mut self, f: impl FnOnce(NoiseLayerSettings) -> NoiseLayerSettings) -> Self {
+        self.detail = f(self.detail);
+        self
+    }
+
+    /// Build the noise layers
+    pub fn build(self) -> TerrainNoise {
+        let seed = self.seed;
+        TerrainNoise {
+            continental: self.continental.build(seed),
+            erosion: self.erosion.build(seed + 81),
+            ridges: self.ridges.build(seed + 414),
+            warp: self.warp.build(seed + 747),
+            moisture: self.moisture.build(seed + 957),
+            detail: self.detail.build(seed + 969),
+            settings: self,
+        }
+    }
+}
+
+impl TerrainNoise {
+    /// Create terrain noise with a specific seed and the default layers
+    pub fn with_seed(seed: i32) -> Self {
+        Self::builder().seed(seed).build()
+    }
+
+    /// Create a builder to customize individual noise layers
+    pub fn builder() -> TerrainNoiseBuilder {
+        TerrainNoiseBuilder::default()
+    }
+
+    /// Builder with the settings this noise was created from
+    ///
+    /// Direct changes to the public layer fields are not reflected here.
+    pub fn settings(&self) -> &TerrainNoiseBuilder {
+        &self.settings
+    }
+
+    /// Seed the noise layers were derived from
+    pub fn seed(&self) -> i32 {
+        self.settings.seed
+    }
 
     /// Sample moisture at a world position (0 = dry, 1 = wet)
     pub fn sample_moisture(&self, x: f32, z: f32) -> f32 {
@@ -272,6 +938,44 @@ impl TerrainNoise {
     }
 }
 
+// Cloned so async mesh tasks sample exactly the same terrain as height queries
+impl Clone for TerrainNoise {
+    fn clone(&self) -> Self {
+        Self {
+            continental: clone_noise(&self.continental),
+            erosion: clone_noise(&self.erosion),
+            ridges: clone_noise(&self.ridges),
+            warp: clone_noise(&self.warp),
+            moisture: clone_noise(&self.moisture),
+            detail: clone_noise(&self.detail),
+            settings: self.settings.clone(),
+        }
+    }
+}
+
+/// Rebuild a `FastNoiseLite` layer from its public settings
+///
+/// `FastNoiseLite` doesn't implement `Clone`; going through the setters also
+/// recomputes its private derived state (fractal bounding, 3D transforms).
+fn clone_noise(source: &FastNoiseLite) -> FastNoiseLite {
+    let mut noise = FastNoiseLite::with_seed(source.seed);
+    noise.set_frequency(Some(source.frequency));
+    noise.set_noise_type(Some(source.noise_type));
+    noise.set_rotation_type_3d(Some(source.rotation_type_3d));
+    noise.set_fractal_type(Some(source.fractal_type));
+    noise.set_fractal_octaves(Some(source.octaves));
+    noise.set_fractal_lacunarity(Some(source.lacunarity));
+    noise.set_fractal_gain(Some(source.gain));
+    noise.set_fractal_weighted_strength(Some(source.weighted_strength));
+    noise.set_fractal_ping_pong_strength(Some(source.ping_pong_strength));
+    noise.set_cellular_distance_function(Some(source.cellular_distance_function));
+    noise.set_cellular_return_type(Some(source.cellular_return_type));
+    noise.set_cellular_jitter(Some(source.cellular_jitter_modifier));
+    noise.set_domain_warp_type(Some(source.domain_warp_type));
+    noise.set_domain_warp_amp(Some(source.domain_warp_amp));
+    noise
+}
+
 /// Sample terrain height using multi-layer noise with erosion approximation
 pub fn sample_terrain_height(
     world_x: f32,
@@ -279,10 +983,35 @@ pub fn sample_terrain_height(
     noise: &TerrainNoise,
     config: &TerrainConfig,
 ) -> f32 {
+    sample_terrain_height_band_limited(world_x, world_z, noise, config, 0.0)
+}
+
+/// Weight for a noise layer of the given base frequency when sampled with features no
+/// smaller than `min_feature_size` (1.0 = full contribution, 0.0 = skipped)
+///
+/// Layers fade out as the sampling step approaches half their wavelength (Nyquist limit).
+pub fn band_limit_weight(frequency: f32, min_feature_size: f32) -> f32 {
+    let wavelength = 1.0 / frequency;
+    1.0 - smoothstep(wavelength * 0.25, wavelength * 0.5, min_feature_size)
+}
+
+/// Sample terrain height, attenuating noise layers finer than `min_feature_size`
+///
+/// Pass the mesh grid step for coarse LODs so they don't alias high-frequency
+/// detail; a `min_feature_size` of 0 gives the full-detail height.
+pub fn sample_terrain_height_band_limited(
+    world_x: f32,
+    world_z: f32,
+    noise: &TerrainNoise,
+    config: &TerrainConfig,
+    min_feature_size: f32,
+) -> f32 {
+    let world_x = world_x + config.noise_origin.x;
+    let world_z = world_z + config.noise_origin.y;
     let warp_x = noise.warp.get_noise_2d(world_x, world_z) * config.warp_strength;
     let warp_z = noise.warp.get_noise_2d(world_x + 1000.0, world_z + 1000.0) * config.warp_strength;
-    let wx = world_x + warp_x;
-    let wz = world_z + warp_z;
+    let Vec2 { x: wx, y: wz } =
+        config.domain_transform * Vec2::new(world_x + warp_x, world_z + warp_z);
 
     // Continental: -1 to 1 range, normalized to 0-1
     let continental = (noise.continental.get_noise_2d(wx, wz) + 1.0) * 0.5;
@@ -290,13 +1019,23 @@ pub fn sample_terrain_height(
     let erosion = (erosion_raw + 1.0) * 0.5;
 
     // Ridges: Sharp features
-    let ridge = noise.ridges.get_noise_2d(wx, wz);
+    let ridge_weight = band_limit_weight(noise.ridges.frequency, min_feature_size);
+    let ridge = if ridge_weight > 0.0 {
+        noise.ridges.get_noise_2d(wx, wz) * ridge_weight
+    } else {
+        0.0
+    };
     // Mask ridges to only appear on "high" areas of continental noise
     let mountain_mask = (continental - config.mountain_threshold * 0.5).max(0.0) * 2.5;
     let ridge_masked = ridge.max(0.0) * mountain_mask.powf(1.2);
 
     // Detail noise for surface roughness
-    let detail = noise.detail.get_noise_2d(wx, wz) * 0.02;
+    let detail_weight = band_limit_weight(noise.detail.frequency, min_feature_size);
+    let detail = if detail_weight > 0.0 {
+        noise.detail.get_noise_2d(wx, wz) * 0.02 * detail_weight
+    } else {
+        0.0
+    };
 
     // --- Erosion approximation ---
     // 1. Valley carving: In low areas, use erosion noise to carve deeper channels
@@ -317,8 +1056,26 @@ pub fn sample_terrain_height(
     let combined =
         (base_combined - valley_carve + plateau_smoothing - coastal_flatten).clamp(0.0, 1.0);
 
-    let curved = apply_height_curve(combined);
-    (curved * config.max_height) - config.water_level
+    let curved = apply_terraces(apply_height_curve(combined), config);
+    ((curved * config.max_height) - config.water_level) * config.vertical_exaggeration
+}
+
+/// Snap a curved 0-1 height onto `config.terrace_steps` bands
+///
+/// Each band is flattened around its center and rises to the next band along a
+/// shoulder whose steepness grows with `terrace_sharpness`. The result stays
+/// continuous, so terraced terrain has no vertical cliffs.
+fn apply_terraces(value: f32, config: &TerrainConfig) -> f32 {
+    let Some(steps) = config.terrace_steps.filter(|&steps| steps > 0) else {
+        return value;
+    };
+    let steps = steps as f32;
+    let scaled = value * steps;
+    let band = scaled.floor().min(steps - 1.0);
+    // Offset from the band center in -1..1, pushed towards 0 to flatten the band
+    let offset = (scaled - band) * 2.0 - 1.0;
+    let flattened = offset.signum() * offset.abs().powf(config.terrace_sharpness.max(1.0));
+    (band + (flattened + 1.0) * 0.5) / steps
 }
 
 /// Apply a multi-stage height curve for natural terrain
@@ -356,10 +1113,317 @@ fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_noise_builder_configures_layers() {
+        let config = TerrainConfig::default();
+        let points = [(0.0, 0.0), (1500.0, -800.0), (-3200.0, 2100.0)];
+        let heights =
+            |noise: &TerrainNoise| points.map(|(x, z)| sample_terrain_height(x, z, noise, &config));
+
+        // Builder defaults reproduce with_seed exactly
+        let built = TerrainNoise::builder().seed(7).build();
+        assert_eq!(heights(&built), heights(&TerrainNoise::with_seed(7)));
+        assert_eq!(built.seed(), 7);
+
+        let custom = TerrainNoise::builder()
+            .seed(7)
+            .continental(|layer| NoiseLayerSettings {
+                frequency: 0.0001,
+                ..layer
+            })
+            .ridges(|layer| NoiseLayerSettings {
+                noise_type: NoiseType::Perlin,
+                ..layer
+            })
+            .build();
+        assert_eq!(custom.settings().continental.frequency, 0.0001);
+        assert_eq!(custom.continental.frequency, 0.0001);
+        assert_ne!(heights(&custom), heights(&built));
+
+        // Clones keep the customized layers
+        let cloned = custom.clone();
+        assert_eq!(cloned.settings(), custom.settings());
+        assert_eq!(heights(&cloned), heights(&custom));
+    }
+
     #[test]
     fn test_procedural_heightmap() {
         let heightmap = ProceduralHeightmap::new(|x, z| x + z);
         assert_eq!(heightmap.sample(1.0, 2.0), 3.0);
+
+        let boxed: Box<dyn HeightmapSource> = Box::new(heightmap);
+        let handle = HeightmapHandle::from(boxed);
+        assert_eq!(handle.sample(1.0, 2.0), 3.0);
+    }
+
+    /// Encode grayscale samples as a 16-bit PNG
+    fn encode_png16(width: u32, height: u32, pixels: Vec<u16>) -> Vec<u8> {
+        let image = image::ImageBuffer::<image::Luma<u16>, _>::from_raw(width, height, pixels)
+            .expect("pixel count should match dimensions");
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_analytic_normal_matches_finite_differences() {
+        let height = |x: f32, z: f32| 0.01 * (x * x + z * z);
+        let analytic = ProceduralHeightmap::new_with_normal(height, |x, z| {
+            Vec3::new(-0.02 * x, 1.0, -0.02 * z)
+        });
+        let sampled = ProceduralHeightmap::new(height);
+
+        for (x, z) in [(0.0, 0.0), (12.0, -7.0), (-40.0, 25.0), (80.0, 80.0)] {
+            let exact = analytic.sample_normal(x, z, 0.5);
+            let approx = sampled.sample_normal(x, z, 0.5);
+            assert!((exact.length() - 1.0).abs() < 1e-5);
+            assert!(exact.distance(approx) < 1e-3, "{exact} vs {approx}");
+        }
+        assert_eq!(analytic.sample(3.0, 4.0), 0.25);
+    }
+
+    #[test]
+    fn test_image_heightmap_from_png16() {
+        let pixels: Vec<u16> = (0..16).map(|i| i * 4096).collect();
+        let bytes = encode_png16(4, 4, pixels.clone());
+
+        let heightmap = ImageHeightmap::from_png_bytes(&bytes, Vec2::splat(3.0), 10.0).unwrap();
+        assert_eq!((heightmap.width, heightmap.height), (4, 4));
+        for (height, pixel) in heightmap.heights.iter().zip(&pixels) {
+            assert_eq!(*height, *pixel as f32 / u16::MAX as f32);
+        }
+
+        // One world unit per pixel: corners land exactly on samples
+        let scale = 10.0 / u16::MAX as f32;
+        assert!((heightmap.sample(0.0, 0.0) - 0.0).abs() < 1e-4);
+        assert!((heightmap.sample(3.0, 0.0) - 3.0 * 4096.0 * scale).abs() < 1e-4);
+        assert!((heightmap.sample(1.0, 2.0) - 9.0 * 4096.0 * scale).abs() < 1e-4);
+        assert!((heightmap.sample(3.0, 3.0) - 15.0 * 4096.0 * scale).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_image_heightmap_rejects_bad_png() {
+        let corrupt = ImageHeightmap::from_png_bytes(b"not a png", Vec2::ONE, 1.0);
+        assert!(matches!(corrupt, Err(HeightmapError::Decode(_))));
+
+        let rgb = image::RgbImage::new(2, 2);
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        rgb.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        let color = ImageHeightmap::from_png_bytes(bytes.get_ref(), Vec2::ONE, 1.0);
+        assert!(matches!(color, Err(HeightmapError::UnsupportedColor(_))));
+    }
+
+    #[test]
+    fn test_image_heightmap_from_r16() {
+        let pixels: [u16; 6] = [0, 1000, u16::MAX, 32768, 7, 65000];
+        let bytes: Vec<u8> = pixels.iter().flat_map(|p| p.to_le_bytes()).collect();
+
+        let heightmap = ImageHeightmap::from_r16(&bytes, 3, 2, Vec2::new(2.0, 1.0), 1.0).unwrap();
+        assert_eq!((heightmap.width, heightmap.height), (3, 2));
+        for (height, pixel) in heightmap.heights.iter().zip(pixels) {
+            assert_eq!(*height, pixel as f32 / u16::MAX as f32);
+        }
+        assert_eq!(heightmap.sample(2.0, 0.0), 1.0);
+
+        let truncated = ImageHeightmap::from_r16(&bytes[..11], 3, 2, Vec2::ONE, 1.0);
+        assert!(matches!(
+            truncated,
+            Err(HeightmapError::InvalidLength {
+                expected: 12,
+                actual: 11
+            })
+        ));
+    }
+
+    #[test]
+    fn test_image_heightmap_edge_modes() {
+        // Ramp from 0 to 1 along x over one world unit
+        let ramp = || ImageHeightmap::new(vec![0.0, 0.5, 1.0, 0.0, 0.5, 1.0], 3, 2, Vec2::ONE, 1.0);
+
+        let clamp = ramp();
+        assert_eq!(clamp.edge_mode, EdgeMode::Clamp);
+        assert_eq!(clamp.sample(-0.2, 0.5), 0.0);
+        assert_eq!(clamp.sample(1.3, 0.5), 1.0);
+
+        let wrap = ramp().with_edge_mode(EdgeMode::Wrap);
+        assert!((wrap.sample(-0.2, 0.5) - 0.8).abs() < 1e-5);
+        assert!((wrap.sample(1.3, 0.5) - 0.3).abs() < 1e-5);
+
+        let value = ramp().with_edge_mode(EdgeMode::Value(-5.0));
+        assert_eq!(value.sample(-0.2, 0.5), -5.0);
+        assert_eq!(value.sample(1.3, 0.5), -5.0);
+        assert_eq!(value.sample(0.5, 0.5), 0.5);
+    }
+
+    #[test]
+    fn test_precomputed_normals_match_finite_differences() {
+        // Gentle hills over 64x64 pixels, 10 world units per pixel
+        let size = 64;
+        let heights: Vec<f32> = (0..size * size)
+            .map(|i| {
+                let (x, y) = ((i % size) as f32, (i / size) as f32);
+                0.5 + 0.25 * (x * 0.15).sin() * (y * 0.1).cos()
+            })
+            .collect();
+        let world_size = Vec2::splat(630.0);
+        let on_the_fly = ImageHeightmap::new(heights, size as u32, size as u32, world_size, 80.0)
+            .with_precomputed_normals(false);
+        let precomputed = ImageHeightmap::new(
+            on_the_fly.heights.clone(),
+            size as u32,
+            size as u32,
+            world_size,
+            80.0,
+        );
+        assert!(precomputed.gradients.is_some());
+        assert!(on_the_fly.gradients.is_none());
+
+        for i in 0..50 {
+            let (x, z) = (40.0 + i as f32 * 10.7, 590.0 - i as f32 * 9.3);
+            let expected = on_the_fly.sample_normal(x, z, 10.0);
+            let normal = precomputed.sample_normal(x, z, 10.0);
+            assert!(
+                normal.angle_between(expected) < 0.02,
+                "{normal} vs {expected} at ({x}, {z})"
+            );
+        }
+
+        // Past a clamped edge the precomputed normals don't extend the edge gradient
+        for (x, z) in [
+            (-50.0, 300.0),
+            (700.0, 300.0),
+            (300.0, -20.0),
+            (300.0, 660.0),
+        ] {
+            assert_eq!(precomputed.sample_normal(x, z, 10.0), Vec3::Y);
+        }
+    }
+
+    #[test]
+    fn test_wrapped_precomputed_normals_continue_across_edges() {
+        // One full period across the image, so the first and last columns match
+        let size = 33;
+        let heights: Vec<f32> = (0..size * size)
+            .map(|i| {
+                let x = (i % size) as f32 / (size - 1) as f32;
+                0.5 + 0.25 * (x * std::f32::consts::TAU).sin()
+            })
+            .collect();
+        let world_size = Vec2::splat(320.0);
+        let on_the_fly = ImageHeightmap::new(heights, size as u32, size as u32, world_size, 80.0)
+            .with_edge_mode(EdgeMode::Wrap)
+            .with_precomputed_normals(false);
+        let precomputed = ImageHeightmap::new(
+            on_the_fly.heights.clone(),
+            size as u32,
+            size as u32,
+            world_size,
+            80.0,
+        )
+        .with_edge_mode(EdgeMode::Wrap);
+
+        // The sine is steepest at the seam, where clamped neighbors would halve the slope
+        for x in [0.0, 1.0, 319.0, 320.0, 321.0] {
+            let expected = on_the_fly.sample_normal(x, 160.0, 10.0);
+            let normal = precomputed.sample_normal(x, 160.0, 10.0);
+            assert!(
+                normal.angle_between(expected) < 0.02,
+                "{normal} vs {expected} at x = {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bicubic_smooths_derivatives() {
+        // Flat, then a one-pixel step up, then flat again; one world unit per pixel
+        let row = [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+        let step = |mode| {
+            ImageHeightmap::new([row, row].concat(), 8, 2, Vec2::new(7.0, 1.0), 1.0)
+                .with_interpolation(mode)
+        };
+        let bilinear = step(InterpolationMode::Bilinear);
+        let bicubic = step(InterpolationMode::Bicubic);
+
+        // Both pass through the pixels
+        for (x, height) in row.iter().enumerate() {
+            assert!((bicubic.sample(x as f32, 0.0) - height).abs() < 1e-5);
+        }
+
+        // Largest change in slope between neighboring samples across the step
+        let max_slope_change = |heightmap: &ImageHeightmap| {
+            let dx = 0.01;
+            let slopes: Vec<f32> = (0..400)
+                .map(|i| {
+                    let x = 1.5 + i as f32 * dx;
+                    (heightmap.sample(x + dx, 0.0) - heightmap.sample(x, 0.0)) / dx
+                })
+                .collect();
+            slopes
+                .windows(2)
+                .map(|w| (w[1] - w[0]).abs())
+                .fold(0.0, f32::max)
+        };
+        let bilinear_kink = max_slope_change(&bilinear);
+        let bicubic_kink = max_slope_change(&bicubic);
+        assert!(bilinear_kink > 0.5, "bilinear kink {bilinear_kink}");
+        assert!(bicubic_kink < 0.1, "bicubic kink {bicubic_kink}");
+    }
+
+    #[test]
+    fn test_blend_heightmap() {
+        let base = |x: f32, z: f32| x - z;
+        let overlay = |x: f32, z: f32| (x * 0.5).sin() * 10.0 + z;
+        let blend = |op| {
+            BlendHeightmap::new(
+                ProceduralHeightmap::new(base),
+                ProceduralHeightmap::new(overlay),
+                op,
+            )
+        };
+
+        let handle = HeightmapHandle::Procedural(Arc::new(blend(BlendOp::Max)));
+        let add = blend(BlendOp::Add);
+        let min = blend(BlendOp::Min);
+        let lerp = blend(BlendOp::Lerp(0.25));
+        for i in 0..32 {
+            let (x, z) = (i as f32 * 1.7 - 20.0, i as f32 * -0.9 + 5.0);
+            let (b, o) = (base(x, z), overlay(x, z));
+            assert_eq!(handle.sample(x, z), b.max(o));
+            assert_eq!(add.sample(x, z), b + o);
+            assert_eq!(min.sample(x, z), b.min(o));
+            assert!((lerp.sample(x, z) - (b * 0.75 + o * 0.25)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_sample_many_matches_sample() {
+        let points: Vec<Vec2> = (0..50)
+            .map(|i| Vec2::new(i as f32 * 0.37 - 4.0, i as f32 * -0.21 + 6.0))
+            .collect();
+        let image = ImageHeightmap::new(
+            (0..16).map(|i| (i % 5) as f32 / 4.0).collect(),
+            4,
+            4,
+            Vec2::splat(10.0),
+            7.0,
+        )
+        .with_origin(Vec2::new(-2.0, 1.0));
+        let sources: [&dyn HeightmapSource; 3] = [
+            &ProceduralHeightmap::new(|x, z| x * z),
+            &image,
+            &ImageHeightmap::new(vec![0.2, 0.9, 0.4, 0.1], 2, 2, Vec2::ONE, 3.0)
+                .with_edge_mode(EdgeMode::Wrap)
+                .with_interpolation(InterpolationMode::Bicubic),
+        ];
+
+        let mut out = vec![f32::NAN; 3];
+        for source in sources {
+            source.sample_many(&points, &mut out);
+            assert_eq!(out.len(), points.len());
+            for (height, p) in out.iter().zip(&points) {
+                assert_eq!(*height, source.sample(p.x, p.y));
+            }
+        }
     }
 
     #[test]
@@ -373,6 +1437,167 @@ mod tests {
         assert!(height < config.max_height);
     }
 
+    #[test]
+    fn test_clone_preserves_seed() {
+        let config = TerrainConfig::default();
+        let mut noise = TerrainNoise::with_seed(777);
+        noise.detail.set_frequency(Some(0.08));
+        let cloned = noise.clone();
+
+        assert_eq!(cloned.seed(), 777);
+        for (x, z) in [
+            (0.0, 0.0),
+            (123.4, -56.7),
+            (-2500.0, 1800.0),
+            (9000.0, 9000.0),
+        ] {
+            assert_eq!(
+                sample_terrain_height(x, z, &cloned, &config),
+                sample_terrain_height(x, z, &noise, &config)
+            );
+            assert_eq!(cloned.sample_detail(x, z), noise.sample_detail(x, z));
+        }
+
+        // Differs from the default seed the old clone fell back to
+        let default = TerrainNoise::default();
+        assert_ne!(
+            sample_terrain_height(123.4, -56.7, &cloned, &config),
+            sample_terrain_height(123.4, -56.7, &default, &config)
+        );
+    }
+
+    #[test]
+    fn test_band_limit_attenuates_detail() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+
+        assert_eq!(band_limit_weight(noise.detail.frequency, 1.0), 1.0);
+        assert_eq!(band_limit_weight(noise.detail.frequency, 12.0), 0.0);
+        assert_eq!(band_limit_weight(noise.ridges.frequency, 12.0), 1.0);
+
+        let mut fine_error = 0.0;
+        let mut coarse_error = 0.0;
+        for i in 0..64 {
+            let (x, z) = (i as f32 * 37.3, i as f32 * -11.9);
+            let full = sample_terrain_height(x, z, &noise, &config);
+            fine_error +=
+                (sample_terrain_height_band_limited(x, z, &noise, &config, 1.0) - full).abs();
+            coarse_error +=
+                (sample_terrain_height_band_limited(x, z, &noise, &config, 12.0) - full).abs();
+        }
+        assert_eq!(fine_error, 0.0);
+        assert!(coarse_error > 0.0);
+    }
+
+    #[test]
+    fn test_noise_origin_shifts_terrain() {
+        let noise = TerrainNoise::default();
+        let origin = Vec2::new(250.0, -75.0);
+        let centered = TerrainConfig::default();
+        let shifted = TerrainConfig::builder().noise_origin(origin).build();
+
+        let mut moved = false;
+        for i in 0..32 {
+            let (x, z) = (i as f32 * 41.0, i as f32 * -13.0);
+            let height = sample_terrain_height(x, z, &noise, &shifted);
+            assert_eq!(
+                height,
+                sample_terrain_height(x + origin.x, z + origin.y, &noise, &centered)
+            );
+            moved |= height != sample_terrain_height(x, z, &noise, &centered);
+        }
+        assert!(moved);
+    }
+
+    #[test]
+    fn test_domain_transform_elongates_features() {
+        let noise = TerrainNoise::default();
+        let plain = TerrainConfig::builder().warp_strength(0.0).build();
+        let stretched = TerrainConfig::builder()
+            .warp_strength(0.0)
+            .domain_transform(Mat2::from_diagonal(Vec2::new(0.5, 1.0)))
+            .build();
+
+        // Features are twice as long along X and unchanged along Z
+        let (mut along_x, mut along_z) = (0.0, 0.0);
+        for i in 0..64 {
+            let (x, z) = (i as f32 * 37.0, i as f32 * -11.0);
+            let height = sample_terrain_height(x, z, &noise, &stretched);
+            assert_eq!(height, sample_terrain_height(x * 0.5, z, &noise, &plain));
+            along_x += (sample_terrain_height(x + 10.0, z, &noise, &stretched) - height).abs();
+            along_z += (sample_terrain_height(x, z + 10.0, &noise, &stretched) - height).abs();
+        }
+        assert!(along_x < along_z);
+    }
+
+    #[test]
+    fn test_terraces_cluster_heights_at_band_centers() {
+        let noise = TerrainNoise::default();
+        let steps = 6;
+        let smooth = TerrainConfig::default();
+        let terraced = TerrainConfig::builder().terraces(steps, 6.0).build();
+
+        // Fraction of heights within a fifth of a band from the nearest band center
+        let near_centers = |config: &TerrainConfig| {
+            let points: Vec<(f32, f32)> = (-30..30)
+                .flat_map(|x| (-30..30).map(move |z| (x as f32 * 97.0, z as f32 * 89.0)))
+                .collect();
+            let near = points
+                .iter()
+                .filter(|&&(x, z)| {
+                    let height = sample_terrain_height(x, z, &noise, config);
+                    let band = (height + config.water_level) / config.max_height * steps as f32;
+                    (band - (band.floor() + 0.5)).abs() < 0.2
+                })
+                .count();
+            near as f32 / points.len() as f32
+        };
+
+        // Uniformly spread heights land there 40% of the time; sharpness 6
+        // flattens 0.4^(1/6) = 86% of each band into that range
+        assert!(near_centers(&smooth) < 0.5);
+        assert!(near_centers(&terraced) > 0.75);
+    }
+
+    #[test]
+    fn test_closest_surface_point_inclined_plane() {
+        // Plane y = x, tilted 45 degrees around the z axis
+        let plane = |x: f32, _z: f32| x;
+        let point = Vec3::new(0.0, 10.0, 3.0);
+
+        let closest = closest_surface_point(point, plane);
+        let straight_down = Vec3::new(point.x, plane(point.x, point.z), point.z);
+
+        // Exact answer: foot of the perpendicular from the point to the plane
+        let expected = Vec3::new(5.0, 5.0, 3.0);
+        assert!(closest.distance(expected) < 1e-3);
+        assert!(closest.distance(point) < straight_down.distance(point));
+        assert!((closest.y - plane(closest.x, closest.z)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_raycast_heightfield_plane() {
+        // Plane y = 0.5x + 10, hit by a 45 degree ray at x = 60
+        let plane = |x: f32, _z: f32| 0.5 * x + 10.0;
+        let origin = Vec3::new(0.0, 100.0, 5.0);
+        let dir = Vec3::new(1.0, -1.0, 0.0);
+
+        let hit = raycast_heightfield(origin, dir, 200.0, 7.0, plane).unwrap();
+        assert!(hit.distance(Vec3::new(60.0, 40.0, 5.0)) < 1e-3);
+
+        // Too short, pointing away, or degenerate
+        assert!(raycast_heightfield(origin, dir, 80.0, 7.0, plane).is_none());
+        assert!(raycast_heightfield(origin, Vec3::Y, 200.0, 7.0, plane).is_none());
+        assert!(raycast_heightfield(origin, Vec3::ZERO, 200.0, 7.0, plane).is_none());
+
+        // Starting underground hits immediately
+        let buried = Vec3::new(0.0, 0.0, 0.0);
+        assert_eq!(
+            raycast_heightfield(buried, dir, 200.0, 7.0, plane),
+            Some(buried)
+        );
+    }
+
     #[test]
     fn test_smoothstep() {
         assert_eq!(smoothstep(0.0, 1.0, 0.0), 0.0);
@@ -4,44 +4,111 @@
 //! - CDLOD quadtree-based level of detail
 //! - Async chunk streaming with priority queue
 //! - HeightmapSource abstraction for procedural/image-based terrain
+//! - Droplet-based hydraulic erosion for image heightmaps
+//! - Runtime height editing with brush strokes
 //! - Texture splatting with automatic slope/height-based layer blending
 //! - Height query API for gameplay systems
-//! - Optional Rapier physics integration (feature-gated)
+//! - Headless chunk mesh baking for offline export
+//! - Ground snapping for props placed on terrain
+//! - Seeded spawn point selection with slope/height/water filters
+//! - Debug gizmo overlay of the active quadtree
+//! - Optional water surface at sea level
+//! - Optional Rapier or Avian physics integration (feature-gated)
 
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
 use bevy::prelude::*;
 
 pub mod config;
+pub mod debug;
+pub mod edit;
+pub mod erosion;
 pub mod heightmap;
 pub mod material;
 pub mod mesh;
-#[cfg(feature = "rapier")]
 pub mod physics;
+pub mod placement;
 pub mod quadtree;
+pub mod snap;
 pub mod streaming;
+pub mod water;
 
 pub mod prelude {
-    pub use crate::config::{TerrainConfig, TerrainConfigBuilder};
-    pub use crate::heightmap::{HeightmapSource, ImageHeightmap, ProceduralHeightmap};
+    pub use crate::config::{
+        AerialConfig, BiomePalette, ConfigError, GenerationMode, LodMetric, SeamMode,
+        TerrainConfig, TerrainConfigBuilder, TriangulationPattern,
+    };
+    pub use crate::debug::{TerrainDebugMode, TerrainDebugPlugin, TerrainDebugSettings};
+    pub use crate::edit::{EditableHeightmap, Falloff};
+    pub use crate::erosion::ErosionParams;
+    pub use crate::heightmap::{
+        BlendHeightmap, BlendOp, EdgeMode, HeightmapError, HeightmapSource, ImageHeightmap,
+        InterpolationMode, ProceduralHeightmap,
+    };
     pub use crate::material::{TerrainLayers, TerrainMaterial, TerrainMaterialExtension};
-    pub use crate::quadtree::{QuadtreeNode, TerrainQuadtree};
-    pub use crate::streaming::TerrainHeightQuery;
-    pub use crate::{TerrainBundle, TerrainPlugin};
+    pub use crate::mesh::{
+        Biome, ChunkSamples, GeneratedChunk, HoleMask, MeshGenOptions, Shading, bake_chunk,
+    };
+    pub use crate::placement::{
+        ChunkFeatureSpawner, ChunkRng, SpawnCriteria, SpawnPoint, TerrainScatterGrid,
+    };
+    pub use crate::quadtree::{
+        CorridorFocus, DetailBoost, LodProjection, QuadtreeNode, TerrainQuadtree,
+    };
+    pub use crate::snap::{SnapMode, SnapToTerrain};
+    pub use crate::streaming::{
+        ChunkData, RegenerateChunk, TerrainChunkDespawned, TerrainChunkIndex, TerrainChunkSpawned,
+        TerrainEdited, TerrainHeightQuery, TerrainMeshHeights, TerrainStreamingMetrics,
+        WarmupRequest, terrain_ready,
+    };
+    pub use crate::water::{TerrainWaterPlugin, TerrainWaterSettings};
+    pub use crate::{TerrainBundle, TerrainCamera, TerrainPlugin, TerrainSchedule};
 
-    #[cfg(feature = "rapier")]
+    #[cfg(any(feature = "rapier", feature = "avian"))]
     pub use crate::physics::TerrainCollider;
 }
 
+/// Schedule the terrain streaming systems run in
+///
+/// `FixedUpdate` decouples quadtree selection and chunk streaming from the
+/// render frame rate, which a tick-based server needs for determinism. Mesh
+/// generation still runs on the `AsyncComputeTaskPool`; only selection,
+/// task polling, and chunk spawning happen on the chosen schedule, so a task
+/// finishing between ticks is picked up on the next fixed tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TerrainSchedule {
+    /// Run once per rendered frame
+    #[default]
+    Update,
+    /// Run on the fixed timestep
+    FixedUpdate,
+}
+
+impl TerrainSchedule {
+    /// Get the schedule label for this option
+    pub fn label(self) -> InternedScheduleLabel {
+        match self {
+            TerrainSchedule::Update => Update.intern(),
+            TerrainSchedule::FixedUpdate => FixedUpdate.intern(),
+        }
+    }
+}
+
 /// Main terrain plugin that sets up all terrain systems
 #[derive(Default)]
 pub struct TerrainPlugin {
     /// Configuration for terrain generation
     pub config: config::TerrainConfig,
+    /// Schedule the streaming and collider systems run in
+    pub schedule: TerrainSchedule,
 }
 
 impl TerrainPlugin {
     /// Create a new terrain plugin with the given configuration
     pub fn new(config: config::TerrainConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            schedule: TerrainSchedule::default(),
+        }
     }
 
     /// Create a terrain plugin using a builder pattern
@@ -54,24 +121,60 @@ impl Plugin for TerrainPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(bevy::pbr::MaterialPlugin::<material::TerrainMaterial>::default())
             .insert_resource(self.config.clone())
-            .init_resource::<quadtree::TerrainQuadtree>()
+            .insert_resource(quadtree::TerrainQuadtree::from_config(&self.config))
             .init_resource::<streaming::TerrainStreaming>()
+            .init_resource::<streaming::TerrainStreamingMetrics>()
+            .init_resource::<streaming::TerrainChunkIndex>()
+            .init_resource::<streaming::TerrainMeshHeights>()
+            .init_resource::<placement::TerrainScatterGrid>()
             .init_resource::<material::TerrainMaterialHandle>()
+            .add_message::<streaming::TerrainChunkSpawned>()
+            .add_message::<streaming::TerrainChunkDespawned>()
+            .add_message::<streaming::WarmupRequest>()
+            .add_message::<streaming::TerrainEdited>()
+            .add_message::<streaming::RegenerateChunk>()
             .add_systems(Startup, material::setup_terrain_material)
             .add_systems(
                 Update,
                 (
+                    material::update_terrain_layer_textures,
+                    material::update_terrain_morph,
+                ),
+            )
+            .add_systems(
+                self.schedule.label(),
+                (
+                    streaming::rebuild_on_config_change,
+                    streaming::rebuild_on_heightmap_change,
+                    streaming::handle_terrain_edits,
+                    streaming::handle_regenerate_requests,
                     streaming::update_quadtree,
+                    streaming::handle_warmup_requests,
                     streaming::spawn_mesh_tasks,
                     streaming::poll_mesh_tasks,
                     streaming::spawn_chunk_entities,
+                    streaming::update_streaming_metrics,
+                    placement::release_despawned_scatter,
+                    snap::snap_to_terrain,
                 )
                     .chain(),
             );
 
-        #[cfg(feature = "rapier")]
+        #[cfg(any(feature = "rapier", feature = "avian"))]
         {
-            app.add_systems(Update, physics::spawn_terrain_colliders);
+            app.init_resource::<physics::TerrainColliderTasks>()
+                .add_systems(
+                    self.schedule.label(),
+                    (
+                        physics::remove_distant_colliders,
+                        physics::spawn_terrain_colliders,
+                        physics::update_terrain_colliders,
+                        physics::spawn_collider_tasks,
+                        physics::poll_collider_tasks,
+                        physics::insert_terrain_colliders,
+                    )
+                        .chain(),
+                );
         }
     }
 }
@@ -80,6 +183,7 @@ impl Plugin for TerrainPlugin {
 #[derive(Default)]
 pub struct TerrainPluginBuilder {
     config: config::TerrainConfig,
+    schedule: TerrainSchedule,
 }
 
 impl TerrainPluginBuilder {
@@ -98,18 +202,26 @@ impl TerrainPluginBuilder {
         self
     }
 
-    pub fn lod_distances(mut self, distances: [f32; 3]) -> Self {
-        self.config.lod_distances = distances;
+    pub fn lod_distances(mut self, distances: impl Into<Vec<f32>>) -> Self {
+        self.config.lod_distances = distances.into();
+        self
+    }
+
+    pub fn lod_subdivisions(mut self, subdivisions: impl Into<Vec<u32>>) -> Self {
+        self.config.lod_subdivisions = subdivisions.into();
         self
     }
 
-    pub fn lod_subdivisions(mut self, subdivisions: [u32; 4]) -> Self {
-        self.config.lod_subdivisions = subdivisions;
+    pub fn schedule(mut self, schedule: TerrainSchedule) -> Self {
+        self.schedule = schedule;
         self
     }
 
     pub fn build(self) -> TerrainPlugin {
-        TerrainPlugin::new(self.config)
+        TerrainPlugin {
+            config: self.config,
+            schedule: self.schedule,
+        }
     }
 }
 
@@ -117,6 +229,14 @@ impl TerrainPluginBuilder {
 #[derive(Component)]
 pub struct Terrain;
 
+/// Marker for the camera that drives terrain LOD selection
+///
+/// With a single camera the marker is optional. Apps with more than one
+/// camera (UI, minimap, reflection probes) must tag their terrain camera,
+/// otherwise no camera drives the quadtree.
+#[derive(Component, Default)]
+pub struct TerrainCamera;
+
 /// Component storing chunk metadata
 #[derive(Component)]
 pub struct Chunk {
@@ -126,6 +246,8 @@ pub struct Chunk {
     pub current_lod: u32,
     /// Quadtree node ID this chunk belongs to
     pub node_id: u64,
+    /// Edge length of this chunk in world units
+    pub size: f32,
 }
 
 /// Bundle for spawning a terrain entity
@@ -148,7 +270,7 @@ impl TerrainBundle {
     {
         Self {
             terrain: Terrain,
-            heightmap: heightmap::HeightmapHandle::Procedural(Box::new(
+            heightmap: heightmap::HeightmapHandle::Procedural(std::sync::Arc::new(
                 heightmap::ProceduralHeightmap::new(height_fn),
             )),
             transform: Transform::default(),
@@ -159,6 +281,19 @@ impl TerrainBundle {
         }
     }
 
+    /// Create terrain from a shared height source, e.g. an [`edit::EditableHeightmap`]
+    pub fn from_source(source: std::sync::Arc<dyn heightmap::HeightmapSource>) -> Self {
+        Self {
+            terrain: Terrain,
+            heightmap: heightmap::HeightmapHandle::Procedural(source),
+            transform: Transform::default(),
+            global_transform: GlobalTransform::default(),
+            visibility: Visibility::default(),
+            inherited_visibility: InheritedVisibility::default(),
+            view_visibility: ViewVisibility::default(),
+        }
+    }
+
     /// Create terrain with a multi-layer noise heightmap (Stadt-style)
     pub fn noise(noise: heightmap::TerrainNoise, config: &config::TerrainConfig) -> Self {
         Self {
@@ -172,3 +307,53 @@ impl TerrainBundle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_fixed_update_streams_chunks() {
+        let config = config::TerrainConfig::builder().render_distance(4).build();
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )))
+            .add_plugins(
+                TerrainPlugin::builder()
+                    .render_distance(config.render_distance)
+                    .schedule(TerrainSchedule::FixedUpdate)
+                    .build(),
+            );
+
+        app.world_mut().spawn(TerrainBundle::noise(
+            heightmap::TerrainNoise::default(),
+            &config,
+        ));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 5000.0, 0.0)));
+
+        let mut chunk_count = 0;
+        for _ in 0..200 {
+            app.update();
+            chunk_count = app.world_mut().query::<&Chunk>().iter(app.world()).count();
+            if chunk_count > 0
+                && app
+                    .world()
+                    .resource::<streaming::TerrainStreaming>()
+                    .pending_iter()
+                    .next()
+                    .is_none()
+            {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(chunk_count > 0, "chunks should stream on fixed ticks");
+    }
+}
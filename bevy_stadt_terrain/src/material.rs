@@ -2,17 +2,28 @@
 //!
 //! Extends Bevy's StandardMaterial with:
 //! - Vertex morphing for smooth LOD transitions
+//! - Optional quantized vertex positions reconstructed from per-chunk uniforms
 //! - 4-layer texture splatting (optional)
 //! - Auto-splatting based on height/slope
+//! - Optional elevation contour lines
+//! - Optional distance fog
+//! - Debug views for LOD levels and normals
 
+use crate::config::{AerialConfig, LodMetric, TerrainConfig};
+use crate::mesh::PositionQuantization;
 use bevy::{
+    asset::RenderAssetUsages,
+    image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor, TextureFormatPixelInfo},
     mesh::{MeshVertexAttribute, MeshVertexBufferLayoutRef},
     pbr::{
         ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
         MeshPipelineKey, StandardMaterial,
     },
     prelude::*,
-    render::render_resource::{AsBindGroup, SpecializedMeshPipelineError, VertexFormat},
+    render::render_resource::{
+        AsBindGroup, Extent3d, ShaderType, SpecializedMeshPipelineError, TextureDimension,
+        TextureFormat, TextureViewDescriptor, TextureViewDimension, VertexFormat,
+    },
     shader::ShaderRef,
 };
 
@@ -20,6 +31,28 @@ use bevy::{
 pub const ATTRIBUTE_MORPH_HEIGHT: MeshVertexAttribute =
     MeshVertexAttribute::new("MorphHeight", 988540917, VertexFormat::Float32);
 
+/// Custom vertex attribute for chunk-local positions packed into normalized 16-bit values
+pub const ATTRIBUTE_QUANTIZED_POSITION: MeshVertexAttribute =
+    MeshVertexAttribute::new("QuantizedPosition", 988540918, VertexFormat::Unorm16x4);
+
+/// Maximum number of splatted texture layers (one per splatmap channel)
+pub const MAX_SPLAT_LAYERS: usize = 4;
+
+/// Resolution of the generated splatmap along both the height and slope axes
+const SPLATMAP_RESOLUTION: u32 = 64;
+
+/// Distance outside a layer's height/slope range over which it fades out
+const SPLAT_BLEND: f32 = 0.05;
+
+/// Fraction of each LOD's distance band over which vertices morph to the next LOD
+const MORPH_REGION: f32 = 0.3;
+
+/// Morph distance for LODs that never transition further
+const NO_MORPH_DISTANCE: f32 = 1.0e9;
+
+/// LOD levels with their own geomorph band; coarser levels don't morph
+pub const MAX_MORPH_LODS: usize = 16;
+
 /// Type alias for the terrain material
 pub type TerrainMaterial = ExtendedMaterial<StandardMaterial, TerrainMaterialExtension>;
 
@@ -29,18 +62,93 @@ pub struct TerrainMaterialHandle {
     pub handle: Option<Handle<TerrainMaterial>>,
 }
 
-/// Material extension that adds vertex morphing to StandardMaterial
-/// Uses Bevy's view uniform for camera position and hardcoded morph distances in the shader
+/// Material extension that adds vertex morphing and texture splatting to StandardMaterial
+/// Uses Bevy's view uniform for camera position and per-LOD morph distances from the config
 #[derive(Asset, AsBindGroup, Reflect, Debug, Clone, Default)]
 pub struct TerrainMaterialExtension {
-    // Future: Add splatmap and layer textures here
-    // #[texture(100)]
-    // #[sampler(101)]
-    // pub splatmap: Option<Handle<Image>>,
-    //
-    // #[texture(102, dimension = "2d_array")]
-    // #[sampler(103)]
-    // pub layer_textures: Option<Handle<Image>>,
+    /// Reconstruction parameters for chunks with quantized positions
+    #[uniform(100)]
+    pub quantization: PositionQuantization,
+    /// Aerial perspective parameters (zero strength when disabled)
+    #[uniform(101)]
+    pub aerial: AerialUniform,
+    /// Layer weights looked up by (normalized height, slope), one layer per channel
+    #[texture(102)]
+    #[sampler(103)]
+    pub splatmap: Option<Handle<Image>>,
+    /// Layer textures stacked into a 2D array, in [`TerrainLayers`] order
+    #[texture(104, dimension = "2d_array")]
+    #[sampler(105)]
+    pub layer_textures: Option<Handle<Image>>,
+    /// Splatting parameters (zero layers falls back to vertex colors)
+    #[uniform(106)]
+    pub splat: SplatUniform,
+    /// Geomorph distance bands derived from the LOD distances
+    #[uniform(107)]
+    pub morph: MorphUniform,
+    /// Color of the contour lines; alpha scales how strongly they cover the terrain
+    #[uniform(108)]
+    pub contour_color: LinearRgba,
+    /// World-space height between contour lines (0 disables them)
+    #[uniform(108)]
+    pub contour_interval: f32,
+    /// Color lit fragments fade toward with camera distance
+    #[uniform(109)]
+    pub fog_color: LinearRgba,
+    /// Camera distance where the fog starts
+    #[uniform(109)]
+    pub fog_start: f32,
+    /// Camera distance where fragments are fully fogged (0 disables the fog)
+    #[uniform(109)]
+    pub fog_end: f32,
+    /// Debug view replacing the shaded color (see [`crate::debug::TerrainDebugMode`])
+    #[uniform(110)]
+    pub debug: DebugUniform,
+}
+
+impl TerrainMaterialExtension {
+    /// Extension with the tiling of the given layers
+    ///
+    /// The splatmap and layer texture array need image assets, so
+    /// [`setup_terrain_material`] and [`update_terrain_layer_textures`] fill them
+    /// in from the [`TerrainLayers`] resource.
+    pub fn from_layers(layers: &TerrainLayers) -> Self {
+        let mut tiling = Vec4::ONE;
+        for (i, layer) in layers.layers().iter().take(MAX_SPLAT_LAYERS).enumerate() {
+            tiling[i] = layer.tiling;
+        }
+        Self {
+            splat: SplatUniform {
+                tiling,
+                ..default()
+            },
+            ..default()
+        }
+    }
+
+    /// Sample splatted layers with triplanar projection so cliffs don't stretch
+    ///
+    /// `sharpness` is the exponent applied to the normal's components when
+    /// blending the X/Y/Z projections; 0 keeps the planar XZ projection.
+    pub fn with_triplanar(mut self, sharpness: f32) -> Self {
+        self.splat.triplanar_sharpness = sharpness.max(0.0);
+        self
+    }
+
+    /// Draw elevation contour lines every `interval` world units of height
+    pub fn with_contours(mut self, interval: f32, color: Color) -> Self {
+        self.contour_interval = interval.max(0.0);
+        self.contour_color = color.to_linear();
+        self
+    }
+
+    /// Fade lit fragments toward `color` between `start` and `end` camera distance
+    pub fn with_fog(mut self, color: Color, start: f32, end: f32) -> Self {
+        self.fog_color = color.to_linear();
+        self.fog_start = start.max(0.0);
+        self.fog_end = end.max(self.fog_start);
+        self
+    }
 }
 
 impl MaterialExtension for TerrainMaterialExtension {
@@ -48,7 +156,9 @@ impl MaterialExtension for TerrainMaterialExtension {
         "shaders/terrain.wgsl".into()
     }
 
-    // Use default fragment shader - vertex colors are handled by StandardMaterial
+    fn fragment_shader() -> ShaderRef {
+        "shaders/terrain_fragment.wgsl".into()
+    }
 
     fn specialize(
         _pipeline: &MaterialExtensionPipeline,
@@ -69,25 +179,160 @@ impl MaterialExtension for TerrainMaterialExtension {
             return Ok(());
         }
 
-        // Configure vertex buffer layout with our custom morph_height attribute for forward pass
-        let vertex_layout = layout.0.get_layout(&[
-            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+        // Configure vertex buffer layout for the forward pass, including the custom
+        // morph_height attribute only when the mesh was generated with it
+        let mut attributes = vec![
+            if layout.0.contains(ATTRIBUTE_QUANTIZED_POSITION) {
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("TERRAIN_QUANTIZED".into());
+                ATTRIBUTE_QUANTIZED_POSITION.at_shader_location(0)
+            } else {
+                Mesh::ATTRIBUTE_POSITION.at_shader_location(0)
+            },
             Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
             Mesh::ATTRIBUTE_UV_0.at_shader_location(2),
             Mesh::ATTRIBUTE_COLOR.at_shader_location(5),
-            ATTRIBUTE_MORPH_HEIGHT.at_shader_location(17),
-        ])?;
+        ];
+        if layout.0.contains(Mesh::ATTRIBUTE_UV_1) {
+            attributes.push(Mesh::ATTRIBUTE_UV_1.at_shader_location(3));
+        }
+        if layout.0.contains(ATTRIBUTE_MORPH_HEIGHT) {
+            attributes.push(ATTRIBUTE_MORPH_HEIGHT.at_shader_location(17));
+            descriptor.vertex.shader_defs.push("TERRAIN_MORPH".into());
+        }
+        let vertex_layout = layout.0.get_layout(&attributes)?;
 
         descriptor.vertex.buffers = vec![vertex_layout];
         Ok(())
     }
 }
 
+/// GPU representation of [`AerialConfig`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, ShaderType)]
+pub struct AerialUniform {
+    pub color: Vec4,
+    pub start_distance: f32,
+    pub end_distance: f32,
+    pub max_strength: f32,
+    pub height_falloff: f32,
+}
+
+impl From<Option<&AerialConfig>> for AerialUniform {
+    fn from(aerial: Option<&AerialConfig>) -> Self {
+        let Some(aerial) = aerial else {
+            return Self::default();
+        };
+        Self {
+            color: Vec4::from_array(aerial.color),
+            start_distance: aerial.start_distance,
+            end_distance: aerial.end_distance,
+            max_strength: aerial.max_strength,
+            height_falloff: aerial.height_falloff,
+        }
+    }
+}
+
+/// GPU splatting parameters
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, ShaderType)]
+pub struct SplatUniform {
+    /// Texture tiling per layer
+    pub tiling: Vec4,
+    /// World height mapped to normalized height 0
+    pub min_height: f32,
+    /// World height mapped to normalized height 1
+    pub max_height: f32,
+    /// Layers present in the texture array
+    pub layer_count: u32,
+    /// Triplanar blend exponent (0 = planar XZ projection)
+    pub triplanar_sharpness: f32,
+}
+
+/// GPU geomorph distances, one band per LOD level (finest first), four per vector
+///
+/// Each chunk carries its LOD in its [`MeshTag`](bevy::mesh::MeshTag), and its
+/// vertices blend from their own height at `start[lod]` to their morph height
+/// at `end[lod]`. A chunk held at a coarser LOD by hysteresis keeps morphing
+/// within its own band instead of the band its camera distance falls into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, ShaderType)]
+pub struct MorphUniform {
+    pub start: [Vec4; MAX_MORPH_LODS / 4],
+    pub end: [Vec4; MAX_MORPH_LODS / 4],
+}
+
+/// GPU debug view selection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, ShaderType)]
+pub struct DebugUniform {
+    /// Unlit color drawn by the LOD heatmap
+    pub lod_color: Vec4,
+    /// 0 = shaded normally, 1 = LOD heatmap, 2 = world normals
+    pub view: u32,
+}
+
+impl MorphUniform {
+    /// Morph over the last part of each LOD band so vertices reach the coarser
+    /// LOD's heights by the time the chunk switches
+    ///
+    /// LODs past [`MAX_MORPH_LODS`] and the coarsest LOD don't morph.
+    pub fn from_lod_distances(lod_distances: &[f32]) -> Self {
+        let mut morph = Self {
+            start: [Vec4::splat(NO_MORPH_DISTANCE); MAX_MORPH_LODS / 4],
+            end: [Vec4::splat(NO_MORPH_DISTANCE); MAX_MORPH_LODS / 4],
+        };
+        let mut band_start = 0.0;
+        for (lod, &band_end) in lod_distances.iter().take(MAX_MORPH_LODS).enumerate() {
+            morph.start[lod / 4][lod % 4] = band_end - (band_end - band_start) * MORPH_REGION;
+            morph.end[lod / 4][lod % 4] = band_end;
+            band_start = band_end;
+        }
+        morph
+    }
+
+    /// Geomorph bands for the config's LOD metric
+    ///
+    /// Screen-space error switches LODs at distances that depend on each
+    /// node's size, so no per-LOD band fits and morphing is turned off.
+    pub fn from_config(config: &TerrainConfig) -> Self {
+        match config.lod_metric {
+            LodMetric::Distance => Self::from_lod_distances(&config.lod_distances),
+            LodMetric::ScreenSpaceError { .. } => Self::from_lod_distances(&[]),
+        }
+    }
+
+    /// Morph start and end distance of one LOD level
+    pub fn band(&self, lod: usize) -> (f32, f32) {
+        let lod = lod.min(MAX_MORPH_LODS - 1);
+        (self.start[lod / 4][lod % 4], self.end[lod / 4][lod % 4])
+    }
+}
+
 /// Initialize the shared terrain material once at startup
 pub fn setup_terrain_material(
     mut materials: ResMut<Assets<TerrainMaterial>>,
     mut terrain_material: ResMut<TerrainMaterialHandle>,
+    config: Res<TerrainConfig>,
+    layers: Option<Res<TerrainLayers>>,
+    images: Option<ResMut<Assets<Image>>>,
 ) {
+    let mut extension = layers
+        .as_deref()
+        .map(TerrainMaterialExtension::from_layers)
+        .unwrap_or_default();
+    extension = extension.with_triplanar(config.triplanar_sharpness);
+    extension.aerial = config.aerial_perspective.as_ref().into();
+    extension.morph = MorphUniform::from_config(&config);
+    // Same height normalization as the noise terrain: 0 at the sea floor, 1 at max_height
+    extension.splat.min_height = -config.water_level * config.vertical_exaggeration;
+    extension.splat.max_height =
+        (config.max_height - config.water_level) * config.vertical_exaggeration;
+    // Headless apps have no image assets and nothing to splat
+    if let (Some(layers), Some(mut images)) = (layers, images)
+        && !layers.layers().is_empty()
+    {
+        extension.splatmap = Some(images.add(layers.build_splatmap()));
+    }
+
     terrain_material.handle = Some(materials.add(ExtendedMaterial {
         base: StandardMaterial {
             base_color: Color::WHITE, // Vertex colors will modulate this
@@ -96,11 +341,142 @@ pub fn setup_terrain_material(
             reflectance: 0.25,
             ..default()
         },
-        extension: TerrainMaterialExtension::default(),
+        extension,
     }));
 }
 
-/// Configuration for terrain texture layers (for future splatting support)
+/// System: Rebuild the geomorph bands of every terrain material when the LOD distances or metric change
+///
+/// Covers the shared material as well as per-chunk and debug copies of it.
+pub fn update_terrain_morph(
+    config: Res<TerrainConfig>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+    mut applied: Local<Option<MorphUniform>>,
+) {
+    if !config.is_changed() {
+        return;
+    }
+    let morph = MorphUniform::from_config(&config);
+    if *applied == Some(morph) {
+        return;
+    }
+    *applied = Some(morph);
+
+    for (_, material) in materials.iter_mut() {
+        material.extension.morph = morph;
+    }
+}
+
+/// Stack the layer textures into the material's texture array once they've loaded
+///
+/// Runs again whenever [`TerrainLayers`] changes, refreshing the splatmap and
+/// tiling too; the previous array stays bound until the new textures load.
+/// Layer images must share the same size and uncompressed format; otherwise
+/// splatting stays off and the terrain keeps its vertex colors.
+pub fn update_terrain_layer_textures(
+    layers: Option<Res<TerrainLayers>>,
+    terrain_material: Res<TerrainMaterialHandle>,
+    images: Option<ResMut<Assets<Image>>>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+    mut built: Local<bool>,
+) {
+    let (Some(layers), Some(mut images), Some(handle)) =
+        (layers, images, terrain_material.handle.as_ref())
+    else {
+        return;
+    };
+    if layers.is_changed() {
+        *built = false;
+        // setup_terrain_material already covered layers present at startup
+        if !layers.is_added()
+            && let Some(material) = materials.get_mut(handle)
+        {
+            material.extension.splat.tiling =
+                TerrainMaterialExtension::from_layers(&layers).splat.tiling;
+            material.extension.splatmap =
+                (!layers.layers().is_empty()).then(|| images.add(layers.build_splatmap()));
+        }
+    }
+    if *built {
+        return;
+    }
+
+    let mut loaded = Vec::new();
+    for layer in layers.layers().iter().take(MAX_SPLAT_LAYERS) {
+        let Some(image) = images.get(&layer.texture) else {
+            return;
+        };
+        loaded.push(image);
+    }
+    *built = true;
+
+    let layer_count = loaded.len() as u32;
+    let array = stack_layer_images(&loaded).map(|array| images.add(array));
+    if let Some(material) = materials.get_mut(handle) {
+        // Nothing to sample without an array, so fall back to vertex colors
+        material.extension.splat.layer_count = if array.is_some() { layer_count } else { 0 };
+        material.extension.layer_textures = array;
+    }
+}
+
+/// Combine equally sized 2D images into one repeating 2D array texture
+fn stack_layer_images(images: &[&Image]) -> Option<Image> {
+    let first = images.first()?;
+    let size = first.texture_descriptor.size;
+    let format = first.texture_descriptor.format;
+    let Ok(pixel_size) = format.pixel_size() else {
+        warn!("Terrain layer textures must be uncompressed, got {format:?}");
+        return None;
+    };
+    let layer_bytes = size.width as usize * size.height as usize * pixel_size;
+
+    let mut data = Vec::with_capacity(layer_bytes * images.len());
+    for image in images {
+        let descriptor = &image.texture_descriptor;
+        if descriptor.size != size || descriptor.format != format {
+            warn!(
+                "Terrain layer textures must all be {}x{} {format:?}, got {}x{} {:?}",
+                size.width,
+                size.height,
+                descriptor.size.width,
+                descriptor.size.height,
+                descriptor.format
+            );
+            return None;
+        }
+        // Only the top mip level is stacked
+        let Some(pixels) = image.data.as_ref().and_then(|data| data.get(..layer_bytes)) else {
+            warn!("Terrain layer texture holds fewer than the {layer_bytes} bytes its size needs");
+            return None;
+        };
+        data.extend_from_slice(pixels);
+    }
+
+    let mut array = Image::new(
+        Extent3d {
+            width: size.width,
+            height: size.height,
+            depth_or_array_layers: images.len() as u32,
+        },
+        TextureDimension::D2,
+        data,
+        format,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // A single layer would otherwise default to a plain 2D view
+    array.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::D2Array),
+        ..default()
+    });
+    array.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::linear()
+    });
+    Some(array)
+}
+
+/// Configuration for a splatted terrain texture layer
 #[derive(Clone, Debug)]
 pub struct TerrainLayer {
     /// Name of this layer
@@ -116,7 +492,11 @@ pub struct TerrainLayer {
 }
 
 /// Builder for configuring terrain layers
-#[derive(Default, Clone)]
+///
+/// Insert as a resource before startup to splat up to [`MAX_SPLAT_LAYERS`]
+/// textures by height and slope; later layers are ignored. Changing the
+/// resource afterwards re-stacks the textures and rebuilds the splatmap.
+#[derive(Resource, Default, Clone)]
 pub struct TerrainLayers {
     layers: Vec<TerrainLayer>,
 }
@@ -167,6 +547,56 @@ impl TerrainLayers {
     pub fn layers(&self) -> &[TerrainLayer] {
         &self.layers
     }
+
+    /// Unnormalized weight of each layer at a normalized height and slope
+    ///
+    /// Layers are fully weighted inside both of their ranges and fade out over
+    /// a short distance past the range edges.
+    pub fn splat_weights(&self, height: f32, slope: f32) -> Vec4 {
+        let mut weights = Vec4::ZERO;
+        for (i, layer) in self.layers.iter().take(MAX_SPLAT_LAYERS).enumerate() {
+            weights[i] =
+                range_weight(&layer.height_range, height) * range_weight(&layer.slope_range, slope);
+        }
+        weights
+    }
+
+    /// Bake [`Self::splat_weights`] into a texture indexed by (height, slope)
+    pub fn build_splatmap(&self) -> Image {
+        let max = (SPLATMAP_RESOLUTION - 1) as f32;
+        let mut data = Vec::with_capacity((SPLATMAP_RESOLUTION * SPLATMAP_RESOLUTION * 4) as usize);
+        for y in 0..SPLATMAP_RESOLUTION {
+            for x in 0..SPLATMAP_RESOLUTION {
+                let weights = self.splat_weights(x as f32 / max, y as f32 / max);
+                data.extend(
+                    weights
+                        .to_array()
+                        .map(|w| (w.clamp(0.0, 1.0) * 255.0).round() as u8),
+                );
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: SPLATMAP_RESOLUTION,
+                height: SPLATMAP_RESOLUTION,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
+}
+
+/// 1 inside `range`, fading to 0 over [`SPLAT_BLEND`] past either end
+fn range_weight(range: &std::ops::Range<f32>, value: f32) -> f32 {
+    let below = ((value - (range.start - SPLAT_BLEND)) / SPLAT_BLEND).clamp(0.0, 1.0);
+    let above = (((range.end + SPLAT_BLEND) - value) / SPLAT_BLEND).clamp(0.0, 1.0);
+    below.min(above)
 }
 
 #[cfg(test)]
@@ -179,4 +609,249 @@ mod tests {
         let layers = TerrainLayers::new();
         assert!(layers.layers().is_empty());
     }
+
+    #[test]
+    fn test_splat_weights_follow_ranges() {
+        let layers = TerrainLayers::new()
+            .add("grass", Handle::default(), 0.0..0.5, 0.0..0.3)
+            .add_with_tiling("rock", Handle::default(), 0.5..1.0, 0.4..1.0, 4.0);
+
+        assert_eq!(
+            layers.splat_weights(0.2, 0.1),
+            Vec4::new(1.0, 0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            layers.splat_weights(0.8, 0.7),
+            Vec4::new(0.0, 1.0, 0.0, 0.0)
+        );
+        // Nothing covers high, flat ground
+        assert_eq!(layers.splat_weights(0.8, 0.1), Vec4::ZERO);
+        // Halfway through the fade past the grass height range
+        let fading = layers.splat_weights(0.5 + SPLAT_BLEND * 0.5, 0.1);
+        assert!((fading.x - 0.5).abs() < 1e-5);
+
+        let extension = TerrainMaterialExtension::from_layers(&layers);
+        assert_eq!(extension.splat.tiling, Vec4::new(1.0, 4.0, 1.0, 1.0));
+        assert_eq!(extension.splat.triplanar_sharpness, 0.0);
+        assert_eq!(extension.with_triplanar(6.0).splat.triplanar_sharpness, 6.0);
+    }
+
+    #[test]
+    fn test_contour_uniform_is_set() {
+        let material = TerrainMaterialExtension::default();
+        assert_eq!(material.contour_interval, 0.0);
+
+        let material = material.with_contours(10.0, Color::BLACK.with_alpha(0.5));
+        assert_eq!(material.contour_interval, 10.0);
+        assert_eq!(material.contour_color, LinearRgba::new(0.0, 0.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_fog_uniforms_from_builder() {
+        assert_eq!(TerrainMaterialExtension::default().fog_end, 0.0);
+
+        let haze = Color::srgb(0.7, 0.75, 0.8);
+        let material = TerrainMaterialExtension::default().with_fog(haze, 500.0, 4000.0);
+        assert_eq!(material.fog_color, haze.to_linear());
+        assert_eq!(material.fog_start, 500.0);
+        assert_eq!(material.fog_end, 4000.0);
+    }
+
+    #[test]
+    fn test_aerial_uniform_follows_config() {
+        let aerial = AerialConfig {
+            color: [0.6, 0.7, 0.9, 1.0],
+            start_distance: 100.0,
+            end_distance: 1000.0,
+            max_strength: 0.5,
+            height_falloff: 0.01,
+        };
+        let uniform_for = |config: TerrainConfig| {
+            let mut app = App::new();
+            app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+                .init_asset::<TerrainMaterial>()
+                .init_resource::<TerrainMaterialHandle>()
+                .insert_resource(config)
+                .add_systems(Startup, setup_terrain_material);
+            app.update();
+            let handle = app
+                .world()
+                .resource::<TerrainMaterialHandle>()
+                .handle
+                .clone();
+            let materials = app.world().resource::<Assets<TerrainMaterial>>();
+            materials.get(&handle.unwrap()).unwrap().extension.aerial
+        };
+
+        // The shader tints by max_strength, so a disabled config leaves colors alone
+        assert_eq!(uniform_for(TerrainConfig::default()).max_strength, 0.0);
+
+        let uniform = uniform_for(TerrainConfig::builder().aerial_perspective(aerial).build());
+        assert_eq!(uniform.color, Vec4::new(0.6, 0.7, 0.9, 1.0));
+        assert_eq!(
+            (uniform.start_distance, uniform.end_distance),
+            (100.0, 1000.0)
+        );
+        assert_eq!(uniform.max_strength, 0.5);
+        assert_eq!(uniform.height_falloff, 0.01);
+    }
+
+    #[test]
+    fn test_morph_bands_end_at_lod_distances() {
+        let morph = MorphUniform::from_lod_distances(&[300.0, 1000.0, 2500.0]);
+        assert_eq!(morph.end[0].truncate(), Vec3::new(300.0, 1000.0, 2500.0));
+        assert_eq!(morph.start[0].truncate(), Vec3::new(210.0, 790.0, 2050.0));
+        // The coarsest LOD has nothing to morph into
+        assert!(morph.band(3).0 >= NO_MORPH_DISTANCE);
+    }
+
+    #[test]
+    fn test_morph_bands_cover_every_lod_and_follow_config() {
+        let distances: Vec<f32> = (1..=6).map(|lod| lod as f32 * 100.0).collect();
+        let morph = MorphUniform::from_lod_distances(&distances);
+        assert_eq!(morph.band(4), (470.0, 500.0));
+        assert_eq!(morph.band(5), (570.0, 600.0));
+        assert!(morph.band(6).0 >= NO_MORPH_DISTANCE);
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<TerrainMaterial>()
+            .insert_resource(TerrainConfig::default())
+            .add_systems(Update, update_terrain_morph);
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<TerrainMaterial>>()
+            .add(TerrainMaterial::default());
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<TerrainConfig>()
+            .lod_distances = distances;
+        app.update();
+        let materials = app.world().resource::<Assets<TerrainMaterial>>();
+        assert_eq!(materials.get(&handle).unwrap().extension.morph, morph);
+    }
+
+    #[test]
+    fn test_screen_space_error_disables_morph() {
+        let config = TerrainConfig::builder()
+            .lod_metric(LodMetric::ScreenSpaceError { pixel_error: 4.0 })
+            .build();
+        let morph = MorphUniform::from_config(&config);
+        for lod in 0..MAX_MORPH_LODS {
+            assert!(morph.band(lod).0 >= NO_MORPH_DISTANCE, "LOD {lod} morphs");
+        }
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<TerrainMaterial>()
+            .insert_resource(TerrainConfig::default())
+            .add_systems(Update, update_terrain_morph);
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<TerrainMaterial>>()
+            .add(TerrainMaterial::default());
+        app.update();
+
+        // Switching the metric alone rebuilds the bands
+        app.world_mut().resource_mut::<TerrainConfig>().lod_metric = config.lod_metric;
+        app.update();
+        let materials = app.world().resource::<Assets<TerrainMaterial>>();
+        assert_eq!(materials.get(&handle).unwrap().extension.morph, morph);
+    }
+
+    #[test]
+    fn test_layer_textures_stack_into_array() {
+        let solid = |value: u8| {
+            Image::new_fill(
+                Extent3d {
+                    width: 2,
+                    height: 2,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[value; 4],
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::RENDER_WORLD,
+            )
+        };
+        let (grass, rock) = (solid(10), solid(200));
+
+        let array = stack_layer_images(&[&grass, &rock]).unwrap();
+        assert_eq!(array.texture_descriptor.size.depth_or_array_layers, 2);
+        let data = array.data.unwrap();
+        assert!(data[..16].iter().all(|&b| b == 10));
+        assert!(data[16..].iter().all(|&b| b == 200));
+
+        let mut small = solid(0);
+        small.resize(Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        });
+        assert!(stack_layer_images(&[&grass, &small]).is_none());
+
+        // A descriptor promising more pixels than the data holds is rejected, not sliced
+        let mut truncated = solid(0);
+        truncated.data.as_mut().unwrap().truncate(8);
+        assert!(stack_layer_images(&[&grass, &truncated]).is_none());
+    }
+
+    #[test]
+    fn test_layer_textures_rebuild_when_layers_change() {
+        let solid = |value: u8| {
+            Image::new_fill(
+                Extent3d {
+                    width: 2,
+                    height: 2,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[value; 4],
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::RENDER_WORLD,
+            )
+        };
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TerrainMaterial>()
+            .add_systems(Update, update_terrain_layer_textures);
+        let (grass, rock) = {
+            let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+            (images.add(solid(10)), images.add(solid(200)))
+        };
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<TerrainMaterial>>()
+            .add(TerrainMaterial::default());
+        app.insert_resource(TerrainMaterialHandle {
+            handle: Some(handle.clone()),
+        })
+        .insert_resource(TerrainLayers::new().add(
+            "grass",
+            grass.clone(),
+            0.0..1.0,
+            0.0..1.0,
+        ));
+        app.update();
+
+        let layer_count = |app: &App| {
+            let materials = app.world().resource::<Assets<TerrainMaterial>>();
+            materials.get(&handle).unwrap().extension.splat.layer_count
+        };
+        assert_eq!(layer_count(&app), 1);
+
+        app.insert_resource(
+            TerrainLayers::new()
+                .add("grass", grass, 0.0..0.5, 0.0..1.0)
+                .add_with_tiling("rock", rock, 0.5..1.0, 0.0..1.0, 4.0),
+        );
+        app.update();
+        assert_eq!(layer_count(&app), 2);
+        let materials = app.world().resource::<Assets<TerrainMaterial>>();
+        let extension = &materials.get(&handle).unwrap().extension;
+        assert_eq!(extension.splat.tiling.y, 4.0);
+        assert!(extension.splatmap.is_some());
+    }
 }
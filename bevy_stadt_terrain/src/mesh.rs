@@ -3,22 +3,436 @@
 //! Generates terrain meshes with smooth normals, vertex colors for biomes,
 //! and morph heights for smooth LOD transitions.
 
-use crate::config::TerrainConfig;
-use crate::heightmap::{TerrainNoise, sample_terrain_height};
-use crate::material::ATTRIBUTE_MORPH_HEIGHT;
+use crate::config::{ConfigError, SeamMode, TerrainConfig, TriangulationPattern};
+use crate::heightmap::{
+    HeightmapHandle, HeightmapSource, TerrainNoise, sample_terrain_height_band_limited,
+};
+use crate::material::{ATTRIBUTE_MORPH_HEIGHT, ATTRIBUTE_QUANTIZED_POSITION};
 use bevy::asset::RenderAssetUsages;
-use bevy::mesh::Indices;
+use bevy::camera::primitives::Aabb;
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+use bevy::mesh::{Indices, VertexAttributeValues};
 use bevy::prelude::*;
-use bevy::render::render_resource::PrimitiveTopology;
+use bevy::render::render_resource::{PrimitiveTopology, ShaderType};
+use std::sync::Arc;
+
+/// Vertex color callback: (height, normal, world_x, world_z) -> RGBA
+pub type VertexColorFn = Arc<dyn Fn(f32, Vec3, f32, f32) -> [f32; 4] + Send + Sync>;
+
+/// Hole callback: (x, z) in terrain-local space -> true inside a hole
+pub type HolePredicateFn = Arc<dyn Fn(f32, f32) -> bool + Send + Sync>;
+
+/// Regions cut out of the terrain surface, e.g. cave entrances or building foundations
+///
+/// Triangles whose center falls inside a hole are left out of the mesh, and
+/// skirts are not generated along edges that border a hole.
+#[derive(Clone, Default)]
+pub struct HoleMask {
+    /// Terrain-local XZ rectangles to cut out
+    pub rects: Vec<Aabb2d>,
+    /// Extra shapes as a callback, consulted after the rectangles
+    pub predicate: Option<HolePredicateFn>,
+}
+
+impl HoleMask {
+    /// Cut out a terrain-local XZ rectangle
+    pub fn with_rect(mut self, rect: Aabb2d) -> Self {
+        self.rects.push(rect);
+        self
+    }
+
+    /// Cut out every point the callback returns true for
+    pub fn with_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(f32, f32) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Whether the mask cuts anything at all
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty() && self.predicate.is_none()
+    }
+
+    /// Whether a terrain-local XZ point lies inside a hole
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.rects
+            .iter()
+            .any(|rect| point.cmpge(rect.min).all() && point.cmple(rect.max).all())
+            || self
+                .predicate
+                .as_ref()
+                .is_some_and(|predicate| predicate(point.x, point.y))
+    }
+
+    /// Copy of the mask with only the rectangles overlapping `area`
+    pub fn clipped_to(&self, area: Aabb2d) -> Self {
+        Self {
+            rects: self
+                .rects
+                .iter()
+                .filter(|rect| rect.intersects(&area))
+                .copied()
+                .collect(),
+            predicate: self.predicate.clone(),
+        }
+    }
+}
+
+/// How vertex normals are assigned
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Shading {
+    /// Shared vertices with normals averaged from neighboring heights
+    #[default]
+    Smooth,
+    /// Every triangle gets its own three vertices carrying the face normal
+    ///
+    /// Gives a faceted low-poly look at roughly three times the vertex
+    /// count (six vertices per grid quad instead of about one), which costs
+    /// memory, upload time, and vertex shading work.
+    Flat,
+}
+
+/// Optional per-mesh generation settings that don't belong on [`TerrainConfig`]
+#[derive(Clone, Default)]
+pub struct MeshGenOptions {
+    /// Replaces the built-in biome coloring when set
+    pub vertex_color: Option<VertexColorFn>,
+    /// Insert `Mesh::ATTRIBUTE_TANGENT` for normal-mapped layers
+    pub tangents: bool,
+    /// Coarser-neighbor levels per edge, ordered -Z, +X, +Z, -X
+    ///
+    /// Each entry is how many times the neighbor's vertex spacing along that
+    /// edge doubles this chunk's; 0 leaves the edge untouched. Edge normals
+    /// use the neighbor's spacing in every seam mode; [`SeamMode::Stitch`]
+    /// also snaps the edge heights onto it.
+    pub neighbor_lods: [u8; 4],
+    /// Regions left out of the mesh
+    pub holes: HoleMask,
+    /// Smooth or faceted normals
+    pub shading: Shading,
+}
+
+impl MeshGenOptions {
+    /// Color vertices with a custom function instead of the biome palette
+    pub fn with_vertex_color<F>(mut self, color_fn: F) -> Self
+    where
+        F: Fn(f32, Vec3, f32, f32) -> [f32; 4] + Send + Sync + 'static,
+    {
+        self.vertex_color = Some(Arc::new(color_fn));
+        self
+    }
+
+    /// Generate per-vertex tangents from the UV layout
+    pub fn with_tangents(mut self) -> Self {
+        self.tangents = true;
+        self
+    }
+
+    /// Match edges to coarser neighbors (see [`Self::neighbor_lods`])
+    pub fn with_neighbor_lods(mut self, neighbor_lods: [u8; 4]) -> Self {
+        self.neighbor_lods = neighbor_lods;
+        self
+    }
+
+    /// Cut holes out of the mesh
+    pub fn with_holes(mut self, holes: HoleMask) -> Self {
+        self.holes = holes;
+        self
+    }
+
+    /// Choose between smooth and faceted normals
+    pub fn with_shading(mut self, shading: Shading) -> Self {
+        self.shading = shading;
+        self
+    }
+}
+
+/// Heights sampled while generating a chunk, for deriving per-chunk data
+#[derive(Clone, Debug)]
+pub struct ChunkSamples {
+    /// Grid coordinates of the chunk
+    pub coords: IVec2,
+    /// World-space XZ position of the first sample (grid corner)
+    pub origin: Vec2,
+    /// World-space spacing between samples
+    pub step: f32,
+    /// Number of samples along each side
+    pub vertices_per_side: u32,
+    /// Surface heights in row-major order (z outer, x inner)
+    pub heights: Vec<f32>,
+}
+
+/// A generated chunk mesh with its vertical extent
+#[derive(Clone, Debug)]
+pub struct GeneratedChunk {
+    pub mesh: Mesh,
+    /// Lowest vertex Y in chunk-local space, including skirts
+    pub min_y: f32,
+    /// Highest vertex Y in chunk-local space
+    pub max_y: f32,
+}
+
+impl ChunkSamples {
+    /// Get the height at a grid sample
+    pub fn height(&self, x: u32, z: u32) -> f32 {
+        self.heights[(z * self.vertices_per_side + x) as usize]
+    }
+
+    /// World-space XZ position of a grid sample
+    pub fn position(&self, x: u32, z: u32) -> Vec2 {
+        self.origin + Vec2::new(x as f32, z as f32) * self.step
+    }
+}
+
+/// Per-chunk parameters for reconstructing quantized vertex positions
+///
+/// Positions are stored as normalized 16-bit values within the chunk's local
+/// bounds; the shader rebuilds them as `origin + quantized * scale`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, ShaderType)]
+pub struct PositionQuantization {
+    /// Minimum corner of the chunk-local bounds
+    pub origin: Vec3,
+    /// Extent of the chunk-local bounds
+    pub scale: Vec3,
+}
+
+impl PositionQuantization {
+    /// Build quantization parameters covering the given local positions
+    pub fn from_positions(positions: &[[f32; 3]]) -> Self {
+        let (min, max) = positions.iter().map(|p| Vec3::from_array(*p)).fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(min, max), p| (min.min(p), max.max(p)),
+        );
+        Self {
+            origin: min,
+            // Avoid a zero extent on perfectly flat chunks
+            scale: (max - min).max(Vec3::splat(f32::EPSILON)),
+        }
+    }
+
+    /// Pack a local position into normalized 16-bit components
+    pub fn quantize(&self, position: Vec3) -> [u16; 4] {
+        let normalized = ((position - self.origin) / self.scale).clamp(Vec3::ZERO, Vec3::ONE);
+        let packed = (normalized * u16::MAX as f32).round();
+        [packed.x as u16, packed.y as u16, packed.z as u16, 0]
+    }
+
+    /// Reconstruct a local position from its packed form
+    pub fn dequantize(&self, packed: [u16; 4]) -> Vec3 {
+        let normalized =
+            Vec3::new(packed[0] as f32, packed[1] as f32, packed[2] as f32) / u16::MAX as f32;
+        self.origin + normalized * self.scale
+    }
+
+    /// Maximum reconstruction error along each axis
+    pub fn tolerance(&self) -> Vec3 {
+        self.scale / u16::MAX as f32 * 0.5
+    }
+
+    /// Local-space bounding box of the quantized chunk
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_min_max(self.origin, self.origin + self.scale)
+    }
+}
+
+/// Add quantized positions alongside a mesh's full-precision ones
+///
+/// Returns the parameters needed to reconstruct them, or `None` if the mesh
+/// has no Float32x3 positions. The forward pass reads the quantized attribute;
+/// `Mesh::ATTRIBUTE_POSITION` stays for the prepass and shadow pipelines and
+/// for Bevy's bounds.
+pub fn quantize_mesh_positions(mesh: &mut Mesh) -> Option<PositionQuantization> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+
+    let quantization = PositionQuantization::from_positions(positions);
+    let packed: Vec<[u16; 4]> = positions
+        .iter()
+        .map(|p| quantization.quantize(Vec3::from_array(*p)))
+        .collect();
+    mesh.insert_attribute(
+        ATTRIBUTE_QUANTIZED_POSITION,
+        VertexAttributeValues::Unorm16x4(packed),
+    );
+
+    Some(quantization)
+}
+
+/// Terrain-local XZ position of a chunk's first grid corner
+///
+/// Chunks are centered on `coords * size`. Mesh generation and physics
+/// colliders sample their grids through [`grid_world_position`], which starts
+/// at this corner, so their surfaces line up.
+pub fn chunk_world_origin(coords: IVec2, size: f32) -> Vec2 {
+    coords.as_vec2() * size - Vec2::splat(size / 2.0)
+}
+
+/// Terrain-local XZ position of grid vertex (`x`, `z`) of a chunk
+///
+/// Positions are computed from integer indices on a global half-step grid
+/// rather than from the chunk origin, so a vertex shared by two adjacent
+/// chunks of the same size and subdivisions gets bit-identical coordinates
+/// in both. Indices may lie outside `0..=subdivisions` for padding samples.
+pub fn grid_world_position(coords: IVec2, size: f32, subdivisions: u32, x: i32, z: i32) -> Vec2 {
+    let subdivisions = subdivisions.max(1) as i64;
+    let half_step = size / (2 * subdivisions) as f32;
+    let axis = |chunk: i32, index: i32| {
+        let half_steps = 2 * chunk as i64 * subdivisions - subdivisions + 2 * index as i64;
+        half_steps as f32 * half_step
+    };
+    Vec2::new(axis(coords.x, x), axis(coords.y, z))
+}
 
 /// Generate terrain mesh with smooth normals and biome-based vertex colors
+///
+/// The returned Y bounds let callers set a tight [`Aabb`] for culling.
 pub fn generate_chunk_mesh(
     coords: IVec2,
     size: f32,
     subdivisions: u32,
     noise: &TerrainNoise,
     config: &TerrainConfig,
-) -> Mesh {
+) -> GeneratedChunk {
+    generate_chunk_mesh_with_samples(
+        coords,
+        size,
+        subdivisions,
+        noise,
+        config,
+        &MeshGenOptions::default(),
+    )
+    .0
+}
+
+/// Generate a terrain mesh and also return the height samples it was built from
+pub fn generate_chunk_mesh_with_samples(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    noise: &TerrainNoise,
+    config: &TerrainConfig,
+    options: &MeshGenOptions,
+) -> (GeneratedChunk, ChunkSamples) {
+    // Coarse LODs skip noise detail finer than their grid step
+    let step = size / subdivisions as f32;
+    let min_feature_size = if config.band_limit_noise { step } else { 0.0 };
+
+    build_chunk_mesh(
+        coords,
+        size,
+        subdivisions,
+        config,
+        options,
+        |x, z| sample_terrain_height_band_limited(x, z, noise, config, min_feature_size),
+        |x, z| {
+            let p = Vec2::new(x, z) + config.noise_origin;
+            (
+                noise.sample_moisture(p.x, p.y),
+                noise.sample_detail(p.x, p.y),
+            )
+        },
+    )
+}
+
+/// Generate a terrain mesh from any height source (closures, images)
+///
+/// Sources carry no moisture or detail noise, so vertex colors use a neutral
+/// moisture and only vary with height and slope.
+pub fn generate_chunk_mesh_from_source(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    source: &dyn HeightmapSource,
+    config: &TerrainConfig,
+) -> GeneratedChunk {
+    generate_chunk_mesh_from_source_with_samples(
+        coords,
+        size,
+        subdivisions,
+        source,
+        config,
+        &MeshGenOptions::default(),
+    )
+    .0
+}
+
+/// [`generate_chunk_mesh_from_source`], also returning the height samples
+pub fn generate_chunk_mesh_from_source_with_samples(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    source: &dyn HeightmapSource,
+    config: &TerrainConfig,
+    options: &MeshGenOptions,
+) -> (GeneratedChunk, ChunkSamples) {
+    build_chunk_mesh(
+        coords,
+        size,
+        subdivisions,
+        config,
+        options,
+        |x, z| source.sample(x, z),
+        |_, _| (0.5, 0.0),
+    )
+}
+
+/// Bake one chunk's mesh outside of any Bevy `App`, e.g. for offline export
+///
+/// `coords` index the grid of `config.chunk_size` chunks and `lod` indexes
+/// `config.lod_subdivisions` (clamped to the last level). Heights come from
+/// `heightmap` the way streaming samples them, including noise terrain and
+/// `vertical_exaggeration`, so this produces the same mesh streaming would
+/// for a chunk with same-LOD neighbors.
+pub fn bake_chunk(
+    coords: IVec2,
+    config: &TerrainConfig,
+    heightmap: &HeightmapHandle,
+    lod: usize,
+) -> Result<Mesh, ConfigError> {
+    let last_lod = config
+        .lod_subdivisions
+        .len()
+        .checked_sub(1)
+        .ok_or(ConfigError::NoLodLevels)?;
+    let subdivisions = config.lod_subdivisions[lod.min(last_lod)];
+
+    let generated = match (heightmap, heightmap.exaggerated_source(config)) {
+        (_, Some(source)) => generate_chunk_mesh_from_source(
+            coords,
+            config.chunk_size,
+            subdivisions,
+            source.as_ref(),
+            config,
+        ),
+        (HeightmapHandle::Noise(noise, _), None) => {
+            generate_chunk_mesh(coords, config.chunk_size, subdivisions, noise, config)
+        }
+        (_, None) => generate_chunk_mesh(
+            coords,
+            config.chunk_size,
+            subdivisions,
+            &TerrainNoise::default(),
+            config,
+        ),
+    };
+    Ok(generated.mesh)
+}
+
+/// Shared mesh builder; `sample_surface` returns (moisture, detail noise) for coloring
+fn build_chunk_mesh(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    config: &TerrainConfig,
+    options: &MeshGenOptions,
+    sample_height: impl Fn(f32, f32) -> f32 + Sync,
+    sample_surface: impl Fn(f32, f32) -> (f32, f32),
+) -> (GeneratedChunk, ChunkSamples) {
     let mut mesh = Mesh::new(
         PrimitiveTopology::TriangleList,
         RenderAssetUsages::default(),
@@ -26,20 +440,28 @@ pub fn generate_chunk_mesh(
 
     let vertices_per_side = subdivisions + 1;
     let step = size / subdivisions as f32;
-    let start_x = coords.x as f32 * size;
-    let start_z = coords.y as f32 * size;
+    let origin = chunk_world_origin(coords, size);
 
     // Generate height map for this chunk (with 1 extra on each side for normal calculation)
-    let mut heights: Vec<Vec<f32>> = Vec::new();
-    for z in 0..=subdivisions + 2 {
-        let mut row = Vec::new();
-        for x in 0..=subdivisions + 2 {
-            let world_x = start_x + (x as f32 - 1.0) * step - size / 2.0;
-            let world_z = start_z + (z as f32 - 1.0) * step - size / 2.0;
-            let height = sample_terrain_height(world_x, world_z, noise, config);
-            row.push(height);
-        }
-        heights.push(row);
+    #[cfg(feature = "parallel")]
+    let mut heights = sample_height_grid_parallel(coords, size, subdivisions, &sample_height);
+    #[cfg(not(feature = "parallel"))]
+    let mut heights = sample_height_grid(coords, size, subdivisions, &sample_height);
+
+    let samples = ChunkSamples {
+        coords,
+        origin,
+        step,
+        vertices_per_side,
+        heights: heights[1..=vertices_per_side as usize]
+            .iter()
+            .flat_map(|row| row[1..=vertices_per_side as usize].iter().copied())
+            .collect(),
+    };
+
+    // Snap edges onto coarser neighbors after capturing the true samples
+    if config.seam_mode == SeamMode::Stitch {
+        stitch_edges(&mut heights, subdivisions, options.neighbor_lods);
     }
 
     // Generate vertices with smooth normals and morph heights
@@ -47,6 +469,7 @@ pub fn generate_chunk_mesh(
     let mut normals: Vec<[f32; 3]> = Vec::new();
     let mut colors: Vec<[f32; 4]> = Vec::new();
     let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut uv1s: Vec<[f32; 2]> = Vec::new();
     let mut morph_heights: Vec<f32> = Vec::new();
 
     for z in 0..vertices_per_side {
@@ -58,30 +481,59 @@ pub fn generate_chunk_mesh(
             positions.push([local_x, height, local_z]);
 
             // Calculate morph height for LOD transitions
-            let morph_height = calculate_morph_height(&heights, x, z);
-            morph_heights.push(morph_height);
+            if config.generate_morph {
+                morph_heights.push(calculate_morph_height(&heights, x, z));
+            }
 
-            // Calculate smooth normal from neighboring heights
-            let normal =
-                calculate_smooth_normal(&heights, (x + 1) as usize, (z + 1) as usize, step);
+            // Calculate smooth normal from neighboring heights; edges facing a
+            // coarser neighbor use its vertex spacing so both sides agree
+            let edge_stride = edge_normal_stride(x, z, subdivisions, options.neighbor_lods);
+            let normal = if edge_stride > 1 {
+                let at = |dx: i32, dz: i32| {
+                    let p = grid_world_position(
+                        coords,
+                        size,
+                        subdivisions,
+                        x as i32 + dx,
+                        z as i32 + dz,
+                    );
+                    sample_height(p.x, p.y)
+                };
+                let s = edge_stride as i32;
+                normal_from_differences(
+                    at(-s, 0),
+                    at(s, 0),
+                    at(0, -s),
+                    at(0, s),
+                    step * edge_stride as f32,
+                )
+            } else {
+                calculate_smooth_normal(&heights, (x + 1) as usize, (z + 1) as usize, step)
+            };
             normals.push(normal);
 
             // Biome color based on height, slope, and moisture
             let normal_vec = Vec3::from_array(normal);
-            let world_x = start_x + local_x;
-            let world_z = start_z + local_z;
-
-            let moisture = noise.sample_moisture(world_x, world_z);
-            let detail_noise_val = noise.sample_detail(world_x, world_z);
-            let color = terrain_to_color(
-                height,
-                moisture,
-                normal_vec,
-                world_x,
-                world_z,
-                config,
-                detail_noise_val,
-            );
+            let Vec2 {
+                x: world_x,
+                y: world_z,
+            } = grid_world_position(coords, size, subdivisions, x as i32, z as i32);
+
+            let color = match &options.vertex_color {
+                Some(color_fn) => color_fn(height, normal_vec, world_x, world_z),
+                None => {
+                    let (moisture, detail_noise_val) = sample_surface(world_x, world_z);
+                    terrain_to_color(
+                        height,
+                        moisture,
+                        normal_vec,
+                        world_x,
+                        world_z,
+                        config,
+                        detail_noise_val,
+                    )
+                }
+            };
             colors.push(color);
 
             // UV coordinates
@@ -89,9 +541,22 @@ pub fn generate_chunk_mesh(
                 x as f32 / subdivisions as f32,
                 z as f32 / subdivisions as f32,
             ]);
+
+            // World-space UVs for detail masks and decals that shouldn't tile with UV0
+            if config.generate_uv1 {
+                uv1s.push([
+                    world_x * config.detail_uv_scale,
+                    world_z * config.detail_uv_scale,
+                ]);
+            }
         }
     }
 
+    // Holes are tested in terrain-local space; vertex positions are relative to the chunk center
+    let chunk_center = origin + Vec2::splat(size / 2.0);
+    let in_hole =
+        |local: Vec2| !options.holes.is_empty() && options.holes.contains(chunk_center + local);
+
     // Generate indices for triangles
     let mut indices: Vec<u32> = Vec::new();
     for z in 0..subdivisions {
@@ -101,38 +566,223 @@ pub fn generate_chunk_mesh(
             let bottom_left = (z + 1) * vertices_per_side + x;
             let bottom_right = bottom_left + 1;
 
-            // Triangle 1
-            indices.push(top_left);
-            indices.push(bottom_left);
-            indices.push(top_right);
+            let quad_heights = [
+                heights[(z + 1) as usize][(x + 1) as usize],
+                heights[(z + 1) as usize][(x + 2) as usize],
+                heights[(z + 2) as usize][(x + 1) as usize],
+                heights[(z + 2) as usize][(x + 2) as usize],
+            ];
+
+            let triangles = if split_along_main_diagonal(config.triangulation, x, z, quad_heights) {
+                // Split along top_left -> bottom_right
+                [
+                    [top_left, bottom_left, bottom_right],
+                    [top_left, bottom_right, top_right],
+                ]
+            } else {
+                [
+                    [top_left, bottom_left, top_right],
+                    [top_right, bottom_left, bottom_right],
+                ]
+            };
 
-            // Triangle 2
-            indices.push(top_right);
-            indices.push(bottom_left);
-            indices.push(bottom_right);
+            for triangle in triangles {
+                let center = triangle
+                    .iter()
+                    .map(|&i| Vec2::new(positions[i as usize][0], positions[i as usize][2]))
+                    .sum::<Vec2>()
+                    / 3.0;
+                if !in_hole(center) {
+                    indices.extend(triangle);
+                }
+            }
         }
     }
 
-    // Add skirts to hide LOD cracks
-    add_skirts(
-        &mut positions,
-        &mut normals,
-        &mut colors,
-        &mut uvs,
-        &mut morph_heights,
-        &mut indices,
-        vertices_per_side as usize,
-        config.skirt_depth,
-    );
+    // Add skirts to hide LOD cracks (a non-positive depth turns them off)
+    if config.seam_mode == SeamMode::Skirts && config.skirt_depth > 0.0 {
+        add_skirts(
+            &mut positions,
+            &mut normals,
+            &mut colors,
+            &mut uvs,
+            &mut uv1s,
+            &mut morph_heights,
+            &mut indices,
+            vertices_per_side as usize,
+            config.skirt_depth,
+            config.skirt_color,
+            in_hole,
+        );
+    }
+
+    // Faceted look: give every triangle its own vertices and face normal
+    if options.shading == Shading::Flat {
+        unweld(&mut positions, &indices);
+        unweld(&mut colors, &indices);
+        unweld(&mut uvs, &indices);
+        unweld(&mut uv1s, &indices);
+        unweld(&mut morph_heights, &indices);
+        normals = positions
+            .chunks_exact(3)
+            .flat_map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec3::from_array(triangle[i]));
+                [(b - a).cross(c - a).normalize_or(Vec3::Y).to_array(); 3]
+            })
+            .collect();
+        indices = (0..positions.len() as u32).collect();
+    }
+
+    let (min_y, max_y) = positions
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+            (min.min(p[1]), max.max(p[1]))
+        });
+
+    if options.tangents {
+        let tangents = calculate_tangents(&positions, &normals, &uvs, &indices);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+    }
 
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
     mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
     mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
     mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-    mesh.insert_attribute(ATTRIBUTE_MORPH_HEIGHT, morph_heights);
+    if config.generate_uv1 {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uv1s);
+    }
+    if config.generate_morph {
+        mesh.insert_attribute(ATTRIBUTE_MORPH_HEIGHT, morph_heights);
+    }
     mesh.insert_indices(Indices::U32(indices));
 
-    mesh
+    (GeneratedChunk { mesh, min_y, max_y }, samples)
+}
+
+/// Replace indexed vertex attributes with one copy per index (empty attributes stay empty)
+fn unweld<T: Copy>(values: &mut Vec<T>, indices: &[u32]) {
+    if !values.is_empty() {
+        *values = indices.iter().map(|&i| values[i as usize]).collect();
+    }
+}
+
+/// Sample a chunk's height grid, padded by one sample on each side for normals
+#[cfg(any(test, not(feature = "parallel")))]
+fn sample_height_grid(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    sample_height: &impl Fn(f32, f32) -> f32,
+) -> Vec<Vec<f32>> {
+    (0..=subdivisions + 2)
+        .map(|z| sample_height_row(coords, size, subdivisions, z, sample_height))
+        .collect()
+}
+
+/// [`sample_height_grid`] with rows sampled in parallel on the rayon pool
+///
+/// Every sample runs the same function on the same inputs as the serial path,
+/// so the grid is bit-identical.
+#[cfg(feature = "parallel")]
+fn sample_height_grid_parallel(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    sample_height: &(impl Fn(f32, f32) -> f32 + Sync),
+) -> Vec<Vec<f32>> {
+    use rayon::prelude::*;
+
+    (0..=subdivisions + 2)
+        .into_par_iter()
+        .map(|z| sample_height_row(coords, size, subdivisions, z, sample_height))
+        .collect()
+}
+
+fn sample_height_row(
+    coords: IVec2,
+    size: f32,
+    subdivisions: u32,
+    z: u32,
+    sample_height: &impl Fn(f32, f32) -> f32,
+) -> Vec<f32> {
+    (0..=subdivisions + 2)
+        .map(|x| {
+            let world = grid_world_position(coords, size, subdivisions, x as i32 - 1, z as i32 - 1);
+            sample_height(world.x, world.y)
+        })
+        .collect()
+}
+
+/// Whether a quad should be split along its top_left -> bottom_right diagonal
+///
+/// `heights` are the quad corners ordered top_left, top_right, bottom_left, bottom_right.
+/// The fixed pattern always uses the top_right -> bottom_left diagonal.
+fn split_along_main_diagonal(
+    pattern: TriangulationPattern,
+    x: u32,
+    z: u32,
+    heights: [f32; 4],
+) -> bool {
+    let [top_left, top_right, bottom_left, bottom_right] = heights;
+    match pattern {
+        TriangulationPattern::Fixed => false,
+        TriangulationPattern::Checkerboard => (x + z).is_multiple_of(2),
+        TriangulationPattern::Adaptive => {
+            (top_left - bottom_right).abs() < (top_right - bottom_left).abs()
+        }
+    }
+}
+
+/// Interpolate edge heights along a coarser neighbor's vertex spacing
+///
+/// `heights` is the padded grid from [`sample_height_grid`]; edges are ordered
+/// like [`MeshGenOptions::neighbor_lods`]. Vertices shared with the coarse edge
+/// keep their height, the ones between them move onto the coarse segment.
+fn stitch_edges(heights: &mut [Vec<f32>], subdivisions: u32, neighbor_lods: [u8; 4]) {
+    let subdivisions = subdivisions as usize;
+    let last = subdivisions + 1;
+
+    for (edge, &levels) in neighbor_lods.iter().enumerate() {
+        let stride = (1usize << levels.min(16)).min(subdivisions);
+        if stride <= 1 {
+            continue;
+        }
+
+        // Padded grid (row, column) of the i-th vertex along this edge
+        let cell = |i: usize| match edge {
+            0 => (1, 1 + i),
+            1 => (1 + i, last),
+            2 => (last, 1 + i),
+            _ => (1 + i, 1),
+        };
+
+        for i in 0..=subdivisions {
+            let offset = i % stride;
+            if offset == 0 {
+                continue;
+            }
+            let start = i - offset;
+            let end = (start + stride).min(subdivisions);
+            let ((z0, x0), (z1, x1), (z, x)) = (cell(start), cell(end), cell(i));
+            let t = offset as f32 / (end - start) as f32;
+            heights[z][x] = heights[z0][x0] + (heights[z1][x1] - heights[z0][x0]) * t;
+        }
+    }
+}
+
+/// Coarse neighbor vertex spacing (in grid steps) for a vertex on a stitched edge
+///
+/// Returns 1 for interior vertices and edges facing a same-LOD neighbor.
+/// Corners take the coarser of their two edges.
+fn edge_normal_stride(x: u32, z: u32, subdivisions: u32, neighbor_lods: [u8; 4]) -> u32 {
+    let on_edge = [z == 0, x == subdivisions, z == subdivisions, x == 0];
+    on_edge
+        .iter()
+        .zip(neighbor_lods)
+        .filter(|(on_edge, _)| **on_edge)
+        .map(|(_, levels)| (1u32 << levels.min(16)).min(subdivisions.max(1)))
+        .max()
+        .unwrap_or(1)
 }
 
 /// Helper to add skirts on chunk edges to hide LOD gaps
@@ -142,10 +792,13 @@ fn add_skirts(
     normals: &mut Vec<[f32; 3]>,
     colors: &mut Vec<[f32; 4]>,
     uvs: &mut Vec<[f32; 2]>,
+    uv1s: &mut Vec<[f32; 2]>,
     morph_heights: &mut Vec<f32>,
     indices: &mut Vec<u32>,
     vertices_per_side: usize,
     skirt_depth: f32,
+    skirt_color: Option<[f32; 4]>,
+    in_hole: impl Fn(Vec2) -> bool,
 ) {
     let skirt_height = -skirt_depth;
     let start_vertex = positions.len() as u32;
@@ -174,16 +827,20 @@ fn add_skirts(
     for &idx in &edge_indices {
         let p = positions[idx as usize];
         let n = normals[idx as usize];
-        let c = colors[idx as usize];
+        let c = skirt_color.unwrap_or(colors[idx as usize]);
         let uv = uvs[idx as usize];
-        let mh = morph_heights[idx as usize];
 
         positions.push([p[0], p[1] + skirt_height, p[2]]);
         normals.push(n);
         colors.push(c);
         uvs.push(uv);
+        if let Some(&uv1) = uv1s.get(idx as usize) {
+            uv1s.push(uv1);
+        }
         // Skirt vertices morph to the same relative depth below their source vertex
-        morph_heights.push(mh + skirt_height);
+        if let Some(&mh) = morph_heights.get(idx as usize) {
+            morph_heights.push(mh + skirt_height);
+        }
     }
 
     // Generate skirt indices (quads)
@@ -195,6 +852,12 @@ fn add_skirts(
         let curr_skirt = start_vertex + i as u32;
         let next_skirt = start_vertex + ((i + 1) % skirt_vertex_count) as u32;
 
+        // No skirt hanging across a hole that reaches the chunk edge
+        let (curr, next) = (positions[curr_orig as usize], positions[next_orig as usize]);
+        if in_hole(Vec2::new(curr[0] + next[0], curr[2] + next[2]) * 0.5) {
+            continue;
+        }
+
         // Quad 1
         indices.push(curr_orig);
         indices.push(next_orig);
@@ -207,14 +870,74 @@ fn add_skirts(
     }
 }
 
+/// Per-vertex tangents (xyz + handedness in w) from triangle UV and position deltas
+///
+/// Tangents are accumulated per triangle and Gram-Schmidt orthogonalized
+/// against the vertex normal. Skirt triangles have degenerate UVs and are
+/// skipped; vertices left without a tangent fall back to the +X axis.
+fn calculate_tangents(
+    positions: &[[f32; 3]],
+    normals: &[[f32; 3]],
+    uvs: &[[f32; 2]],
+    indices: &[u32],
+) -> Vec<[f32; 4]> {
+    let mut tangents = vec![Vec3::ZERO; positions.len()];
+    let mut bitangents = vec![Vec3::ZERO; positions.len()];
+
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        let edge1 = Vec3::from_array(positions[b]) - Vec3::from_array(positions[a]);
+        let edge2 = Vec3::from_array(positions[c]) - Vec3::from_array(positions[a]);
+        let duv1 = Vec2::from_array(uvs[b]) - Vec2::from_array(uvs[a]);
+        let duv2 = Vec2::from_array(uvs[c]) - Vec2::from_array(uvs[a]);
+
+        let det = duv1.x * duv2.y - duv2.x * duv1.y;
+        if det.abs() < f32::EPSILON {
+            continue;
+        }
+        let r = 1.0 / det;
+        let tangent = (edge1 * duv2.y - edge2 * duv1.y) * r;
+        let bitangent = (edge2 * duv1.x - edge1 * duv2.x) * r;
+
+        for i in [a, b, c] {
+            tangents[i] += tangent;
+            bitangents[i] += bitangent;
+        }
+    }
+
+    tangents
+        .iter()
+        .zip(&bitangents)
+        .zip(normals)
+        .map(|((&t, &b), &n)| {
+            let n = Vec3::from_array(n);
+            let t = if t.length_squared() > 0.0 { t } else { Vec3::X };
+            let t = (t - n * n.dot(t)).normalize_or(Vec3::X);
+            let w = if n.cross(t).dot(b) < 0.0 { -1.0 } else { 1.0 };
+            [t.x, t.y, t.z, w]
+        })
+        .collect()
+}
+
+/// Smooth normal from central differences on the padded height grid
+///
+/// The grid is sampled at [`grid_world_position`], so the four neighbors of a
+/// vertex on a chunk edge are the same world positions, with the same heights,
+/// as seen from the adjacent chunk. Same-LOD neighbors therefore compute
+/// identical normals along their shared edge and lighting shows no seam.
 fn calculate_smooth_normal(heights: &[Vec<f32>], x: usize, z: usize, step: f32) -> [f32; 3] {
     let left = heights[z][x.saturating_sub(1)];
     let right = heights[z][(x + 1).min(heights[z].len() - 1)];
     let down = heights[z.saturating_sub(1)][x];
     let up = heights[(z + 1).min(heights.len() - 1)][x];
 
-    let dx = (right - left) / (2.0 * step);
-    let dz = (up - down) / (2.0 * step);
+    normal_from_differences(left, right, down, up, step)
+}
+
+/// Normal from heights `spacing` away on either side along X and Z
+fn normal_from_differences(left: f32, right: f32, down: f32, up: f32, spacing: f32) -> [f32; 3] {
+    let dx = (right - left) / (2.0 * spacing);
+    let dz = (up - down) / (2.0 * spacing);
 
     Vec3::new(-dx, 1.0, -dz).normalize().to_array()
 }
@@ -263,6 +986,121 @@ fn calculate_morph_height(heights: &[Vec<f32>], x: u32, z: u32) -> f32 {
     }
 }
 
+/// Dominant biome at a terrain point, as painted by the vertex colors
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Biome {
+    DeepWater,
+    ShallowWater,
+    Sand,
+    Grass,
+    Forest,
+    Tundra,
+    Rock,
+    Snow,
+}
+
+/// Classify the biome the default vertex colors show for a terrain point
+///
+/// Uses the same [`BiomePalette`](crate::config::BiomePalette) thresholds as
+/// mesh coloring and picks whichever side of each blend dominates, so
+/// gameplay logic (e.g. vegetation scatter) agrees with what is rendered.
+/// `height` is the terrain height as sampled, `normal` the surface normal.
+pub fn classify_biome(height: f32, moisture: f32, normal: Vec3, config: &TerrainConfig) -> Biome {
+    let w = BiomeWeights::new(height, moisture, normal, config);
+    let dominant = |t: f32| t >= 0.5;
+
+    if dominant(w.rock) {
+        return Biome::Rock;
+    }
+    if !dominant(w.shore) {
+        return if dominant(w.water_depth) {
+            Biome::ShallowWater
+        } else {
+            Biome::DeepWater
+        };
+    }
+    if dominant(w.highland_to_mountain) {
+        return if dominant(w.snow) {
+            Biome::Snow
+        } else {
+            Biome::Rock
+        };
+    }
+    if dominant(w.lowland_to_highland) {
+        let [to_tundra, to_boreal] = w.highland;
+        return if dominant(to_boreal) {
+            Biome::Forest
+        } else if dominant(to_tundra) {
+            Biome::Tundra
+        } else {
+            Biome::Rock
+        };
+    }
+    let [to_dry_grass, to_lush_grass, to_forest] = w.lowland;
+    if dominant(to_forest) {
+        Biome::Forest
+    } else if dominant(to_lush_grass) || dominant(to_dry_grass) {
+        Biome::Grass
+    } else {
+        Biome::Sand
+    }
+}
+
+/// Blend factors between biome colors, shared by coloring and classification
+struct BiomeWeights {
+    normalized_height: f32,
+    /// Deep -> shallow water
+    water_depth: f32,
+    /// Water -> land
+    shore: f32,
+    /// Sand -> dry grass, dry -> lush grass, lush grass -> forest
+    lowland: [f32; 3],
+    /// Bare rock -> tundra, tundra -> boreal forest
+    highland: [f32; 2],
+    lowland_to_highland: f32,
+    highland_to_mountain: f32,
+    /// Mountain rock -> snow
+    snow: f32,
+    /// Any surface -> steep rock
+    rock: f32,
+}
+
+impl BiomeWeights {
+    fn new(height: f32, moisture: f32, normal: Vec3, config: &TerrainConfig) -> Self {
+        // Biomes follow the unexaggerated relief; `build()` doesn't validate,
+        // so leave heights alone rather than divide by zero
+        let height = if config.vertical_exaggeration > 0.0 {
+            height / config.vertical_exaggeration
+        } else {
+            height
+        };
+        let normalized_height = ((height + config.water_level)
+            / (config.max_height + config.water_level))
+            .clamp(0.0, 1.0);
+        let slope = normal.y; // 1.0 = flat, 0.0 = vertical
+        let palette = &config.biome_palette;
+        let blend = |range: &std::ops::Range<f32>, x: f32| smoothstep(range.start, range.end, x);
+
+        Self {
+            normalized_height,
+            water_depth: blend(&palette.water_depth, normalized_height),
+            shore: blend(&palette.shore, normalized_height),
+            lowland: palette
+                .lowland_moisture
+                .each_ref()
+                .map(|range| blend(range, moisture)),
+            highland: palette
+                .highland_moisture
+                .each_ref()
+                .map(|range| blend(range, moisture)),
+            lowland_to_highland: blend(&palette.lowland_to_highland, normalized_height),
+            highland_to_mountain: blend(&palette.highland_to_mountain, normalized_height),
+            snow: blend(&palette.snow_line, normalized_height),
+            rock: blend(&palette.steep_rock, slope), // Note: inverted range for steep
+        }
+    }
+}
+
 /// Convert terrain properties to biome color with smooth blending
 fn terrain_to_color(
     height: f32,
@@ -273,82 +1111,68 @@ fn terrain_to_color(
     config: &TerrainConfig,
     detail_noise: f32,
 ) -> [f32; 4] {
-    let normalized_height =
-        ((height + config.water_level) / (config.max_height + config.water_level)).clamp(0.0, 1.0);
-
-    let slope = normal.y; // 1.0 = flat, 0.0 = vertical
-
-    // --- Colors ---
-    let color_deep_water = [0.05, 0.15, 0.35, 1.0];
-    let color_shallow_water = [0.15, 0.30, 0.50, 1.0];
-    let color_sand = [0.82, 0.76, 0.58, 1.0];
-    let color_grass_dry = [0.55, 0.60, 0.30, 1.0];
-    let color_grass_lush = [0.22, 0.50, 0.12, 1.0];
-    let color_forest_tropical = [0.08, 0.35, 0.08, 1.0];
-    let color_tundra = [0.50, 0.53, 0.40, 1.0];
-    let color_forest_boreal = [0.12, 0.30, 0.18, 1.0];
-    let color_rock_dark = [0.25, 0.23, 0.21, 1.0];
-    let color_rock_grey = [0.45, 0.45, 0.47, 1.0];
-    let color_snow = [0.93, 0.93, 0.96, 1.0];
+    let w = BiomeWeights::new(height, moisture, normal, config);
+    let palette = &config.biome_palette;
+    // Stops are sRGB-encoded; decode them so blends happen in linear light
+    let stop = |color: [f32; 4]| {
+        if palette.linear_blend {
+            map_rgb(color, Srgba::gamma_function)
+        } else {
+            color
+        }
+    };
 
     // Texture variation from detail noise
-    let variation = detail_noise * 0.06;
+    let variation = detail_noise * palette.detail_variation;
 
     // --- Smooth blending with gradients ---
 
     // Water gradient (deep -> shallow)
     let water_color = lerp_color(
-        color_deep_water,
-        color_shallow_water,
-        smoothstep(0.0, 0.1, normalized_height),
+        stop(palette.deep_water),
+        stop(palette.shallow_water),
+        w.water_depth,
     );
 
-    // Shore transition (water -> land)
-    let shore_blend = smoothstep(0.08, 0.14, normalized_height);
-
     // Lowland biome based on moisture (smooth transitions)
     let lowland_color = {
-        let dry_to_moderate = smoothstep(0.2, 0.4, moisture);
-        let moderate_to_lush = smoothstep(0.5, 0.7, moisture);
-        let lush_to_forest = smoothstep(0.75, 0.9, moisture);
+        let [dry_to_moderate, moderate_to_lush, lush_to_forest] = w.lowland;
 
-        let c1 = lerp_color(color_sand, color_grass_dry, dry_to_moderate);
-        let c2 = lerp_color(c1, color_grass_lush, moderate_to_lush);
-        lerp_color(c2, color_forest_tropical, lush_to_forest)
+        let c1 = lerp_color(stop(palette.sand), stop(palette.grass_dry), dry_to_moderate);
+        let c2 = lerp_color(c1, stop(palette.grass_lush), moderate_to_lush);
+        lerp_color(c2, stop(palette.forest_tropical), lush_to_forest)
     };
 
     // Highland biome based on moisture
     let highland_color = {
-        let dry_to_tundra = smoothstep(0.3, 0.5, moisture);
-        let tundra_to_boreal = smoothstep(0.6, 0.8, moisture);
+        let [dry_to_tundra, tundra_to_boreal] = w.highland;
 
-        let c1 = lerp_color(color_rock_grey, color_tundra, dry_to_tundra);
-        lerp_color(c1, color_forest_boreal, tundra_to_boreal)
+        let c1 = lerp_color(stop(palette.rock_grey), stop(palette.tundra), dry_to_tundra);
+        lerp_color(c1, stop(palette.forest_boreal), tundra_to_boreal)
     };
 
     // Mountain/snow gradient
-    let mountain_color = lerp_color(
-        color_rock_grey,
-        color_snow,
-        smoothstep(0.75, 0.90, normalized_height),
-    );
+    let mountain_color = lerp_color(stop(palette.rock_grey), stop(palette.snow), w.snow);
 
     // Blend lowland -> highland -> mountain based on height
-    let lowland_to_highland = smoothstep(0.30, 0.50, normalized_height);
-    let highland_to_mountain = smoothstep(0.60, 0.80, normalized_height);
-
     let land_color = {
-        let c1 = lerp_color(lowland_color, highland_color, lowland_to_highland);
-        lerp_color(c1, mountain_color, highland_to_mountain)
+        let c1 = lerp_color(lowland_color, highland_color, w.lowland_to_highland);
+        lerp_color(c1, mountain_color, w.highland_to_mountain)
     };
 
     // Blend water -> land
-    let base_color = lerp_color(water_color, land_color, shore_blend);
+    let base_color = lerp_color(water_color, land_color, w.shore);
 
     // Steep slope -> rock (smooth blend)
-    let rock_blend = smoothstep(0.75, 0.60, slope); // Note: inverted range for steep
-    let rock_color = lerp_color(color_rock_dark, color_rock_grey, normalized_height);
-    let final_color = lerp_color(base_color, rock_color, rock_blend);
+    let rock_color = lerp_color(
+        stop(palette.rock_dark),
+        stop(palette.rock_grey),
+        w.normalized_height,
+    );
+    let mut final_color = lerp_color(base_color, rock_color, w.rock);
+    if palette.linear_blend {
+        final_color = map_rgb(final_color, Srgba::gamma_function_inverse);
+    }
 
     // Apply subtle variation
     [
@@ -365,6 +1189,11 @@ fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
     t * t * (3.0 - 2.0 * t)
 }
 
+/// Apply `f` to the RGB channels of a color, keeping alpha
+fn map_rgb(color: [f32; 4], f: impl Fn(f32) -> f32) -> [f32; 4] {
+    [f(color[0]), f(color[1]), f(color[2]), color[3]]
+}
+
 fn lerp_color(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
     let t = t.clamp(0.0, 1.0);
     [
@@ -378,6 +1207,73 @@ fn lerp_color(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::heightmap::sample_terrain_height;
+
+    #[test]
+    fn test_biome_matches_vertex_color() {
+        // Distinct colors and near-instant blends, so every vertex color is
+        // exactly one palette color and shows which path terrain_to_color took
+        let sharp = |at: f32| at..at + 1e-4;
+        let palette = crate::config::BiomePalette {
+            deep_water: [0.0, 0.0, 0.1, 1.0],
+            shallow_water: [0.0, 0.0, 0.2, 1.0],
+            sand: [0.0, 0.0, 0.3, 1.0],
+            grass_dry: [0.0, 0.0, 0.4, 1.0],
+            grass_lush: [0.0, 0.0, 0.5, 1.0],
+            forest_tropical: [0.0, 0.0, 0.6, 1.0],
+            tundra: [0.0, 0.0, 0.7, 1.0],
+            forest_boreal: [0.0, 0.0, 0.8, 1.0],
+            rock_dark: [0.0, 0.0, 0.9, 1.0],
+            rock_grey: [0.0, 0.0, 0.9, 1.0],
+            snow: [0.0, 0.0, 1.0, 1.0],
+            water_depth: sharp(0.05),
+            shore: sharp(0.11),
+            lowland_moisture: [sharp(0.3), sharp(0.6), sharp(0.82)],
+            highland_moisture: [sharp(0.4), sharp(0.7)],
+            lowland_to_highland: sharp(0.4),
+            highland_to_mountain: sharp(0.7),
+            snow_line: sharp(0.82),
+            steep_rock: 0.7..0.6999,
+            detail_variation: 0.0,
+            linear_blend: false,
+        };
+        let config = TerrainConfig::builder()
+            .biome_palette(palette.clone())
+            .build();
+        let colors_of = |biome: Biome| match biome {
+            Biome::DeepWater => vec![palette.deep_water],
+            Biome::ShallowWater => vec![palette.shallow_water],
+            Biome::Sand => vec![palette.sand],
+            Biome::Grass => vec![palette.grass_dry, palette.grass_lush],
+            Biome::Forest => vec![palette.forest_tropical, palette.forest_boreal],
+            Biome::Tundra => vec![palette.tundra],
+            Biome::Rock => vec![palette.rock_grey],
+            Biome::Snow => vec![palette.snow],
+        };
+
+        let mut seen = std::collections::HashSet::new();
+        let range = config.max_height + config.water_level;
+        for h in 0..=50 {
+            let height = (h as f32 + 0.5) / 51.0 * range - config.water_level;
+            for m in 0..=20 {
+                let moisture = (m as f32 + 0.5) / 21.0;
+                for normal_y in [1.0f32, 0.9, 0.5] {
+                    let normal = Vec3::new((1.0 - normal_y * normal_y).sqrt(), normal_y, 0.0);
+                    let biome = classify_biome(height, moisture, normal, &config);
+                    let color = terrain_to_color(height, moisture, normal, 0.0, 0.0, &config, 0.0);
+                    assert!(
+                        colors_of(biome)
+                            .iter()
+                            .any(|c| (c[2] - color[2]).abs() < 1e-4),
+                        "{biome:?} at height {height}, moisture {moisture}, slope {normal_y} \
+                         rendered as {color:?}"
+                    );
+                    seen.insert(biome);
+                }
+            }
+        }
+        assert_eq!(seen.len(), 8, "grid should cover every biome");
+    }
 
     #[test]
     fn test_smoothstep() {
@@ -402,7 +1298,7 @@ mod tests {
         let noise = TerrainNoise::default();
         let config = TerrainConfig::default();
 
-        let mesh = generate_chunk_mesh(IVec2::ZERO, 100.0, 8, &noise, &config);
+        let mesh = generate_chunk_mesh(IVec2::ZERO, 100.0, 8, &noise, &config).mesh;
 
         // Check that mesh has required attributes
         assert!(mesh.attribute(Mesh::ATTRIBUTE_POSITION).is_some());
@@ -411,4 +1307,793 @@ mod tests {
         assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_some());
         assert!(mesh.attribute(ATTRIBUTE_MORPH_HEIGHT).is_some());
     }
+
+    #[test]
+    fn test_bake_chunk_vertex_count() {
+        use crate::heightmap::ProceduralHeightmap;
+
+        let config = TerrainConfig::default();
+        let source = ProceduralHeightmap::new(|x, z| (x * 0.01).sin() * 20.0 + z * 0.1);
+        let heightmap = HeightmapHandle::Procedural(Arc::new(source));
+
+        let mesh = bake_chunk(IVec2::new(2, -1), &config, &heightmap, 1).unwrap();
+
+        // Grid vertices plus one skirt vertex per edge vertex
+        let vertices_per_side = config.lod_subdivisions[1] as usize + 1;
+        assert_eq!(
+            mesh.count_vertices(),
+            vertices_per_side * vertices_per_side + 4 * vertices_per_side
+        );
+    }
+
+    #[test]
+    fn test_zero_exaggeration_keeps_colors_finite() {
+        let config = TerrainConfig::builder().vertical_exaggeration(0.0).build();
+        let color = terrain_to_color(50.0, 0.5, Vec3::Y, 0.0, 0.0, &config, 0.0);
+        assert!(color.iter().all(|c| c.is_finite()));
+    }
+
+    #[test]
+    fn test_bake_chunk_matches_streamed_noise_and_exaggeration() {
+        use crate::streaming::TerrainHeightQuery;
+
+        let config = TerrainConfig::builder().vertical_exaggeration(2.0).build();
+        let noise = TerrainNoise::default();
+        let heightmap = HeightmapHandle::Noise(Box::new(noise.clone()), config.clone());
+        let coords = IVec2::new(1, 3);
+
+        let baked = bake_chunk(coords, &config, &heightmap, 2).unwrap();
+        let streamed = generate_chunk_mesh(
+            coords,
+            config.chunk_size,
+            config.lod_subdivisions[2],
+            &noise,
+            &config,
+        )
+        .mesh;
+        let positions = |mesh: &Mesh| match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions.clone(),
+            _ => panic!("missing positions"),
+        };
+        assert_eq!(positions(&baked), positions(&streamed));
+
+        // Vertices sit on the exaggerated surface, relative to the chunk center
+        let query = TerrainHeightQuery::new(noise, config.clone());
+        let center =
+            chunk_world_origin(coords, config.chunk_size) + Vec2::splat(config.chunk_size / 2.0);
+        let [x, y, z] = positions(&baked)[0];
+        let height = query.get_height(center.x + x, center.y + z);
+        assert!((y - height).abs() < 1e-3, "{y} vs {height}");
+    }
+
+    #[test]
+    fn test_bake_chunk_without_lod_levels_errors() {
+        let config = TerrainConfig {
+            lod_subdivisions: Vec::new(),
+            ..default()
+        };
+        let heightmap = HeightmapHandle::Procedural(Arc::new(
+            crate::heightmap::ProceduralHeightmap::new(|_, _| 0.0),
+        ));
+
+        assert_eq!(
+            bake_chunk(IVec2::ZERO, &config, &heightmap, 0).unwrap_err(),
+            ConfigError::NoLodLevels
+        );
+    }
+
+    #[test]
+    fn test_shared_source_meshes_in_task() {
+        use crate::heightmap::{HeightmapHandle, ProceduralHeightmap};
+        use bevy::tasks::{AsyncComputeTaskPool, TaskPool, block_on};
+        use std::sync::Arc;
+
+        let config = TerrainConfig::default();
+        let handle =
+            HeightmapHandle::Procedural(Arc::new(ProceduralHeightmap::new(|x, z| x * 0.2 - z)));
+        let source = handle.source().unwrap();
+
+        let task_config = config.clone();
+        let task_source = source.clone();
+        let task = AsyncComputeTaskPool::get_or_init(TaskPool::default).spawn(async move {
+            generate_chunk_mesh_from_source(
+                IVec2::new(1, 2),
+                100.0,
+                8,
+                task_source.as_ref(),
+                &task_config,
+            )
+        });
+        let chunk = block_on(task);
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            chunk.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh should have positions");
+        };
+        // Surface vertices sit on the closure's heights (skirts hang below)
+        let origin = Vec2::new(100.0, 200.0);
+        for position in &positions[..81] {
+            let expected = (origin.x + position[0]) * 0.2 - (origin.y + position[2]);
+            assert!((position[1] - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_flat_shading_uses_face_normals() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+        let options = MeshGenOptions::default().with_shading(Shading::Flat);
+
+        let (chunk, _) =
+            generate_chunk_mesh_with_samples(IVec2::ZERO, 100.0, 8, &noise, &config, &options);
+        let mesh = chunk.mesh;
+
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("expected u32 indices");
+        };
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("expected positions");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("expected normals");
+        };
+        assert_eq!(positions.len(), indices.len());
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR).unwrap().len(),
+            indices.len()
+        );
+        assert_eq!(
+            mesh.attribute(ATTRIBUTE_MORPH_HEIGHT).unwrap().len(),
+            indices.len()
+        );
+
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::from_array(positions[triangle[i] as usize]));
+            // Skirt corners repeat a vertex, leaving zero-area triangles
+            let Some(face_normal) = (b - a).cross(c - a).try_normalize() else {
+                continue;
+            };
+            for &i in triangle {
+                assert!(Vec3::from_array(normals[i as usize]).abs_diff_eq(face_normal, 1e-5));
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunk_samples_match_mesh() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+
+        let (GeneratedChunk { mesh, .. }, samples) = generate_chunk_mesh_with_samples(
+            IVec2::new(2, -1),
+            100.0,
+            8,
+            &noise,
+            &config,
+            &MeshGenOptions::default(),
+        );
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh should have Float32x3 positions");
+        };
+
+        assert_eq!(samples.heights.len(), 81);
+        for (i, height) in samples.heights.iter().enumerate() {
+            assert_eq!(positions[i][1], *height);
+        }
+
+        let corner = samples.position(0, 0);
+        assert_eq!(
+            samples.height(0, 0),
+            sample_terrain_height(corner.x, corner.y, &noise, &config)
+        );
+    }
+
+    #[test]
+    fn test_quantized_positions_roundtrip() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+
+        let mut mesh = generate_chunk_mesh(IVec2::new(1, 3), 100.0, 16, &noise, &config).mesh;
+        let Some(VertexAttributeValues::Float32x3(original)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION).cloned()
+        else {
+            panic!("mesh should have Float32x3 positions");
+        };
+
+        let quantization = quantize_mesh_positions(&mut mesh).unwrap();
+        // Prepass, shadows and bounds still read the full-precision positions
+        assert!(matches!(
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+            Some(VertexAttributeValues::Float32x3(kept)) if *kept == original
+        ));
+        let Some(VertexAttributeValues::Unorm16x4(packed)) =
+            mesh.attribute(ATTRIBUTE_QUANTIZED_POSITION)
+        else {
+            panic!("mesh should have Unorm16x4 quantized positions");
+        };
+
+        let tolerance = quantization.tolerance() + Vec3::splat(1e-4);
+        for (original, packed) in original.iter().zip(packed) {
+            let error = (quantization.dequantize(*packed) - Vec3::from_array(*original)).abs();
+            assert!(
+                error.cmple(tolerance).all(),
+                "error {error} exceeds {tolerance}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_adaptive_triangulation_follows_ridge() {
+        // Ridge runs from top_left to bottom_right
+        let ridge = [10.0, 0.0, 2.0, 9.0];
+        assert!(split_along_main_diagonal(
+            TriangulationPattern::Adaptive,
+            0,
+            0,
+            ridge
+        ));
+        assert!(!split_along_main_diagonal(
+            TriangulationPattern::Fixed,
+            0,
+            0,
+            ridge
+        ));
+
+        // Ridge runs from top_right to bottom_left
+        let ridge = [2.0, 10.0, 9.0, 0.0];
+        assert!(!split_along_main_diagonal(
+            TriangulationPattern::Adaptive,
+            0,
+            0,
+            ridge
+        ));
+
+        // Checkerboard alternates between neighbours
+        assert_ne!(
+            split_along_main_diagonal(TriangulationPattern::Checkerboard, 0, 0, ridge),
+            split_along_main_diagonal(TriangulationPattern::Checkerboard, 1, 0, ridge)
+        );
+    }
+
+    #[test]
+    fn test_uv1_is_world_xz() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::builder().generate_uv1(true).build();
+        let coords = IVec2::new(-2, 5);
+
+        let mesh = generate_chunk_mesh(coords, 100.0, 8, &noise, &config).mesh;
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh should have Float32x3 positions");
+        };
+        let Some(VertexAttributeValues::Float32x2(uv1s)) = mesh.attribute(Mesh::ATTRIBUTE_UV_1)
+        else {
+            panic!("mesh should have Float32x2 UV1");
+        };
+
+        assert_eq!(uv1s.len(), positions.len());
+        let chunk_center = coords.as_vec2() * 100.0;
+        for (position, uv1) in positions.iter().zip(uv1s) {
+            assert_eq!(uv1[0], chunk_center.x + position[0]);
+            assert_eq!(uv1[1], chunk_center.y + position[2]);
+        }
+
+        let mesh = generate_chunk_mesh(coords, 100.0, 8, &noise, &TerrainConfig::default()).mesh;
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_1).is_none());
+    }
+
+    #[test]
+    fn test_uv1_matches_across_chunk_edge() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::builder()
+            .generate_uv1(true)
+            .detail_uv_scale(0.25)
+            .build();
+        let subdivisions = 8;
+        let uv1s = |coords| {
+            let mesh = generate_chunk_mesh(coords, 100.0, subdivisions, &noise, &config).mesh;
+            let Some(VertexAttributeValues::Float32x2(uv1s)) = mesh.attribute(Mesh::ATTRIBUTE_UV_1)
+            else {
+                panic!("mesh should have Float32x2 UV1");
+            };
+            uv1s.clone()
+        };
+        let (left, right) = (uv1s(IVec2::new(3, 1)), uv1s(IVec2::new(4, 1)));
+
+        // The left chunk's last column is the right chunk's first
+        let per_side = subdivisions as usize + 1;
+        for z in 0..per_side {
+            let shared = left[z * per_side + per_side - 1];
+            assert_eq!(shared, right[z * per_side]);
+        }
+        // Scaled from world units: the shared edge lies at x = 350
+        assert_eq!(left[per_side - 1][0], 350.0 * 0.25);
+    }
+
+    #[test]
+    fn test_morph_disabled_skips_attribute() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::builder().generate_morph(false).build();
+
+        let without = generate_chunk_mesh(IVec2::ZERO, 100.0, 8, &noise, &config).mesh;
+        let with =
+            generate_chunk_mesh(IVec2::ZERO, 100.0, 8, &noise, &TerrainConfig::default()).mesh;
+
+        assert!(without.attribute(ATTRIBUTE_MORPH_HEIGHT).is_none());
+        let Some(VertexAttributeValues::Float32(morph)) = with.attribute(ATTRIBUTE_MORPH_HEIGHT)
+        else {
+            panic!("morph heights missing");
+        };
+        assert_eq!(morph.len(), with.count_vertices());
+
+        // Only the morph attribute differs; the surface itself is unchanged
+        assert_eq!(
+            without.attribute(Mesh::ATTRIBUTE_POSITION),
+            with.attribute(Mesh::ATTRIBUTE_POSITION)
+        );
+        assert_eq!(without.indices(), with.indices());
+    }
+
+    #[test]
+    fn test_skirt_color_override() {
+        let noise = TerrainNoise::default();
+        let skirt_color = [0.1, 0.1, 0.1, 1.0];
+        let config = TerrainConfig::builder().skirt_color(skirt_color).build();
+
+        let subdivisions = 8;
+        let mesh = generate_chunk_mesh(IVec2::ZERO, 100.0, subdivisions, &noise, &config).mesh;
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) =
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("mesh should have Float32x4 colors");
+        };
+
+        let surface_vertices = ((subdivisions + 1) * (subdivisions + 1)) as usize;
+        assert!(colors.len() > surface_vertices);
+        for color in &colors[surface_vertices..] {
+            assert_eq!(*color, skirt_color);
+        }
+    }
+
+    #[test]
+    fn test_skirt_normals_match_edge() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+        let subdivisions = 8;
+        let mesh = generate_chunk_mesh(IVec2::ZERO, 100.0, subdivisions, &noise, &config).mesh;
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("mesh should have Float32x3 normals");
+        };
+
+        // The first skirt vertices hang below the top edge (z = 0)
+        let surface_vertices = ((subdivisions + 1) * (subdivisions + 1)) as usize;
+        for x in 0..=subdivisions as usize {
+            assert_eq!(normals[surface_vertices + x], normals[x]);
+        }
+    }
+
+    #[test]
+    fn test_linear_blend_brightens_shoreline() {
+        let blue = [0.0, 0.0, 1.0, 1.0];
+        let green = [0.0, 1.0, 0.0, 1.0];
+        let palette = crate::config::BiomePalette {
+            deep_water: blue,
+            shallow_water: blue,
+            sand: green,
+            grass_dry: green,
+            grass_lush: green,
+            forest_tropical: green,
+            tundra: green,
+            forest_boreal: green,
+            rock_dark: green,
+            rock_grey: green,
+            snow: green,
+            detail_variation: 0.0,
+            ..Default::default()
+        };
+        assert!(palette.linear_blend, "linear blending is the default");
+        let linear = TerrainConfig::builder()
+            .biome_palette(palette.clone())
+            .build();
+        let srgb = TerrainConfig::builder()
+            .biome_palette(crate::config::BiomePalette {
+                linear_blend: false,
+                ..palette
+            })
+            .build();
+
+        // Halfway through the shore blend
+        let shore = &linear.biome_palette.shore;
+        let normalized = (shore.start + shore.end) / 2.0;
+        let height = normalized * (linear.max_height + linear.water_level) - linear.water_level;
+        let color =
+            |config: &TerrainConfig| terrain_to_color(height, 0.5, Vec3::Y, 0.0, 0.0, config, 0.0);
+
+        let mixed = color(&srgb);
+        assert!((mixed[1] - 0.5).abs() < 1e-4 && (mixed[2] - 0.5).abs() < 1e-4);
+        let expected = Srgba::gamma_function_inverse(0.5);
+        let mixed = color(&linear);
+        assert!((mixed[1] - expected).abs() < 1e-4, "{mixed:?}");
+        assert!((mixed[2] - expected).abs() < 1e-4, "{mixed:?}");
+        assert!(expected > 0.7);
+    }
+
+    #[test]
+    fn test_biome_palette_drives_vertex_colors() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let palette = crate::config::BiomePalette {
+            deep_water: red,
+            shallow_water: red,
+            sand: red,
+            grass_dry: red,
+            grass_lush: red,
+            forest_tropical: red,
+            tundra: red,
+            forest_boreal: red,
+            rock_dark: red,
+            rock_grey: red,
+            snow: red,
+            detail_variation: 0.0,
+            ..Default::default()
+        };
+        let config = TerrainConfig::builder().biome_palette(palette).build();
+
+        let mesh =
+            generate_chunk_mesh(IVec2::ZERO, 100.0, 8, &TerrainNoise::default(), &config).mesh;
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) =
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("mesh should have Float32x4 colors");
+        };
+
+        let surface_vertices = 9 * 9;
+        for color in &colors[..surface_vertices] {
+            assert!((color[0] - 1.0).abs() < 1e-5, "{color:?}");
+            assert!(color[1].abs() < 1e-5 && color[2].abs() < 1e-5, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn test_vertex_color_callback_replaces_biome_colors() {
+        let noise = TerrainNoise::default();
+        let options = MeshGenOptions::default().with_vertex_color(|_, _, x, _| {
+            if x < 0.0 {
+                [1.0, 0.0, 0.0, 1.0]
+            } else {
+                [0.0, 0.0, 1.0, 1.0]
+            }
+        });
+
+        let (GeneratedChunk { mesh, .. }, samples) = generate_chunk_mesh_with_samples(
+            IVec2::ZERO,
+            100.0,
+            8,
+            &noise,
+            &TerrainConfig::default(),
+            &options,
+        );
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) =
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("mesh should have Float32x4 colors");
+        };
+
+        for z in 0..samples.vertices_per_side {
+            for x in 0..samples.vertices_per_side {
+                let color = colors[(z * samples.vertices_per_side + x) as usize];
+                let expected = if samples.position(x, z).x < 0.0 {
+                    [1.0, 0.0, 0.0, 1.0]
+                } else {
+                    [0.0, 0.0, 1.0, 1.0]
+                };
+                assert_eq!(color, expected);
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_height_grid_matches_serial() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+        let sample = |x: f32, z: f32| sample_terrain_height(x, z, &noise, &config);
+
+        for coords in [
+            IVec2::ZERO,
+            IVec2::new(3, -2),
+            IVec2::new(-17, 41),
+            IVec2::new(250, 250),
+        ] {
+            let serial = sample_height_grid(coords, 100.0, 64, &sample);
+            let parallel = sample_height_grid_parallel(coords, 100.0, 64, &sample);
+
+            let serial_bits: Vec<u32> = serial.iter().flatten().map(|h| h.to_bits()).collect();
+            let parallel_bits: Vec<u32> = parallel.iter().flatten().map(|h| h.to_bits()).collect();
+            assert_eq!(serial_bits, parallel_bits, "grids differ at {coords}");
+        }
+    }
+
+    #[test]
+    fn test_generated_bounds_match_vertex_extents() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+
+        for coords in [IVec2::ZERO, IVec2::new(4, -7), IVec2::new(-12, 9)] {
+            let chunk = generate_chunk_mesh(coords, 100.0, 16, &noise, &config);
+            let Some(VertexAttributeValues::Float32x3(positions)) =
+                chunk.mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("mesh should have Float32x3 positions");
+            };
+
+            let min_y = positions.iter().map(|p| p[1]).fold(f32::INFINITY, f32::min);
+            let max_y = positions
+                .iter()
+                .map(|p| p[1])
+                .fold(f32::NEG_INFINITY, f32::max);
+            assert_eq!(chunk.min_y, min_y);
+            assert_eq!(chunk.max_y, max_y);
+            assert!(chunk.min_y < chunk.max_y);
+        }
+    }
+
+    #[test]
+    fn test_tangents_are_orthonormal() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::default();
+
+        let mesh = generate_chunk_mesh(IVec2::ZERO, 100.0, 8, &noise, &config).mesh;
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_TANGENT).is_none());
+
+        let (GeneratedChunk { mesh, .. }, _) = generate_chunk_mesh_with_samples(
+            IVec2::new(2, 5),
+            100.0,
+            8,
+            &noise,
+            &config,
+            &MeshGenOptions::default().with_tangents(),
+        );
+        let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        else {
+            panic!("mesh should have Float32x4 tangents");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("mesh should have Float32x3 normals");
+        };
+
+        assert_eq!(tangents.len(), normals.len());
+        for (tangent, normal) in tangents.iter().zip(normals) {
+            let t = Vec3::new(tangent[0], tangent[1], tangent[2]);
+            let n = Vec3::from_array(*normal);
+            assert!(
+                (t.length() - 1.0).abs() < 1e-3,
+                "tangent {t} not unit length"
+            );
+            assert!(t.dot(n).abs() < 1e-3, "tangent {t} not orthogonal to {n}");
+            assert!(tangent[3].abs() == 1.0);
+            // UV u runs along +X across the chunk
+            assert!(t.x > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_stitch_snaps_edge_to_coarser_neighbor() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::builder()
+            .seam_mode(crate::config::SeamMode::Stitch)
+            .build();
+        let subdivisions = 16;
+
+        let (GeneratedChunk { mesh, .. }, samples) = generate_chunk_mesh_with_samples(
+            IVec2::new(1, 2),
+            100.0,
+            subdivisions,
+            &noise,
+            &config,
+            &MeshGenOptions::default().with_neighbor_lods([1, 0, 2, 0]),
+        );
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh should have Float32x3 positions");
+        };
+
+        let per_side = subdivisions + 1;
+        assert_eq!(positions.len(), (per_side * per_side) as usize, "no skirts");
+        let y = |x: u32, z: u32| positions[(z * per_side + x) as usize][1];
+
+        // -Z edge follows a neighbor with twice the spacing
+        for x in (1..subdivisions).step_by(2) {
+            let expected = (y(x - 1, 0) + y(x + 1, 0)) * 0.5;
+            assert!((y(x, 0) - expected).abs() < 1e-4);
+        }
+        // +Z edge follows a neighbor with four times the spacing
+        for x in 1..subdivisions {
+            let start = x - x % 4;
+            let t = (x % 4) as f32 / 4.0;
+            let expected = y(start, subdivisions) * (1.0 - t) + y(start + 4, subdivisions) * t;
+            assert!((y(x, subdivisions) - expected).abs() < 1e-4);
+        }
+        // Shared vertices and untouched edges keep their sampled heights
+        for z in 0..per_side {
+            assert_eq!(y(subdivisions, z), samples.height(subdivisions, z));
+        }
+        for x in (0..per_side).step_by(4) {
+            assert_eq!(y(x, subdivisions), samples.height(x, subdivisions));
+        }
+    }
+
+    fn surface_normals(mesh: &Mesh) -> Vec<[f32; 3]> {
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("mesh should have Float32x3 normals");
+        };
+        normals.clone()
+    }
+
+    #[test]
+    fn test_same_lod_neighbors_share_edge_normals() {
+        let source = crate::heightmap::ProceduralHeightmap::new(|x, z| {
+            (x * 0.37).sin() * 6.0 + (z * 0.21).cos() * 4.0 + x * z * 0.001
+        });
+        let config = TerrainConfig::default();
+        // A size that isn't exactly representable makes origin-based positions drift
+        let (size, subdivisions) = (37.3, 16);
+        let per_side = subdivisions + 1;
+
+        let mesh = |coords| {
+            generate_chunk_mesh_from_source(coords, size, subdivisions, &source, &config).mesh
+        };
+        let left = surface_normals(&mesh(IVec2::new(5, -3)));
+        let right = surface_normals(&mesh(IVec2::new(6, -3)));
+        let below = surface_normals(&mesh(IVec2::new(5, -2)));
+
+        for i in 0..per_side {
+            // +X edge of the left chunk is the -X edge of the right one
+            let (a, b) = (i * per_side + subdivisions, i * per_side);
+            assert_eq!(left[a as usize], right[b as usize]);
+            // +Z edge of the left chunk is the -Z edge of the one below
+            let (a, b) = (subdivisions * per_side + i, i);
+            assert_eq!(left[a as usize], below[b as usize]);
+        }
+    }
+
+    #[test]
+    fn test_stitched_edge_normals_match_coarser_neighbor() {
+        let source = crate::heightmap::ProceduralHeightmap::new(|x, z| {
+            (x * 0.3).sin() * 5.0 + (z * 0.17).sin() * 3.0
+        });
+        let config = TerrainConfig::builder()
+            .seam_mode(crate::config::SeamMode::Stitch)
+            .build();
+        let size = 64.0;
+
+        // Fine chunk whose +X neighbor has half the subdivisions
+        let (fine, _) = generate_chunk_mesh_from_source_with_samples(
+            IVec2::ZERO,
+            size,
+            16,
+            &source,
+            &config,
+            &MeshGenOptions::default().with_neighbor_lods([0, 1, 0, 0]),
+        );
+        let (coarse, _) = generate_chunk_mesh_from_source_with_samples(
+            IVec2::X,
+            size,
+            8,
+            &source,
+            &config,
+            &MeshGenOptions::default(),
+        );
+        let (fine, coarse) = (surface_normals(&fine.mesh), surface_normals(&coarse.mesh));
+
+        // Shared vertices: fine (16, 2k) and coarse (0, k)
+        for k in 0..=8 {
+            let a = Vec3::from_array(fine[(2 * k * 17 + 16) as usize]);
+            let b = Vec3::from_array(coarse[(k * 9) as usize]);
+            assert!(a.distance(b) < 1e-4, "edge normal {a} differs from {b}");
+        }
+    }
+
+    #[test]
+    fn test_skirted_edge_normals_match_coarser_neighbor() {
+        let source = crate::heightmap::ProceduralHeightmap::new(|x, z| {
+            (x * 0.3).sin() * 5.0 + (z * 0.17).sin() * 3.0
+        });
+        let config = TerrainConfig::default();
+        assert_eq!(config.seam_mode, crate::config::SeamMode::Skirts);
+        let size = 64.0;
+
+        let (fine, _) = generate_chunk_mesh_from_source_with_samples(
+            IVec2::ZERO,
+            size,
+            16,
+            &source,
+            &config,
+            &MeshGenOptions::default().with_neighbor_lods([0, 1, 0, 0]),
+        );
+        let (coarse, _) = generate_chunk_mesh_from_source_with_samples(
+            IVec2::X,
+            size,
+            8,
+            &source,
+            &config,
+            &MeshGenOptions::default(),
+        );
+        let (fine, coarse) = (surface_normals(&fine.mesh), surface_normals(&coarse.mesh));
+
+        for k in 0..=8 {
+            let a = Vec3::from_array(fine[(2 * k * 17 + 16) as usize]);
+            let b = Vec3::from_array(coarse[(k * 9) as usize]);
+            assert!(a.distance(b) < 1e-4, "edge normal {a} differs from {b}");
+        }
+    }
+
+    #[test]
+    fn test_zero_skirt_depth_skips_skirts() {
+        let noise = TerrainNoise::default();
+        let config = TerrainConfig::builder().skirt_depth(0.0).build();
+
+        for subdivisions in [8, 16, 64] {
+            let mesh = generate_chunk_mesh(IVec2::ZERO, 100.0, subdivisions, &noise, &config).mesh;
+            let per_side = (subdivisions + 1) as usize;
+            assert_eq!(mesh.count_vertices(), per_side * per_side);
+            assert_eq!(
+                mesh.indices().map(Indices::len),
+                Some((subdivisions * subdivisions * 6) as usize)
+            );
+        }
+    }
+
+    #[test]
+    fn test_hole_mask_removes_triangles() {
+        let source = crate::heightmap::ProceduralHeightmap::new(|_, _| 0.0);
+        let config = TerrainConfig::default();
+        let subdivisions = 8;
+        let index_count = |holes: HoleMask| {
+            let options = MeshGenOptions::default().with_holes(holes);
+            generate_chunk_mesh_from_source_with_samples(
+                IVec2::ZERO,
+                16.0,
+                subdivisions,
+                &source,
+                &config,
+                &options,
+            )
+            .0
+            .mesh
+            .indices()
+            .map_or(0, Indices::len)
+        };
+
+        let full = index_count(HoleMask::default());
+        assert!(full > (subdivisions * subdivisions * 6) as usize);
+
+        // A centered 4x4 hole covers 2x2 cells of the 2-unit grid, 8 triangles
+        let centered =
+            index_count(HoleMask::default().with_rect(Aabb2d::new(Vec2::ZERO, Vec2::splat(2.0))));
+        assert_eq!(centered, full - 8 * 3);
+
+        // Covering the whole chunk also drops every skirt
+        let everything = index_count(HoleMask::default().with_predicate(|_, _| true));
+        assert_eq!(everything, 0);
+    }
 }
@@ -1,105 +1,590 @@
-//! Physics integration for terrain using Rapier
+//! Physics integration for terrain
 //!
-//! This module is only available when the `rapier` feature is enabled.
-//! It provides automatic heightfield collider generation for terrain chunks.
+//! Heightfield sampling and collider metadata shared by the physics backends:
+//! Rapier behind the `rapier` feature and Avian behind the `avian` feature.
+//! Enable at most one of them; the collider systems here are shared and each
+//! backend only supplies `build_chunk_collider`.
+//! Heightfields are sampled on the `AsyncComputeTaskPool`, so a collider
+//! appears a few frames after its chunk, unless the terrain uses
+//! [`GenerationMode::Synchronous`].
 
-use crate::config::TerrainConfig;
+#[cfg(all(feature = "rapier", feature = "avian"))]
+compile_error!("the `rapier` and `avian` features are mutually exclusive");
+
+#[cfg(feature = "avian")]
+mod avian;
+#[cfg(feature = "rapier")]
+mod rapier;
+
+#[cfg(feature = "avian")]
+use avian as backend;
+#[cfg(all(feature = "rapier", not(feature = "avian")))]
+use rapier as backend;
+
+use crate::config::{GenerationMode, TerrainConfig};
 use crate::heightmap::{HeightmapHandle, TerrainNoise, sample_terrain_height};
+use crate::mesh::grid_world_position;
 use crate::{Chunk, Terrain};
+use bevy::math::bounding::Aabb3d;
 use bevy::prelude::*;
-use bevy_rapier3d::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use std::collections::{HashMap, VecDeque};
+
+/// Component indicating a chunk has a physics collider
+///
+/// Records the world-space extents and resolution the heightfield was built
+/// from, which helps diagnose mismatches between the visual mesh and the
+/// collision surface.
+#[derive(Component, Clone, Debug)]
+pub struct TerrainCollider {
+    /// World-space bounds covered by the heightfield
+    pub bounds: Aabb3d,
+    /// Number of heightfield rows (along Z)
+    pub num_rows: usize,
+    /// Number of heightfield columns (along X)
+    pub num_cols: usize,
+    /// Chunk LOD level the heightfield was sampled at
+    pub lod: u32,
+}
 
-/// Marker component indicating a chunk has a physics collider
-#[derive(Component)]
-pub struct TerrainCollider;
+impl TerrainCollider {
+    /// Build collider metadata from a sampled heightfield centered on `center` (XZ)
+    pub fn from_heights(
+        center: Vec2,
+        chunk_size: f32,
+        heights: &[f32],
+        num_rows: usize,
+        num_cols: usize,
+    ) -> Self {
+        let (min_y, max_y) = heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &h| {
+                (min.min(h), max.max(h))
+            });
+        let half = chunk_size / 2.0;
 
-/// System to spawn heightfield colliders for terrain chunks
-pub fn spawn_terrain_colliders(
-    mut commands: Commands,
+        Self {
+            bounds: Aabb3d {
+                min: Vec3A::new(center.x - half, min_y, center.y - half),
+                max: Vec3A::new(center.x + half, max_y, center.y + half),
+            },
+            num_rows,
+            num_cols,
+            lod: 0,
+        }
+    }
+
+    /// Record the chunk LOD level the heightfield was sampled at
+    pub fn with_lod(mut self, lod: u32) -> Self {
+        self.lod = lod;
+        self
+    }
+
+    /// Shift the bounds up by the chunk's Y translation (e.g. the terrain's base elevation)
+    pub fn with_elevation(mut self, elevation: f32) -> Self {
+        self.bounds.min.y += elevation;
+        self.bounds.max.y += elevation;
+        self
+    }
+}
+
+/// Sample a chunk's collider heights on the same grid its mesh was built from
+///
+/// Returns the heights in row-major order (z outer, x inner) and the number
+/// of samples along each side.
+pub fn sample_collider_heights(
+    chunk: &Chunk,
+    config: &TerrainConfig,
+    heightmap: Option<&HeightmapHandle>,
+) -> (Vec<f32>, usize) {
+    let default_noise = TerrainNoise::default();
+
+    let lod = (chunk.current_lod as usize).min(config.lod_subdivisions.len() - 1);
+    let subdivisions = config.lod_subdivisions[lod];
+    let samples_per_side = subdivisions as usize + 1;
+
+    let mut heights = Vec::with_capacity(samples_per_side * samples_per_side);
+    let source = heightmap.and_then(|heightmap| heightmap.exaggerated_source(config));
+
+    for z in 0..samples_per_side {
+        for x in 0..samples_per_side {
+            let Vec2 {
+                x: world_x,
+                y: world_z,
+            } = grid_world_position(chunk.coords, chunk.size, subdivisions, x as i32, z as i32);
+
+            let height = match (&source, heightmap) {
+                (Some(source), _) => source.sample(world_x, world_z),
+                (None, Some(heightmap)) => heightmap.sample(world_x, world_z),
+                (None, None) => sample_terrain_height(world_x, world_z, &default_noise, config),
+            };
+
+            heights.push(height);
+        }
+    }
+
+    (heights, samples_per_side)
+}
+
+/// Regroup row-major heights into X columns that each run along Z
+///
+/// This is the layout both physics backends expect: Avian takes the columns
+/// as they are and Rapier takes them flattened into a column-major matrix.
+pub fn heights_by_column(heights: &[f32], samples_per_side: usize) -> Vec<Vec<f32>> {
+    (0..samples_per_side)
+        .map(|x| {
+            (0..samples_per_side)
+                .map(|z| heights[z * samples_per_side + x])
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether a chunk is close enough (see `collider_max_lod`) to get a collider
+pub fn within_collider_lod(chunk: &Chunk, config: &TerrainConfig) -> bool {
+    chunk.current_lod <= config.collider_max_lod as u32
+}
+
+/// Collider heights sampled off the main thread
+pub struct ColliderHeights {
+    /// Chunk entity the heights belong to
+    pub entity: Entity,
+    /// Chunk LOD level the heights were sampled at
+    pub lod: u32,
+    /// Heights in row-major order (z outer, x inner)
+    pub heights: Vec<f32>,
+    /// Number of samples along each side
+    pub samples_per_side: usize,
+}
+
+/// Resource managing async collider height sampling
+///
+/// Mirrors the mesh streaming queues: chunks wait in `pending`, are sampled
+/// on the `AsyncComputeTaskPool` while in flight, and sit in `completed`
+/// until the physics backend inserts their colliders.
+#[derive(Resource, Default)]
+pub struct TerrainColliderTasks {
+    /// Chunk entities waiting for a sampling task, with the LOD to sample at
+    pending: VecDeque<(Entity, u32)>,
+    /// Running sampling tasks and the LOD they sample at
+    in_flight: HashMap<Entity, (u32, Task<ColliderHeights>)>,
+    /// Sampled heights ready to become colliders
+    pub completed: Vec<ColliderHeights>,
+}
+
+impl TerrainColliderTasks {
+    /// Queue a chunk for sampling at `lod`, replacing any queued request for another LOD
+    pub fn queue(&mut self, entity: Entity, lod: u32) {
+        if self.in_flight.get(&entity).is_some_and(|(l, _)| *l == lod)
+            || self.pending.contains(&(entity, lod))
+        {
+            return;
+        }
+        self.pending.retain(|(e, _)| *e != entity);
+        self.pending.push_back((entity, lod));
+    }
+
+    /// Number of chunks waiting for a sampling task
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of sampling tasks currently running
+    pub fn in_flight_len(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+/// System: Start collider sampling tasks, up to `max_concurrent_tasks` at once
+///
+/// With [`GenerationMode::Synchronous`] the heights are sampled right here
+/// instead, up to `max_concurrent_tasks` per frame, and the task pool is
+/// never touched.
+pub fn spawn_collider_tasks(
     config: Res<TerrainConfig>,
     terrain_query: Query<&HeightmapHandle, With<Terrain>>,
-    chunks_without_colliders: Query<(Entity, &Chunk, &Transform), Without<TerrainCollider>>,
+    chunks: Query<&Chunk>,
+    mut tasks: ResMut<TerrainColliderTasks>,
 ) {
-    // Get the heightmap source
-    let default_noise = TerrainNoise::default();
+    let heightmap = terrain_query.single().ok().cloned();
+    let mut sampled_inline = 0;
 
-    for (entity, chunk, transform) in chunks_without_colliders.iter() {
-        // Calculate chunk bounds
-        let chunk_size = config.chunk_size;
-        let subdivisions = chunk.current_lod;
+    while tasks.in_flight.len() + sampled_inline < config.max_concurrent_tasks {
+        let Some((entity, lod)) = tasks.pending.pop_front() else {
+            break;
+        };
+        // The chunk may have been despawned while queued
+        let Ok(chunk) = chunks.get(entity) else {
+            continue;
+        };
 
-        // Sample heights for the heightfield collider
-        let num_rows = subdivisions as usize + 1;
-        let num_cols = subdivisions as usize + 1;
-        let step = chunk_size / subdivisions as f32;
+        let chunk = Chunk {
+            coords: chunk.coords,
+            current_lod: lod,
+            node_id: chunk.node_id,
+            size: chunk.size,
+        };
 
-        let start_x = transform.translation.x - chunk_size / 2.0;
-        let start_z = transform.translation.z - chunk_size / 2.0;
+        if config.generation_mode == GenerationMode::Synchronous {
+            let (heights, samples_per_side) =
+                sample_collider_heights(&chunk, &config, heightmap.as_ref());
+            tasks.completed.push(ColliderHeights {
+                entity,
+                lod,
+                heights,
+                samples_per_side,
+            });
+            sampled_inline += 1;
+            continue;
+        }
 
-        let mut heights = Vec::with_capacity(num_rows * num_cols);
+        let config = config.clone();
+        let heightmap = heightmap.clone();
 
-        for z in 0..num_rows {
-            for x in 0..num_cols {
-                let world_x = start_x + x as f32 * step;
-                let world_z = start_z + z as f32 * step;
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let (heights, samples_per_side) =
+                sample_collider_heights(&chunk, &config, heightmap.as_ref());
+            ColliderHeights {
+                entity,
+                lod,
+                heights,
+                samples_per_side,
+            }
+        });
+        tasks.in_flight.insert(entity, (lod, task));
+    }
+}
 
-                let height = if let Ok(heightmap) = terrain_query.single() {
-                    heightmap.sample(world_x, world_z)
-                } else {
-                    sample_terrain_height(world_x, world_z, &default_noise, &config)
-                };
+/// System: Move finished sampling tasks to `completed`
+pub fn poll_collider_tasks(mut tasks: ResMut<TerrainColliderTasks>) {
+    let TerrainColliderTasks {
+        in_flight,
+        completed,
+        ..
+    } = &mut *tasks;
 
-                heights.push(height);
-            }
+    in_flight.retain(|_, (_, task)| {
+        if !task.is_finished() {
+            return true;
         }
+        completed.extend(block_on(futures_lite::future::poll_once(task)));
+        false
+    });
+}
 
-        // Create the heightfield collider
-        let collider = Collider::heightfield(
-            heights,
-            num_rows,
-            num_cols,
-            Vec3::new(chunk_size, 1.0, chunk_size),
-        );
+/// System to queue collider sampling for chunks without a collider
+#[cfg(any(feature = "rapier", feature = "avian"))]
+pub fn spawn_terrain_colliders(
+    config: Res<TerrainConfig>,
+    chunks_without_colliders: Query<(Entity, &Chunk), Without<TerrainCollider>>,
+    mut tasks: ResMut<TerrainColliderTasks>,
+) {
+    for (entity, chunk) in chunks_without_colliders.iter() {
+        // Distant chunks are never touched, so skip their heightfields
+        if within_collider_lod(chunk, &config) {
+            tasks.queue(entity, chunk.current_lod);
+        }
+    }
+}
 
-        commands.entity(entity).insert((
-            collider,
-            TerrainCollider,
-            // Terrain is static
-            RigidBody::Fixed,
-            // Adjust collider position to match mesh
-            ColliderMassProperties::Mass(0.0),
-        ));
+/// System to insert colliders for finished sampling tasks
+///
+/// Results for chunks that were despawned, changed LOD, or moved out of
+/// collider range in the meantime are dropped.
+#[cfg(any(feature = "rapier", feature = "avian"))]
+pub fn insert_terrain_colliders(
+    mut commands: Commands,
+    config: Res<TerrainConfig>,
+    chunks: Query<(&Chunk, &Transform)>,
+    mut tasks: ResMut<TerrainColliderTasks>,
+) {
+    for sampled in tasks.completed.drain(..) {
+        let Ok((chunk, transform)) = chunks.get(sampled.entity) else {
+            continue;
+        };
+        if chunk.current_lod != sampled.lod || !within_collider_lod(chunk, &config) {
+            continue;
+        }
+
+        let terrain_collider = TerrainCollider::from_heights(
+            transform.translation.xz(),
+            chunk.size,
+            &sampled.heights,
+            sampled.samples_per_side,
+            sampled.samples_per_side,
+        )
+        .with_lod(sampled.lod)
+        .with_elevation(transform.translation.y);
+        let collider =
+            backend::build_chunk_collider(&sampled.heights, sampled.samples_per_side, chunk.size);
+
+        commands
+            .entity(sampled.entity)
+            .insert((collider, terrain_collider));
     }
 }
 
-/// System to update colliders when chunk LOD changes
-pub fn update_terrain_colliders(
+/// System to remove colliders from chunks that dropped past `collider_max_lod`
+#[cfg(any(feature = "rapier", feature = "avian"))]
+pub fn remove_distant_colliders(
     mut commands: Commands,
     config: Res<TerrainConfig>,
-    terrain_query: Query<&HeightmapHandle, With<Terrain>>,
-    chunks_with_colliders: Query<(Entity, &Chunk, &Transform, &Collider), With<TerrainCollider>>,
+    chunks_with_colliders: Query<(Entity, &Chunk), With<TerrainCollider>>,
 ) {
-    let default_noise = TerrainNoise::default();
+    for (entity, chunk) in chunks_with_colliders.iter() {
+        if !within_collider_lod(chunk, &config) {
+            commands
+                .entity(entity)
+                .remove::<(backend::ChunkCollider, TerrainCollider)>();
+        }
+    }
+}
 
-    for (entity, chunk, transform, _collider) in chunks_with_colliders.iter() {
-        // Check if LOD changed (would need to track previous LOD)
-        // For now, this is a placeholder for future LOD-aware collider updates
-        let _ = (
-            entity,
-            chunk,
-            transform,
-            &default_noise,
-            &config,
-            &terrain_query,
-        );
+/// System to queue a rebuild for colliders whose chunk changed LOD since they were built
+#[cfg(any(feature = "rapier", feature = "avian"))]
+pub fn update_terrain_colliders(
+    config: Res<TerrainConfig>,
+    chunks_with_colliders: Query<(Entity, &Chunk, &TerrainCollider)>,
+    mut tasks: ResMut<TerrainColliderTasks>,
+) {
+    for (entity, chunk, terrain_collider) in chunks_with_colliders.iter() {
+        if terrain_collider.lod != chunk.current_lod && within_collider_lod(chunk, &config) {
+            tasks.queue(entity, chunk.current_lod);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collider_bounds_match_chunk() {
+        let heights = vec![-5.0, 0.0, 3.0, 12.5];
+        let collider =
+            TerrainCollider::from_heights(Vec2::new(250.0, -50.0), 100.0, &heights, 2, 2);
+
+        assert_eq!(collider.bounds.min, Vec3A::new(200.0, -5.0, -100.0));
+        assert_eq!(collider.bounds.max, Vec3A::new(300.0, 12.5, 0.0));
+        assert_eq!((collider.num_rows, collider.num_cols), (2, 2));
+    }
+
     #[test]
-    fn test_physics_module_exists() {
-        // Just verify the module compiles
-        assert!(true);
+    fn test_collider_samples_match_mesh_corners() {
+        use crate::heightmap::ProceduralHeightmap;
+        use crate::mesh::{MeshGenOptions, generate_chunk_mesh_from_source_with_samples};
+        use std::sync::Arc;
+
+        let config = TerrainConfig::default();
+        let source = Arc::new(ProceduralHeightmap::new(|x, z| x * 0.3 - z * 0.7));
+        let heightmap = HeightmapHandle::Procedural(source.clone());
+        let chunk = Chunk {
+            coords: IVec2::new(3, -2),
+            current_lod: 1,
+            node_id: 1,
+            size: 200.0,
+        };
+        let subdivisions = config.lod_subdivisions[1];
+
+        let (heights, samples_per_side) =
+            sample_collider_heights(&chunk, &config, Some(&heightmap));
+        let (_, samples) = generate_chunk_mesh_from_source_with_samples(
+            chunk.coords,
+            chunk.size,
+            subdivisions,
+            source.as_ref(),
+            &config,
+            &MeshGenOptions::default(),
+        );
+
+        let last = samples_per_side - 1;
+        assert_eq!(samples_per_side as u32, samples.vertices_per_side);
+        assert_eq!(heights[0], samples.height(0, 0));
+        assert_eq!(
+            heights[last * samples_per_side + last],
+            samples.height(last as u32, last as u32)
+        );
+
+        // An asymmetric interior sample catches swapped X and Z
+        let (x, z) = (1, last - 1);
+        let mesh_height = samples.height(x as u32, z as u32);
+        assert_ne!(mesh_height, samples.height(z as u32, x as u32));
+        assert_eq!(heights[z * samples_per_side + x], mesh_height);
+        let columns = heights_by_column(&heights, samples_per_side);
+        assert_eq!(columns[x][z], mesh_height);
+        assert_eq!(columns.concat()[x * samples_per_side + z], mesh_height);
+    }
+
+    #[test]
+    fn test_vertical_exaggeration_doubles_relief() {
+        use crate::heightmap::ImageHeightmap;
+        use crate::streaming::TerrainHeightQuery;
+        use std::sync::Arc;
+
+        let flat = TerrainConfig::default();
+        let tall = TerrainConfig::builder().vertical_exaggeration(2.0).build();
+        let chunk = Chunk {
+            coords: IVec2::new(1, -1),
+            current_lod: 2,
+            node_id: 1,
+            size: 200.0,
+        };
+        let subdivisions = tall.lod_subdivisions[2];
+        let p = grid_world_position(chunk.coords, chunk.size, subdivisions, 3, 5);
+
+        // Noise terrain: relief doubles about the water line at y = 0
+        let (flat_heights, samples_per_side) = sample_collider_heights(&chunk, &flat, None);
+        let (tall_heights, _) = sample_collider_heights(&chunk, &tall, None);
+        assert!(flat_heights.iter().any(|&h| h > 0.0));
+        for (flat_height, tall_height) in flat_heights.iter().zip(&tall_heights) {
+            assert!((tall_height - flat_height * 2.0).abs() < 1e-3);
+        }
+        let noise = TerrainHeightQuery::new(TerrainNoise::default(), tall.clone());
+        let index = 5 * samples_per_side + 3;
+        assert!((noise.get_height(p.x, p.y) - tall_heights[index]).abs() < 1e-4);
+
+        // Normalized image data keeps its 0-1 range; only the sampled heights scale
+        let image =
+            || ImageHeightmap::new(vec![0.0, 0.5, 1.0, 0.25], 2, 2, Vec2::splat(400.0), 40.0);
+        let handle = HeightmapHandle::Image(Arc::new(image()));
+        let query = TerrainHeightQuery::from_source(image(), tall.clone());
+        let (heights, _) = sample_collider_heights(&chunk, &tall, Some(&handle));
+        assert!((heights[index] - handle.sample(p.x, p.y) * 2.0).abs() < 1e-4);
+        assert!((query.get_height(p.x, p.y) - heights[index]).abs() < 1e-4);
+        let mesh_source = handle.exaggerated_source(&tall).unwrap();
+        assert_eq!(mesh_source.sample(p.x, p.y), heights[index]);
+        assert_eq!(
+            query.get_height(0.0, 0.0),
+            0.0,
+            "sea-level pixels stay at the water line"
+        );
+    }
+
+    /// Collider systems tests, run against whichever backend is enabled
+    #[cfg(any(feature = "rapier", feature = "avian"))]
+    mod systems {
+        use super::*;
+        #[cfg(feature = "avian")]
+        use avian3d::prelude::Collider;
+        use bevy::ecs::system::RunSystemOnce;
+        use bevy::tasks::TaskPool;
+        #[cfg(all(feature = "rapier", not(feature = "avian")))]
+        use bevy_rapier3d::prelude::Collider;
+
+        /// World with the resources the collider systems need
+        fn test_world() -> World {
+            AsyncComputeTaskPool::get_or_init(TaskPool::default);
+            let mut world = World::new();
+            world.insert_resource(TerrainConfig::default());
+            world.init_resource::<TerrainColliderTasks>();
+            world
+        }
+
+        /// Run the collider systems until every queued chunk was sampled and inserted
+        fn run_collider_systems(world: &mut World) {
+            for _ in 0..1000 {
+                world.run_system_once(spawn_terrain_colliders).unwrap();
+                world.run_system_once(update_terrain_colliders).unwrap();
+                world.run_system_once(spawn_collider_tasks).unwrap();
+                world.run_system_once(poll_collider_tasks).unwrap();
+                world.run_system_once(insert_terrain_colliders).unwrap();
+
+                let tasks = world.resource::<TerrainColliderTasks>();
+                if tasks.pending_len() == 0 && tasks.in_flight_len() == 0 {
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            panic!("collider tasks did not finish");
+        }
+
+        #[test]
+        fn test_lod_change_rebuilds_collider() {
+            let config = TerrainConfig::default();
+            let mut world = test_world();
+            let chunk = world
+                .spawn((
+                    Chunk {
+                        coords: IVec2::ZERO,
+                        current_lod: 0,
+                        node_id: 1,
+                        size: config.chunk_size,
+                    },
+                    Transform::default(),
+                ))
+                .id();
+
+            run_collider_systems(&mut world);
+            let collider = world.get::<TerrainCollider>(chunk).unwrap();
+            let rows = config.lod_subdivisions[0] as usize + 1;
+            assert_eq!(
+                (collider.num_rows, collider.num_cols, collider.lod),
+                (rows, rows, 0)
+            );
+
+            // Unchanged LOD keeps the collider
+            run_collider_systems(&mut world);
+            assert_eq!(world.get::<TerrainCollider>(chunk).unwrap().lod, 0);
+
+            world.get_mut::<Chunk>(chunk).unwrap().current_lod = 1;
+            run_collider_systems(&mut world);
+            let collider = world.get::<TerrainCollider>(chunk).unwrap();
+            let rows = config.lod_subdivisions[1] as usize + 1;
+            assert_eq!(
+                (collider.num_rows, collider.num_cols, collider.lod),
+                (rows, rows, 1)
+            );
+        }
+
+        #[test]
+        fn test_distant_chunks_get_no_collider() {
+            let mut world = test_world();
+            let chunk = |lod| Chunk {
+                coords: IVec2::ZERO,
+                current_lod: lod,
+                node_id: 1,
+                size: 100.0,
+            };
+            let near = world.spawn((chunk(1), Transform::default())).id();
+            let far = world.spawn((chunk(3), Transform::default())).id();
+
+            run_collider_systems(&mut world);
+            assert!(world.get::<TerrainCollider>(near).is_some());
+            assert!(world.get::<Collider>(far).is_none());
+
+            // Dropping past the threshold removes the collider again
+            world.get_mut::<Chunk>(near).unwrap().current_lod = 2;
+            world.run_system_once(remove_distant_colliders).unwrap();
+            assert!(world.get::<TerrainCollider>(near).is_none());
+            assert!(world.get::<Collider>(near).is_none());
+        }
+
+        #[test]
+        fn test_collider_appears_after_polling() {
+            let mut world = test_world();
+            let chunk = world
+                .spawn((
+                    Chunk {
+                        coords: IVec2::new(2, -1),
+                        current_lod: 0,
+                        node_id: 1,
+                        size: 100.0,
+                    },
+                    Transform::from_xyz(200.0, 0.0, -100.0),
+                ))
+                .id();
+
+            // Queuing and starting the task doesn't block on sampling
+            world.run_system_once(spawn_terrain_colliders).unwrap();
+            world.run_system_once(spawn_collider_tasks).unwrap();
+            assert!(world.get::<Collider>(chunk).is_none());
+            assert_eq!(world.resource::<TerrainColliderTasks>().in_flight_len(), 1);
+
+            run_collider_systems(&mut world);
+            assert!(world.get::<Collider>(chunk).is_some());
+            let collider = world.get::<TerrainCollider>(chunk).unwrap();
+            assert_eq!(collider.bounds.min.x, 150.0);
+            assert_eq!(collider.bounds.max.z, -50.0);
+        }
     }
 }
@@ -0,0 +1,30 @@
+//! Avian heightfield colliders for terrain chunks
+//!
+//! Heights are sampled and colliders queued by the shared systems in the
+//! parent module; this backend only turns finished samples into colliders.
+
+use super::heights_by_column;
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+/// Components making up a chunk's collider, besides `TerrainCollider`
+pub(super) type ChunkCollider = (Collider, RigidBody);
+
+/// Build a chunk's collider from its row-major heightfield
+pub(super) fn build_chunk_collider(
+    heights: &[f32],
+    samples_per_side: usize,
+    chunk_size: f32,
+) -> ChunkCollider {
+    // Avian takes the heights as rows along X, columns along Z
+    let collider = Collider::heightfield(
+        heights_by_column(heights, samples_per_side),
+        Vec3::new(chunk_size, 1.0, chunk_size),
+    );
+
+    (
+        collider,
+        // Terrain is static
+        RigidBody::Static,
+    )
+}
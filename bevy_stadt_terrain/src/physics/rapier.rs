@@ -0,0 +1,33 @@
+//! Rapier heightfield colliders for terrain chunks
+//!
+//! Heights are sampled and colliders queued by the shared systems in the
+//! parent module; this backend only turns finished samples into colliders.
+
+use super::heights_by_column;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// Components making up a chunk's collider, besides `TerrainCollider`
+pub(super) type ChunkCollider = (Collider, RigidBody, ColliderMassProperties);
+
+/// Build a chunk's collider from its row-major heightfield
+pub(super) fn build_chunk_collider(
+    heights: &[f32],
+    samples_per_side: usize,
+    chunk_size: f32,
+) -> ChunkCollider {
+    // Rapier takes the heights as a column-major matrix with rows along Z
+    let collider = Collider::heightfield(
+        heights_by_column(heights, samples_per_side).concat(),
+        samples_per_side,
+        samples_per_side,
+        Vec3::new(chunk_size, 1.0, chunk_size),
+    );
+
+    (
+        collider,
+        // Terrain is static
+        RigidBody::Fixed,
+        ColliderMassProperties::Mass(0.0),
+    )
+}
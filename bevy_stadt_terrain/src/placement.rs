@@ -0,0 +1,477 @@
+//! Spawn point selection for procedural content placement
+//!
+//! [`TerrainHeightQuery::find_spawn_points`] picks well-spaced positions in a
+//! region that pass height, slope, water, and moisture filters. Sampling is
+//! seeded, so the same criteria always produce the same points.
+//!
+//! [`TerrainScatterGrid`] keeps spacing across calls: scatter systems
+//! reacting to [`TerrainChunkSpawned`](crate::streaming::TerrainChunkSpawned)
+//! reserve positions in it, so objects near a chunk border don't crowd the
+//! ones already placed by the neighboring chunk.
+//!
+//! [`ChunkFeatureSpawner`]s registered with
+//! [`TerrainStreaming::add_feature_spawner`](crate::streaming::TerrainStreaming::add_feature_spawner)
+//! run right after each chunk spawns, with a [`ChunkRng`] seeded from the
+//! chunk's grid coordinates so decoration comes out the same every session.
+
+use crate::streaming::{TerrainChunkDespawned, TerrainHeightQuery};
+use bevy::math::bounding::Aabb3d;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Filters and spacing for [`TerrainHeightQuery::find_spawn_points`]
+#[derive(Clone, Debug)]
+pub struct SpawnCriteria {
+    /// Seed for the candidate sequence
+    pub seed: u64,
+    /// Minimum horizontal distance between returned points
+    pub min_spacing: f32,
+    /// Lowest allowed terrain height
+    pub min_height: f32,
+    /// Highest allowed terrain height
+    pub max_height: f32,
+    /// Steepest allowed surface, in radians from horizontal
+    pub max_slope: f32,
+    /// Allow points below the water level
+    pub allow_underwater: bool,
+    /// Allowed moisture range (0 = arid, 1 = wet)
+    pub moisture: (f32, f32),
+    /// Candidates tried per requested point before giving up
+    pub attempts_per_point: u32,
+}
+
+impl Default for SpawnCriteria {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            min_spacing: 10.0,
+            min_height: f32::MIN,
+            max_height: f32::MAX,
+            max_slope: 30f32.to_radians(),
+            allow_underwater: false,
+            moisture: (0.0, 1.0),
+            attempts_per_point: 30,
+        }
+    }
+}
+
+impl SpawnCriteria {
+    /// Set the sampling seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the minimum distance between points
+    pub fn with_spacing(mut self, min_spacing: f32) -> Self {
+        self.min_spacing = min_spacing;
+        self
+    }
+
+    /// Restrict points to a height range
+    pub fn with_height_range(mut self, min: f32, max: f32) -> Self {
+        self.min_height = min;
+        self.max_height = max;
+        self
+    }
+
+    /// Set the steepest allowed slope in degrees
+    pub fn with_max_slope_degrees(mut self, degrees: f32) -> Self {
+        self.max_slope = degrees.to_radians();
+        self
+    }
+
+    /// Restrict points to a moisture range
+    pub fn with_moisture_range(mut self, min: f32, max: f32) -> Self {
+        self.moisture = (min, max);
+        self
+    }
+}
+
+/// A position on the terrain surface chosen by [`TerrainHeightQuery::find_spawn_points`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpawnPoint {
+    /// World-space position on the surface
+    pub position: Vec3,
+    /// Surface normal at the position
+    pub normal: Vec3,
+}
+
+impl TerrainHeightQuery {
+    /// Find up to `count` spawn points in the XZ `area` that satisfy `criteria`
+    ///
+    /// Candidates are drawn uniformly from a seeded sequence and accepted when
+    /// they pass the filters and keep `min_spacing` from every accepted point
+    /// (Poisson-disk dart throwing). Returns fewer than `count` points when the
+    /// area is too small or the criteria too strict.
+    pub fn find_spawn_points(
+        &self,
+        area: Rect,
+        count: usize,
+        criteria: &SpawnCriteria,
+    ) -> Vec<SpawnPoint> {
+        let mut points = Vec::with_capacity(count);
+        if count == 0 || area.is_empty() {
+            return points;
+        }
+
+        let mut grid = SpacingGrid::new(area, criteria.min_spacing);
+        let mut rng = SplitMix64(criteria.seed);
+        let attempts = count as u64 * criteria.attempts_per_point.max(1) as u64;
+
+        for _ in 0..attempts {
+            let xz = area.min + Vec2::new(rng.next_f32(), rng.next_f32()) * area.size();
+            if !grid.is_clear(xz) {
+                continue;
+            }
+            let Some(point) = self.spawn_point_at(xz, criteria) else {
+                continue;
+            };
+
+            grid.insert(xz);
+            points.push(point);
+            if points.len() == count {
+                break;
+            }
+        }
+
+        points
+    }
+
+    /// Sample the surface at `xz` and check it against the filters
+    fn spawn_point_at(&self, xz: Vec2, criteria: &SpawnCriteria) -> Option<SpawnPoint> {
+        let height = self.get_height(xz.x, xz.y);
+        if height < criteria.min_height || height > criteria.max_height {
+            return None;
+        }
+        if !criteria.allow_underwater && self.water_depth(xz.x, xz.y) > 0.0 {
+            return None;
+        }
+
+        let normal = self.get_normal(xz.x, xz.y);
+        if normal.y.clamp(-1.0, 1.0).acos() > criteria.max_slope {
+            return None;
+        }
+
+        let moisture = self.sample_moisture(xz.x, xz.y);
+        if moisture < criteria.moisture.0 || moisture > criteria.moisture.1 {
+            return None;
+        }
+
+        Some(SpawnPoint {
+            position: Vec3::new(xz.x, height, xz.y),
+            normal,
+        })
+    }
+}
+
+/// Spatial hash of positions reserved by scatter systems
+///
+/// Points reserved for a chunk with [`Self::try_place_in_chunk`] are released
+/// by [`release_despawned_scatter`] when that chunk despawns; points reserved
+/// with [`Self::try_place`] stay until [`Self::clear`].
+#[derive(Resource, Clone, Debug)]
+pub struct TerrainScatterGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<Vec2>>,
+    /// Points reserved per chunk node id
+    chunks: HashMap<u64, Vec<Vec2>>,
+}
+
+impl Default for TerrainScatterGrid {
+    fn default() -> Self {
+        Self::new(16.0)
+    }
+}
+
+impl TerrainScatterGrid {
+    /// Create an empty grid hashing points into cells `cell_size` wide
+    ///
+    /// Cells around the typical `min_spacing` keep lookups to a few cells.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Reserve `pos` unless a reserved point lies closer than `min_spacing`
+    ///
+    /// Only the new point's spacing is checked, so mixing spacings lets a
+    /// point with small spacing sit close to one placed with a larger one.
+    pub fn try_place(&mut self, pos: Vec2, min_spacing: f32) -> bool {
+        if !self.is_clear(pos, min_spacing) {
+            return false;
+        }
+        self.cells.entry(self.cell(pos)).or_default().push(pos);
+        true
+    }
+
+    /// [`Self::try_place`], releasing the point when chunk `node_id` despawns
+    pub fn try_place_in_chunk(&mut self, node_id: u64, pos: Vec2, min_spacing: f32) -> bool {
+        let placed = self.try_place(pos, min_spacing);
+        if placed {
+            self.chunks.entry(node_id).or_default().push(pos);
+        }
+        placed
+    }
+
+    /// Whether no reserved point lies closer than `min_spacing` to `pos`
+    pub fn is_clear(&self, pos: Vec2, min_spacing: f32) -> bool {
+        let cell = self.cell(pos);
+        let reach = (min_spacing / self.cell_size).ceil() as i32;
+        let min_spacing_sq = min_spacing * min_spacing;
+        for z in cell.y - reach..=cell.y + reach {
+            for x in cell.x - reach..=cell.x + reach {
+                if self.cells.get(&IVec2::new(x, z)).is_some_and(|points| {
+                    points
+                        .iter()
+                        .any(|other| other.distance_squared(pos) < min_spacing_sq)
+                }) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Release every point reserved for chunk `node_id`, returning how many were removed
+    pub fn remove_chunk(&mut self, node_id: u64) -> usize {
+        let Some(points) = self.chunks.remove(&node_id) else {
+            return 0;
+        };
+        for &point in &points {
+            let cell = self.cell(point);
+            if let Some(cell_points) = self.cells.get_mut(&cell) {
+                if let Some(i) = cell_points.iter().position(|&p| p == point) {
+                    cell_points.swap_remove(i);
+                }
+                if cell_points.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        points.len()
+    }
+
+    /// Release every reserved point
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.chunks.clear();
+    }
+
+    /// Number of reserved points
+    pub fn len(&self) -> usize {
+        self.cells.values().map(Vec::len).sum()
+    }
+
+    /// Whether no points are reserved
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn cell(&self, pos: Vec2) -> IVec2 {
+        (pos / self.cell_size).floor().as_ivec2()
+    }
+}
+
+/// System: Release scatter points reserved for despawned chunks
+pub fn release_despawned_scatter(
+    mut despawned: MessageReader<TerrainChunkDespawned>,
+    mut grid: ResMut<TerrainScatterGrid>,
+) {
+    for chunk in despawned.read() {
+        grid.remove_chunk(chunk.node_id);
+    }
+}
+
+/// Per-chunk decoration hook run by `spawn_chunk_entities`
+///
+/// Called once for every chunk entity right after it spawns, including the
+/// entity that replaces a chunk refined to a finer LOD. Spawn features as
+/// children of `chunk_entity` so they unload together with the chunk.
+pub trait ChunkFeatureSpawner: Send + Sync + 'static {
+    fn spawn(
+        &self,
+        commands: &mut Commands,
+        chunk_entity: Entity,
+        coords: IVec2,
+        bounds: Aabb3d,
+        height_query: &TerrainHeightQuery,
+        rng: &mut ChunkRng,
+    );
+}
+
+/// Deterministic random sequence for one chunk
+///
+/// Seeded from the chunk's grid coordinates and edge length, so a chunk
+/// draws the same values every time it streams in, independent of spawn
+/// order. Quadtree nodes at different depths can share coordinates, which
+/// the edge length tells apart.
+#[derive(Clone, Debug)]
+pub struct ChunkRng(SplitMix64);
+
+impl ChunkRng {
+    /// Sequence for the chunk at `coords` with edge length `size`
+    pub fn for_chunk(coords: IVec2, size: f32) -> Self {
+        let packed = (coords.x as u32 as u64) | ((coords.y as u32 as u64) << 32);
+        let mut seeder = SplitMix64(packed ^ (size.to_bits() as u64).rotate_left(17));
+        Self(SplitMix64(seeder.next_u64()))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    /// Uniform value in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        self.0.next_f32()
+    }
+
+    /// Uniform value in [min, max)
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+}
+
+/// Background grid for constant-time spacing checks
+///
+/// Cells are `min_spacing / √2` wide so each usually holds a single point;
+/// they only grow wider (and hold several) when the spacing is tiny relative
+/// to the area.
+struct SpacingGrid {
+    origin: Vec2,
+    cell_size: f32,
+    min_spacing_sq: f32,
+    width: i32,
+    height: i32,
+    cells: Vec<Vec<Vec2>>,
+}
+
+impl SpacingGrid {
+    fn new(area: Rect, min_spacing: f32) -> Self {
+        let min_spacing = min_spacing.max(f32::EPSILON);
+        // Very small spacing over a large area would need an enormous grid
+        let cell_size =
+            (min_spacing / std::f32::consts::SQRT_2).max(area.size().max_element() / 1024.0);
+        let width = (area.width() / cell_size).ceil().max(1.0) as i32;
+        let height = (area.height() / cell_size).ceil().max(1.0) as i32;
+        Self {
+            origin: area.min,
+            cell_size,
+            min_spacing_sq: min_spacing * min_spacing,
+            width,
+            height,
+            cells: vec![Vec::new(); (width * height) as usize],
+        }
+    }
+
+    fn cell(&self, point: Vec2) -> IVec2 {
+        ((point - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2()
+            .clamp(IVec2::ZERO, IVec2::new(self.width - 1, self.height - 1))
+    }
+
+    fn is_clear(&self, point: Vec2) -> bool {
+        let cell = self.cell(point);
+        let reach = (self.min_spacing_sq.sqrt() / self.cell_size).ceil() as i32;
+        for z in (cell.y - reach).max(0)..=(cell.y + reach).min(self.height - 1) {
+            for x in (cell.x - reach).max(0)..=(cell.x + reach).min(self.width - 1) {
+                let cell = &self.cells[(z * self.width + x) as usize];
+                if cell
+                    .iter()
+                    .any(|other| other.distance_squared(point) < self.min_spacing_sq)
+                {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn insert(&mut self, point: Vec2) {
+        let cell = self.cell(point);
+        self.cells[(cell.y * self.width + cell.x) as usize].push(point);
+    }
+}
+
+/// Small deterministic generator so placement doesn't depend on a global RNG
+#[derive(Clone, Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1)
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TerrainConfig;
+    use crate::heightmap::TerrainNoise;
+
+    #[test]
+    fn test_spawn_points_respect_criteria() {
+        let config = TerrainConfig::default();
+        let query = TerrainHeightQuery::new(TerrainNoise::default(), config.clone());
+        let area = Rect::new(-1000.0, -1000.0, 1000.0, 1000.0);
+        let criteria = SpawnCriteria::default()
+            .with_seed(7)
+            .with_spacing(40.0)
+            .with_max_slope_degrees(20.0);
+
+        let points = query.find_spawn_points(area, 100, &criteria);
+        assert!(!points.is_empty());
+        assert!(points.len() <= 100);
+
+        for (i, point) in points.iter().enumerate() {
+            assert!(area.contains(point.position.xz()));
+            assert!(point.position.y >= query.water_surface_y());
+            assert!(point.normal.angle_between(Vec3::Y) <= criteria.max_slope + 1e-4);
+            for other in &points[i + 1..] {
+                assert!(point.position.xz().distance(other.position.xz()) >= criteria.min_spacing);
+            }
+        }
+
+        // Same seed, same points
+        assert_eq!(points, query.find_spawn_points(area, 100, &criteria));
+
+        // Unsatisfiable criteria return nothing rather than bad points
+        let impossible = criteria.clone().with_height_range(1.0e6, f32::MAX);
+        assert!(query.find_spawn_points(area, 10, &impossible).is_empty());
+    }
+
+    #[test]
+    fn test_scatter_grid_rejects_points_within_spacing() {
+        let mut grid = TerrainScatterGrid::new(8.0);
+
+        assert!(grid.try_place_in_chunk(1, Vec2::new(99.0, 0.0), 10.0));
+        // Across a cell (and chunk) border, still too close
+        assert!(!grid.try_place_in_chunk(2, Vec2::new(105.0, 3.0), 10.0));
+        assert!(grid.try_place_in_chunk(2, Vec2::new(110.0, 0.0), 10.0));
+        assert_eq!(grid.len(), 2);
+
+        // Despawning the first chunk frees its space
+        assert_eq!(grid.remove_chunk(1), 1);
+        assert!(grid.try_place(Vec2::new(100.0, 0.0), 10.0));
+        assert_eq!(grid.remove_chunk(1), 0);
+        assert_eq!(grid.len(), 2);
+    }
+}
@@ -4,7 +4,7 @@
 //! structure. The quadtree is traversed each frame to determine which nodes need
 //! to be rendered and at what LOD level.
 
-use crate::config::TerrainConfig;
+use crate::config::{LodMetric, TerrainConfig};
 use bevy::math::bounding::{Aabb2d, BoundingVolume};
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -26,6 +26,14 @@ pub struct QuadtreeNode {
     pub entity: Option<Entity>,
     /// Whether this node is currently selected for rendering
     pub selected: bool,
+    /// Whether this node was subdivided during the last selection pass
+    pub subdivided: bool,
+    /// Extra mesh detail levels for nodes held at `min_mesh_chunk_size` (doubles subdivisions)
+    pub extra_detail: u8,
+    /// Lowest terrain height sampled at the corners and center during the last selection
+    pub min_height: f32,
+    /// Highest terrain height sampled at the corners and center during the last selection
+    pub max_height: f32,
     /// Children nodes (None if leaf node)
     pub children: Option<Box<[QuadtreeNode; 4]>>,
 }
@@ -41,6 +49,10 @@ impl QuadtreeNode {
             coords,
             entity: None,
             selected: false,
+            subdivided: false,
+            extra_detail: 0,
+            min_height: 0.0,
+            max_height: 0.0,
             children: None,
         }
     }
@@ -92,41 +104,108 @@ impl QuadtreeNode {
         ]));
     }
 
+    /// Sample the corners and center to refresh `min_height` and `max_height`
+    pub fn update_height_range(&mut self, height_sampler: impl Fn(f32, f32) -> f32) {
+        let Aabb2d { min, max } = self.bounds;
+        let center = self.center();
+        let (min_height, max_height) = [
+            center,
+            min,
+            max,
+            Vec2::new(min.x, max.y),
+            Vec2::new(max.x, min.y),
+        ]
+        .into_iter()
+        .map(|p| height_sampler(p.x, p.y))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), h| {
+            (lo.min(h), hi.max(h))
+        });
+        self.min_height = min_height;
+        self.max_height = max_height;
+    }
+
     /// Calculate the distance from camera to the closest point on this node's bounds
-    /// Considers terrain height for more accurate 3D distance
-    pub fn distance_to_camera(&self, camera_pos: Vec3, estimated_height: f32) -> f32 {
+    ///
+    /// The closest point's height is clamped into the node's sampled height
+    /// range, so a peak near the camera counts even when the center is low.
+    pub fn distance_to_camera(&self, camera_pos: Vec3) -> f32 {
         let center = self.center();
         let half = self.bounds.half_size();
 
         // Find closest point on the 2D bounds to camera's XZ position
         let closest_x = camera_pos.x.clamp(center.x - half.x, center.x + half.x);
         let closest_z = camera_pos.z.clamp(center.y - half.y, center.y + half.y);
+        // No finite samples (e.g. a source returning NaN) leave an empty range;
+        // measure to the water level then, like a node that was never sampled
+        let closest_y = if self.min_height <= self.max_height {
+            camera_pos.y.clamp(self.min_height, self.max_height)
+        } else {
+            0.0
+        };
 
-        // Use estimated terrain height at closest point for 3D distance
-        let closest_point = Vec3::new(closest_x, estimated_height, closest_z);
+        let closest_point = Vec3::new(closest_x, closest_y, closest_z);
         closest_point.distance(camera_pos)
     }
 
     /// Recursively select nodes for rendering based on camera distance
+    ///
+    /// When a corridor is given, the distance to the corridor replaces the
+    /// distance to the camera for LOD purposes. Nodes overlapping a detail
+    /// boost are treated as if they were closer, one halving per extra level.
+    /// `projection` is only used by [`LodMetric::ScreenSpaceError`].
+    #[allow(clippy::too_many_arguments)]
     pub fn select_for_rendering(
         &mut self,
         camera_pos: Vec3,
         config: &TerrainConfig,
         height_sampler: impl Fn(f32, f32) -> f32 + Copy,
         max_depth: u8,
+        corridor: Option<&CorridorFocus>,
+        boosts: &[DetailBoost],
+        projection: &LodProjection,
     ) {
         // Reset selection
         self.selected = false;
 
-        // Estimate height at node center for distance calculation
-        let center = self.center();
-        let estimated_height = height_sampler(center.x, center.y);
-        let distance = self.distance_to_camera(camera_pos, estimated_height);
+        // Nothing outside a bounded world is rendered
+        if !within_world_bounds(&self.bounds, config) {
+            self.subdivided = false;
+            self.extra_detail = 0;
+            self.children = None;
+            return;
+        }
+
+        // Refreshed in corridor mode too, so the range always matches this pass
+        self.update_height_range(height_sampler);
+        let distance = if let Some(corridor) = corridor {
+            corridor.distance_to_bounds(&self.bounds)
+        } else {
+            self.distance_to_camera(camera_pos)
+        };
+        let distance = distance * 0.5f32.powi(boost_levels(boosts, &self.bounds) as i32);
 
         // Determine if we should subdivide based on distance and current depth
-        let should_subdivide = self.should_subdivide(distance, config, max_depth);
+        let should_subdivide = self.should_subdivide(distance, config, max_depth, projection);
+        // Children below the minimum mesh chunk size add mesh detail instead of entities
+        let children_too_small = self.size() * 0.5 < config.min_mesh_chunk_size;
+        self.subdivided = should_subdivide && self.depth < max_depth && !children_too_small;
+        self.extra_detail = if should_subdivide && children_too_small {
+            (self.depth..max_depth)
+                .zip(0..)
+                .take_while(|&(depth, level)| match config.lod_metric {
+                    LodMetric::Distance => distance < subdivision_threshold(depth, config),
+                    LodMetric::ScreenSpaceError { pixel_error } => {
+                        let step =
+                            self.size() * 0.5f32.powi(level) / config.lod_subdivisions[0] as f32;
+                        projection.projected_error(step, distance) > pixel_error
+                    }
+                })
+                .count() as u8
+        } else {
+            0
+        };
 
-        if should_subdivide && self.depth < max_depth {
+        if self.subdivided {
             // Ensure children exist
             if self.children.is_none() {
                 let mut next_id = self.id * 4;
@@ -136,51 +215,125 @@ impl QuadtreeNode {
             // Recursively select children
             if let Some(children) = &mut self.children {
                 for child in children.iter_mut() {
-                    child.select_for_rendering(camera_pos, config, height_sampler, max_depth);
+                    child.select_for_rendering(
+                        camera_pos,
+                        config,
+                        height_sampler,
+                        max_depth,
+                        corridor,
+                        boosts,
+                        projection,
+                    );
                 }
             }
         } else {
             // This node is selected for rendering
             self.selected = true;
-            self.lod_level = self.calculate_lod(distance, config);
+            self.lod_level = self.calculate_lod(distance, config, projection);
+            // Never double the grid past MAX_MESH_SUBDIVISIONS
+            let base = config.lod_subdivisions[self.lod_level as usize].max(1);
+            let max_extra = (MAX_MESH_SUBDIVISIONS / base).max(1).ilog2() as u8;
+            self.extra_detail = self.extra_detail.min(max_extra);
+            // Kept children no longer take part, so they shouldn't remember it
+            if let Some(children) = &mut self.children {
+                for child in children.iter_mut() {
+                    child.reset_selection();
+                }
+            }
+        }
+    }
+
+    /// Clear the selection state of this node and its descendants after a merge
+    ///
+    /// Otherwise hysteresis would treat them as still subdivided when the
+    /// parent splits again.
+    fn reset_selection(&mut self) {
+        if !self.selected && !self.subdivided {
+            return;
+        }
+        self.selected = false;
+        self.subdivided = false;
+        self.extra_detail = 0;
+        if let Some(children) = &mut self.children {
+            for child in children.iter_mut() {
+                child.reset_selection();
+            }
         }
     }
 
     /// Determine if this node should be subdivided based on distance
-    fn should_subdivide(&self, distance: f32, config: &TerrainConfig, max_depth: u8) -> bool {
+    ///
+    /// Applies `lod_hysteresis` around the threshold: a subdivided node only
+    /// merges once the camera moves past the widened threshold, and a merged
+    /// node only subdivides once the camera is inside the narrowed one.
+    /// With screen-space error, a node subdivides when even its finest LOD
+    /// would exceed the pixel error.
+    fn should_subdivide(
+        &self,
+        distance: f32,
+        config: &TerrainConfig,
+        max_depth: u8,
+        projection: &LodProjection,
+    ) -> bool {
         if self.depth >= max_depth {
             return false;
         }
 
-        // Use the LOD distances to determine subdivision
-        // Closer nodes need more subdivision (higher detail)
-        let lod_threshold = match self.depth {
-            0 => config.lod_distances[2] * 2.0, // Very large nodes
-            1 => config.lod_distances[2],
-            2 => config.lod_distances[1],
-            3 => config.lod_distances[0],
-            _ => config.lod_distances[0] * 0.5,
+        if let LodMetric::ScreenSpaceError { pixel_error } = config.lod_metric {
+            let step = self.size() / config.lod_subdivisions[0] as f32;
+            let buffer = pixel_error * config.lod_hysteresis;
+            let effective_error = if self.subdivided {
+                pixel_error - buffer
+            } else {
+                pixel_error + buffer
+            };
+            return projection.projected_error(step, distance) > effective_error;
+        }
+
+        let lod_threshold = subdivision_threshold(self.depth, config);
+
+        let buffer = lod_threshold * config.lod_hysteresis;
+        let effective_threshold = if self.subdivided {
+            lod_threshold + buffer
+        } else {
+            lod_threshold - buffer
         };
 
-        distance < lod_threshold
+        distance < effective_threshold
     }
 
     /// Calculate the LOD level for this node based on distance
-    fn calculate_lod(&self, distance: f32, config: &TerrainConfig) -> u8 {
-        if distance < config.lod_distances[0] {
-            0 // Highest detail
-        } else if distance < config.lod_distances[1] {
-            1
-        } else if distance < config.lod_distances[2] {
-            2
-        } else {
-            3 // Lowest detail
+    ///
+    /// With screen-space error this is the coarsest LOD whose grid spacing
+    /// stays within the pixel error.
+    fn calculate_lod(
+        &self,
+        distance: f32,
+        config: &TerrainConfig,
+        projection: &LodProjection,
+    ) -> u8 {
+        if let LodMetric::ScreenSpaceError { pixel_error } = config.lod_metric {
+            return (0..config.lod_subdivisions.len())
+                .rev()
+                .find(|&lod| {
+                    let step = self.size() / config.lod_subdivisions[lod] as f32;
+                    projection.projected_error(step, distance) <= pixel_error
+                })
+                .unwrap_or(0) as u8;
         }
+
+        // Past every threshold is the lowest detail level
+        let lod = config
+            .lod_distances
+            .iter()
+            .position(|&threshold| distance < threshold)
+            .unwrap_or(config.lod_distances.len());
+        lod.min(config.lod_subdivisions.len() - 1) as u8
     }
 
-    /// Get the mesh subdivisions for this node's LOD level
+    /// Get the mesh subdivisions for this node's LOD level and extra detail
     pub fn subdivisions(&self, config: &TerrainConfig) -> u32 {
-        config.lod_subdivisions[self.lod_level as usize]
+        config.lod_subdivisions[self.lod_level as usize] << self.extra_detail
     }
 
     /// Collect all selected nodes into a vector
@@ -192,6 +345,7 @@ impl QuadtreeNode {
                 lod_level: self.lod_level,
                 coords: self.coords,
                 entity: self.entity,
+                extra_detail: self.extra_detail,
             });
         } else if let Some(children) = &self.children {
             for child in children.iter() {
@@ -201,6 +355,212 @@ impl QuadtreeNode {
     }
 }
 
+/// Whether node bounds overlap `config.world_bounds` (always true for an endless world)
+///
+/// Nodes merely touching the bounds along an edge don't count.
+fn within_world_bounds(bounds: &Aabb2d, config: &TerrainConfig) -> bool {
+    config.world_bounds.is_none_or(|world| {
+        !world
+            .intersect(Rect::from_corners(bounds.min, bounds.max))
+            .is_empty()
+    })
+}
+
+/// Camera projection parameters for screen-space error LOD
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LodProjection {
+    /// Vertical field of view in radians
+    pub fov_y: f32,
+    /// Viewport height in physical pixels
+    pub viewport_height: f32,
+}
+
+impl Default for LodProjection {
+    fn default() -> Self {
+        Self {
+            fov_y: std::f32::consts::FRAC_PI_4,
+            viewport_height: 1080.0,
+        }
+    }
+}
+
+impl LodProjection {
+    /// Size in pixels of a world-space length seen face-on at `distance`
+    pub fn projected_error(&self, world_error: f32, distance: f32) -> f32 {
+        let view_height = 2.0 * distance.max(f32::EPSILON) * (self.fov_y * 0.5).tan();
+        world_error * self.viewport_height / view_height
+    }
+}
+
+/// Polyline focus for streaming terrain along a path (rails, on-rails levels)
+///
+/// Detail is driven by the distance to the path instead of the distance to the
+/// camera: nodes within `width / 2` of the polyline get full detail, and detail
+/// tapers off with the regular LOD distances beyond that.
+#[derive(Clone, Debug)]
+pub struct CorridorFocus {
+    /// Path vertices in world XZ coordinates
+    pub points: Vec<Vec2>,
+    /// Full width of the high-detail band around the path
+    pub width: f32,
+}
+
+impl CorridorFocus {
+    /// Create a corridor along the given polyline
+    pub fn new(points: Vec<Vec2>, width: f32) -> Self {
+        Self { points, width }
+    }
+
+    /// Distance from a point to the polyline
+    pub fn distance_to_point(&self, point: Vec2) -> f32 {
+        match self.points.as_slice() {
+            [] => f32::INFINITY,
+            [single] => single.distance(point),
+            points => points
+                .windows(2)
+                .map(|segment| distance_point_segment(point, segment[0], segment[1]))
+                .fold(f32::INFINITY, f32::min),
+        }
+    }
+
+    /// LOD distance for a node: distance from its bounds to the corridor band
+    pub fn distance_to_bounds(&self, bounds: &Aabb2d) -> f32 {
+        let distance = match self.points.as_slice() {
+            [] => f32::INFINITY,
+            [single] => distance_point_aabb(*single, bounds),
+            points => points
+                .windows(2)
+                .map(|segment| distance_segment_aabb(segment[0], segment[1], bounds))
+                .fold(f32::INFINITY, f32::min),
+        };
+
+        (distance - self.width * 0.5).max(0.0)
+    }
+}
+
+/// Camera distance below which a node at `depth` subdivides
+///
+/// Closer nodes need more subdivision (higher detail). Depth 1 uses the
+/// farthest LOD distance and each deeper level the next nearer one.
+fn subdivision_threshold(depth: u8, config: &TerrainConfig) -> f32 {
+    let distances = &config.lod_distances;
+    let (Some(&nearest), Some(&farthest)) = (distances.first(), distances.last()) else {
+        return 0.0;
+    };
+
+    match depth as usize {
+        0 => farthest * 2.0, // Very large nodes
+        depth if depth <= distances.len() => distances[distances.len() - depth],
+        _ => nearest * 0.5,
+    }
+}
+
+/// Temporary request for extra detail in a circular region
+///
+/// The extra levels decay linearly over the boost's lifetime; see
+/// [`TerrainQuadtree::boost_region`].
+#[derive(Clone, Debug)]
+pub struct DetailBoost {
+    /// Region center in world XZ coordinates
+    pub center: Vec2,
+    /// Region radius
+    pub radius: f32,
+    /// Extra quadtree levels at the start of the boost
+    pub extra_lod: u8,
+    /// Total lifetime in seconds
+    pub duration: f32,
+    /// Remaining lifetime in seconds
+    pub remaining: f32,
+}
+
+impl DetailBoost {
+    /// Extra levels currently granted, decaying toward zero as the boost expires
+    pub fn extra_levels(&self) -> u8 {
+        if self.remaining <= 0.0 {
+            return 0;
+        }
+        let fraction = (self.remaining / self.duration.max(f32::EPSILON)).min(1.0);
+        (self.extra_lod as f32 * fraction).ceil() as u8
+    }
+
+    /// Whether the boost region touches the given bounds
+    pub fn overlaps(&self, bounds: &Aabb2d) -> bool {
+        distance_point_aabb(self.center, bounds) <= self.radius
+    }
+}
+
+/// Largest boost level among the boosts overlapping `bounds`
+fn boost_levels(boosts: &[DetailBoost], bounds: &Aabb2d) -> u8 {
+    boosts
+        .iter()
+        .filter(|boost| boost.overlaps(bounds))
+        .map(DetailBoost::extra_levels)
+        .max()
+        .unwrap_or(0)
+}
+
+fn distance_point_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_squared();
+    if length_sq <= f32::EPSILON {
+        return point.distance(a);
+    }
+    let t = ((point - a).dot(ab) / length_sq).clamp(0.0, 1.0);
+    point.distance(a + ab * t)
+}
+
+fn distance_point_aabb(point: Vec2, bounds: &Aabb2d) -> f32 {
+    point.distance(point.clamp(bounds.min, bounds.max))
+}
+
+fn distance_segment_aabb(a: Vec2, b: Vec2, bounds: &Aabb2d) -> f32 {
+    if segment_intersects_aabb(a, b, bounds) {
+        return 0.0;
+    }
+
+    // Disjoint convex shapes: the closest pair involves a segment endpoint or a box corner
+    let corners = [
+        bounds.min,
+        Vec2::new(bounds.max.x, bounds.min.y),
+        bounds.max,
+        Vec2::new(bounds.min.x, bounds.max.y),
+    ];
+    corners
+        .iter()
+        .map(|&corner| distance_point_segment(corner, a, b))
+        .chain([
+            distance_point_aabb(a, bounds),
+            distance_point_aabb(b, bounds),
+        ])
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Slab test for a segment against an axis-aligned box
+fn segment_intersects_aabb(a: Vec2, b: Vec2, bounds: &Aabb2d) -> bool {
+    let dir = b - a;
+    let mut t_min: f32 = 0.0;
+    let mut t_max: f32 = 1.0;
+
+    for axis in 0..2 {
+        if dir[axis].abs() <= f32::EPSILON {
+            if a[axis] < bounds.min[axis] || a[axis] > bounds.max[axis] {
+                return false;
+            }
+        } else {
+            let inv = 1.0 / dir[axis];
+            let t0 = (bounds.min[axis] - a[axis]) * inv;
+            let t1 = (bounds.max[axis] - a[axis]) * inv;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+            if t_min > t_max {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
 /// A node that has been selected for rendering
 #[derive(Clone, Debug)]
 pub struct SelectedNode {
@@ -209,6 +569,123 @@ pub struct SelectedNode {
     pub lod_level: u8,
     pub coords: IVec2,
     pub entity: Option<Entity>,
+    /// Extra mesh detail levels (each doubles the LOD's subdivisions)
+    pub extra_detail: u8,
+}
+
+impl SelectedNode {
+    /// Get the mesh subdivisions for this node's LOD level and extra detail
+    pub fn subdivisions(&self, config: &TerrainConfig) -> u32 {
+        config.lod_subdivisions[self.lod_level as usize] << self.extra_detail
+    }
+
+    /// Edge stitch levels toward coarser neighbors, ordered -Z, +X, +Z, -X
+    ///
+    /// Each entry is log2 of the neighbor's vertex spacing over this node's,
+    /// for the selected node just across the middle of that edge. Finer,
+    /// equal, or missing neighbors give 0.
+    pub fn neighbor_lods(&self, selected: &SelectedNodeIndex, config: &TerrainConfig) -> [u8; 4] {
+        let size = self.bounds.half_size().x * 2.0;
+        let step = size / self.subdivisions(config) as f32;
+        let probe = size * 0.5 + step * 0.5;
+
+        [Vec2::NEG_Y, Vec2::X, Vec2::Y, Vec2::NEG_X].map(|direction| {
+            let point = self.bounds.center() + direction * probe;
+            selected
+                .node_at(point)
+                .filter(|other| other.id != self.id)
+                .map_or(0, |neighbor| {
+                    let neighbor_size = neighbor.bounds.half_size().x * 2.0;
+                    let neighbor_step = neighbor_size / neighbor.subdivisions(config) as f32;
+                    (neighbor_step / step).log2().round().max(0.0) as u8
+                })
+        })
+    }
+}
+
+/// Selected nodes keyed by side length and grid cell, for lookups by position
+///
+/// Built once per selection so finding a node's neighbors costs one hash
+/// lookup per side length instead of a scan over every selected node.
+pub struct SelectedNodeIndex<'a> {
+    /// Nodes by side length (as bits) and grid cell
+    cells: HashMap<(u32, IVec2), &'a SelectedNode>,
+    /// Side lengths in use with the offset that puts their cell corners on integers
+    sizes: Vec<(f32, Vec2)>,
+}
+
+impl<'a> SelectedNodeIndex<'a> {
+    pub fn new(selected: &'a [SelectedNode]) -> Self {
+        let mut cells = HashMap::with_capacity(selected.len());
+        let mut sizes: Vec<(f32, Vec2)> = Vec::new();
+        for node in selected {
+            let size = node.bounds.half_size().x * 2.0;
+            let corner = node.bounds.min / size;
+            let offset = sizes
+                .iter()
+                .find(|&&(known, _)| known == size)
+                .map(|&(_, offset)| offset)
+                .unwrap_or_else(|| {
+                    let offset = corner.round() - corner;
+                    sizes.push((size, offset));
+                    offset
+                });
+            cells.insert((size.to_bits(), (corner + offset).round().as_ivec2()), node);
+        }
+        Self { cells, sizes }
+    }
+
+    /// Selected node containing `point`, if any
+    pub fn node_at(&self, point: Vec2) -> Option<&'a SelectedNode> {
+        self.sizes.iter().find_map(|&(size, offset)| {
+            let cell = (point / size + offset).floor().as_ivec2();
+            self.cells
+                .get(&(size.to_bits(), cell))
+                .copied()
+                .filter(|node| node.bounds.closest_point(point) == point)
+        })
+    }
+}
+
+/// Heap level that root nodes occupy in the node ID space
+///
+/// Node IDs follow 4-ary heap numbering (children of `id` are `id * 4 + 1..=4`).
+/// Placing every root on the same heap level gives each root a disjoint ID
+/// range, so IDs never collide across the forest.
+const ROOT_LEVEL: u32 = 16;
+
+/// First node ID on [`ROOT_LEVEL`]: (4^16 - 1) / 3
+const ROOT_ID_BASE: u64 = ((1 << (2 * ROOT_LEVEL)) - 1) / 3;
+
+/// Deepest quadtree depth whose node (and child) IDs fit in a `u64`
+pub const MAX_QUADTREE_DEPTH: u8 = (30 - ROOT_LEVEL) as u8;
+
+/// Most subdivisions `extra_detail` may raise a chunk mesh to
+pub const MAX_MESH_SUBDIVISIONS: u32 = 256;
+
+/// Node ID of the root at the given root grid coordinates
+///
+/// Coordinates are zigzag- and Morton-encoded into the root level, which is
+/// unique for coordinates within ±32767 roots of the origin.
+pub fn root_node_id(coords: IVec2) -> u64 {
+    let zigzag = |v: i32| (((v << 1) ^ (v >> 31)) as u64) & 0xFFFF;
+    let spread = |v: u64| {
+        let v = (v | (v << 8)) & 0x00FF_00FF;
+        let v = (v | (v << 4)) & 0x0F0F_0F0F;
+        let v = (v | (v << 2)) & 0x3333_3333;
+        (v | (v << 1)) & 0x5555_5555
+    };
+    ROOT_ID_BASE + (spread(zigzag(coords.x)) | (spread(zigzag(coords.y)) << 1))
+}
+
+/// Parent node ID, or `None` for root nodes
+pub fn parent_node_id(id: u64) -> Option<u64> {
+    (id > ROOT_ID_BASE * 4).then(|| (id - 1) / 4)
+}
+
+/// IDs of the four children of a node, in [`QuadtreeNode::subdivide`] order
+pub fn child_node_ids(id: u64) -> [u64; 4] {
+    [1, 2, 3, 4].map(|offset| id * 4 + offset)
 }
 
 /// The terrain quadtree resource that manages all terrain nodes
@@ -220,8 +697,12 @@ pub struct TerrainQuadtree {
     pub max_depth: u8,
     /// Size of each root node
     pub root_size: f32,
-    /// Next available node ID
-    next_id: u64,
+    /// Optional corridor that replaces camera distance for LOD selection
+    pub corridor: Option<CorridorFocus>,
+    /// Active temporary detail boosts
+    pub boosts: Vec<DetailBoost>,
+    /// Camera projection used by [`LodMetric::ScreenSpaceError`]
+    pub projection: LodProjection,
 }
 
 impl Default for TerrainQuadtree {
@@ -230,7 +711,9 @@ impl Default for TerrainQuadtree {
             roots: HashMap::new(),
             max_depth: 4,
             root_size: 800.0, // 8x the default chunk size of 100
-            next_id: 0,
+            corridor: None,
+            boosts: Vec::new(),
+            projection: LodProjection::default(),
         }
     }
 }
@@ -242,10 +725,26 @@ impl TerrainQuadtree {
             roots: HashMap::new(),
             max_depth,
             root_size,
-            next_id: 0,
+            corridor: None,
+            boosts: Vec::new(),
+            projection: LodProjection::default(),
+        }
+    }
+
+    /// Create a quadtree deep enough to reach the config's finest LOD
+    pub fn from_config(config: &TerrainConfig) -> Self {
+        Self {
+            max_depth: Self::depth_for(config),
+            ..default()
         }
     }
 
+    /// Depth at which nodes reach LOD 0: one level per LOD distance plus the root,
+    /// capped by `max_quadtree_depth`
+    pub fn depth_for(config: &TerrainConfig) -> u8 {
+        (config.lod_distances.len() + 1).min(config.max_quadtree_depth as usize) as u8
+    }
+
     /// Update the quadtree based on camera position
     pub fn update(
         &mut self,
@@ -265,27 +764,73 @@ impl TerrainQuadtree {
         for z in -roots_needed..=roots_needed {
             for x in -roots_needed..=roots_needed {
                 let coords = IVec2::new(root_x + x, root_z + z);
-                let root = self.roots.entry(coords).or_insert_with(|| {
-                    let center = Vec2::new(
-                        coords.x as f32 * self.root_size,
-                        coords.y as f32 * self.root_size,
-                    );
-                    let bounds = Aabb2d::new(center, Vec2::splat(self.root_size * 0.5));
-                    self.next_id += 1;
-                    QuadtreeNode::new(self.next_id, bounds, 0, coords)
-                });
+                let bounds = self.root_bounds(coords);
+                if !within_world_bounds(&bounds, config) {
+                    continue;
+                }
+                let root = self
+                    .roots
+                    .entry(coords)
+                    .or_insert_with(|| QuadtreeNode::new(root_node_id(coords), bounds, 0, coords));
 
-                root.select_for_rendering(camera_pos, config, height_sampler, self.max_depth);
+                root.select_for_rendering(
+                    camera_pos,
+                    config,
+                    height_sampler,
+                    self.max_depth.min(MAX_QUADTREE_DEPTH),
+                    self.corridor.as_ref(),
+                    &self.boosts,
+                    &self.projection,
+                );
             }
         }
 
-        // Remove root nodes that are too far away
+        // Remove root nodes that are too far away or outside the world
         let max_dist = roots_needed + 2;
-        self.roots.retain(|coords, _| {
-            (coords.x - root_x).abs() <= max_dist && (coords.y - root_z).abs() <= max_dist
+        self.roots.retain(|coords, root| {
+            (coords.x - root_x).abs() <= max_dist
+                && (coords.y - root_z).abs() <= max_dist
+                && within_world_bounds(&root.bounds, config)
         });
     }
 
+    /// Bounds of the root node at the given root grid coordinates
+    fn root_bounds(&self, coords: IVec2) -> Aabb2d {
+        let center = coords.as_vec2() * self.root_size;
+        Aabb2d::new(center, Vec2::splat(self.root_size * 0.5))
+    }
+
+    /// Stream detail along a path instead of around the camera (None restores camera focus)
+    pub fn set_corridor(&mut self, corridor: Option<CorridorFocus>) {
+        self.corridor = corridor;
+    }
+
+    /// Temporarily subdivide `extra_lod` levels deeper than distance alone would within a region
+    ///
+    /// The boost decays over `duration` seconds as [`Self::tick_boosts`] is called.
+    pub fn boost_region(&mut self, center: Vec2, radius: f32, extra_lod: u8, duration: f32) {
+        self.boosts.push(DetailBoost {
+            center,
+            radius,
+            extra_lod,
+            duration,
+            remaining: duration,
+        });
+    }
+
+    /// Advance boost timers and drop expired boosts
+    pub fn tick_boosts(&mut self, delta_secs: f32) {
+        for boost in &mut self.boosts {
+            boost.remaining -= delta_secs;
+        }
+        self.boosts.retain(|boost| boost.remaining > 0.0);
+    }
+
+    /// Extra detail levels currently applied to the given bounds
+    pub fn boost_levels(&self, bounds: &Aabb2d) -> u8 {
+        boost_levels(&self.boosts, bounds)
+    }
+
     /// Collect all nodes that should be rendered
     pub fn collect_selected_nodes(&self) -> Vec<SelectedNode> {
         let mut selected = Vec::new();
@@ -375,7 +920,7 @@ pub fn calculate_lod_with_hysteresis(
         }
     }
 
-    subdivisions[3]
+    subdivisions[subdivisions.len() - 1]
 }
 
 #[cfg(test)]
@@ -417,9 +962,461 @@ mod tests {
         let node = QuadtreeNode::new(1, bounds, 0, IVec2::ZERO);
 
         let camera_pos = Vec3::new(0.0, 100.0, 0.0);
-        let distance = node.distance_to_camera(camera_pos, 0.0);
+        let distance = node.distance_to_camera(camera_pos);
 
         // Should be approximately sqrt((50)^2 + (100)^2 + (50)^2) for corner case
         assert!(distance > 0.0);
     }
+
+    #[test]
+    fn test_six_lod_levels() {
+        let config = TerrainConfig::builder()
+            .lod_distances([100.0, 200.0, 400.0, 800.0, 1600.0])
+            .lod_subdivisions([128, 64, 32, 16, 8, 4])
+            .build();
+
+        let node = QuadtreeNode::new(
+            1,
+            Aabb2d::new(Vec2::ZERO, Vec2::splat(25.0)),
+            0,
+            IVec2::ZERO,
+        );
+        let projection = LodProjection::default();
+        let lods: Vec<u8> = [50.0, 150.0, 300.0, 600.0, 1200.0, 5000.0]
+            .iter()
+            .map(|&distance| node.calculate_lod(distance, &config, &projection))
+            .collect();
+        assert_eq!(lods, [0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(calculate_lod_with_hysteresis(5000.0, 4, &config), 4);
+        assert_eq!(calculate_lod_with_hysteresis(1200.0, 4, &config), 8);
+
+        // Each depth down to the finest LOD gets its own, nearer threshold
+        let thresholds: Vec<f32> = (0..=6)
+            .map(|depth| subdivision_threshold(depth, &config))
+            .collect();
+        assert_eq!(
+            thresholds,
+            [3200.0, 1600.0, 800.0, 400.0, 200.0, 100.0, 50.0]
+        );
+
+        // The plugin's quadtree reaches every level, not just the default four
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 10.0, 0.0)));
+        app.update();
+        let quadtree = app.world().resource::<TerrainQuadtree>();
+        assert_eq!(quadtree.max_depth, 6);
+        let selected = quadtree.collect_selected_nodes();
+        assert!(selected.iter().any(|node| node.lod_level == 5));
+        assert!(selected.iter().any(|node| node.lod_level == 0));
+
+        // Dropping LOD distances at runtime shrinks the tree to match
+        app.world_mut()
+            .resource_mut::<TerrainConfig>()
+            .lod_distances
+            .truncate(2);
+        app.update();
+        assert_eq!(app.world().resource::<TerrainQuadtree>().max_depth, 3);
+    }
+
+    #[test]
+    fn test_world_bounds_clip_selection() {
+        let bounds = Rect::new(-1000.0, -1000.0, 1000.0, 1000.0);
+        let config = TerrainConfig::builder()
+            .render_distance(20)
+            .world_bounds(bounds)
+            .build();
+        let mut quadtree = TerrainQuadtree::new(6, 800.0);
+
+        // Camera near a corner of the map
+        quadtree.update(Vec3::new(900.0, 50.0, -900.0), &config, |_, _| 0.0);
+        let selected = quadtree.collect_selected_nodes();
+        assert!(!selected.is_empty());
+        for node in &selected {
+            let node_rect = Rect::from_corners(node.bounds.min, node.bounds.max);
+            assert!(!bounds.intersect(node_rect).is_empty(), "{node_rect:?}");
+        }
+        assert!(quadtree.roots.len() <= 9);
+
+        // Leaving the map drops roots that were in range before
+        quadtree.update(Vec3::new(5000.0, 50.0, 0.0), &config, |_, _| 0.0);
+        assert!(
+            quadtree
+                .collect_selected_nodes()
+                .iter()
+                .all(|node| node.bounds.min.x < 1000.0)
+        );
+    }
+
+    #[test]
+    fn test_merge_clears_subdivided_descendants() {
+        let config = TerrainConfig::default();
+        let bounds = Aabb2d::new(Vec2::ZERO, Vec2::splat(100.0));
+        let mut node = QuadtreeNode::new(1, bounds, 1, IVec2::ZERO);
+        let select = |node: &mut QuadtreeNode, camera_pos: Vec3| {
+            node.select_for_rendering(
+                camera_pos,
+                &config,
+                |_, _| 0.0,
+                4,
+                None,
+                &[],
+                &LodProjection::default(),
+            );
+        };
+
+        select(&mut node, Vec3::new(0.0, 10.0, 0.0));
+        assert!(node.subdivided);
+        assert!(
+            node.children
+                .as_ref()
+                .unwrap()
+                .iter()
+                .any(|child| child.subdivided)
+        );
+
+        // Far enough to merge the node itself
+        select(&mut node, Vec3::new(10_000.0, 10.0, 0.0));
+        assert!(node.selected && !node.subdivided);
+        fn any_stale(node: &QuadtreeNode) -> bool {
+            node.children
+                .iter()
+                .flat_map(|children| children.iter())
+                .any(|child| child.subdivided || child.selected || any_stale(child))
+        }
+        assert!(!any_stale(&node));
+    }
+
+    #[test]
+    fn test_subdivision_hysteresis() {
+        let config = TerrainConfig::default();
+        // Depth 3 subdivides below lod_distances[0] = 300 (±15% hysteresis)
+        let bounds = Aabb2d::new(Vec2::ZERO, Vec2::splat(25.0));
+        let mut node = QuadtreeNode::new(1, bounds, 3, IVec2::ZERO);
+        let mut select = |distance: f32| {
+            let camera_pos = Vec3::new(25.0 + distance, 0.0, 0.0);
+            node.select_for_rendering(
+                camera_pos,
+                &config,
+                |_, _| 0.0,
+                4,
+                None,
+                &[],
+                &LodProjection::default(),
+            );
+            node.subdivided
+        };
+
+        assert!(!select(400.0));
+        // Inside the raw threshold but not past the narrowed one
+        assert!(!select(280.0));
+        assert!(select(200.0));
+
+        // Oscillating across the raw threshold keeps the node subdivided
+        for _ in 0..5 {
+            assert!(select(320.0));
+            assert!(select(280.0));
+        }
+
+        assert!(!select(400.0));
+    }
+
+    #[test]
+    fn test_tall_node_subdivides_before_flat_one() {
+        let config = TerrainConfig::default();
+        // Depth 3 subdivides below lod_distances[0] = 300; the camera is ~410 from flat ground
+        let bounds = Aabb2d::new(Vec2::ZERO, Vec2::splat(25.0));
+        let camera_pos = Vec3::new(125.0, 400.0, 0.0);
+        let select = |height_sampler: fn(f32, f32) -> f32| {
+            let mut node = QuadtreeNode::new(1, bounds, 3, IVec2::ZERO);
+            node.select_for_rendering(
+                camera_pos,
+                &config,
+                height_sampler,
+                4,
+                None,
+                &[],
+                &LodProjection::default(),
+            );
+            node
+        };
+
+        let flat = select(|_, _| 0.0);
+        assert!(!flat.subdivided);
+
+        // A ridge along the near edge; the center alone would still read as flat
+        let tall = select(|x, _| if x > 20.0 { 380.0 } else { 0.0 });
+        assert_eq!((tall.min_height, tall.max_height), (0.0, 380.0));
+        assert!(tall.subdivided);
+    }
+
+    #[test]
+    fn test_nan_heights_measure_to_water_level() {
+        let mut node = QuadtreeNode::new(
+            1,
+            Aabb2d::new(Vec2::ZERO, Vec2::splat(25.0)),
+            3,
+            IVec2::ZERO,
+        );
+        node.update_height_range(|_, _| f32::NAN);
+        assert!(node.min_height > node.max_height);
+        assert_eq!(node.distance_to_camera(Vec3::new(0.0, 40.0, 0.0)), 40.0);
+    }
+
+    #[test]
+    fn test_corridor_selection_refreshes_height_range() {
+        let config = TerrainConfig::default();
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.set_corridor(Some(CorridorFocus::new(
+            vec![Vec2::ZERO, Vec2::new(500.0, 0.0)],
+            50.0,
+        )));
+        quadtree.update(Vec3::new(0.0, 50.0, 0.0), &config, |_, _| 75.0);
+
+        let selected = quadtree.collect_selected_nodes();
+        assert!(!selected.is_empty());
+        assert!(selected.iter().all(|selected| {
+            let node = quadtree.find_node(selected.id).unwrap();
+            (node.min_height, node.max_height) == (75.0, 75.0)
+        }));
+    }
+
+    #[test]
+    fn test_corridor_focus_detail() {
+        let config = TerrainConfig::builder().render_distance(30).build();
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.set_corridor(Some(CorridorFocus::new(
+            vec![Vec2::new(0.0, 0.0), Vec2::new(3000.0, 0.0)],
+            100.0,
+        )));
+
+        // Camera sits perpendicular to the path start, well outside the corridor
+        let camera_pos = Vec3::new(0.0, 50.0, 1500.0);
+        quadtree.update(camera_pos, &config, |_, _| 0.0);
+
+        let selected = quadtree.collect_selected_nodes();
+        let lod_at = |point: Vec2| {
+            selected
+                .iter()
+                .find(|node| node.bounds.closest_point(point) == point)
+                .map(|node| node.lod_level)
+                .expect("point should be covered by a selected node")
+        };
+
+        // Far from the camera but on the path: full detail
+        assert_eq!(lod_at(Vec2::new(2900.0, 10.0)), 0);
+        // Right under the camera near the path endpoint, but far from the path: coarse
+        assert!(lod_at(Vec2::new(10.0, 1500.0)) >= 2);
+    }
+
+    #[test]
+    fn test_corridor_distance_to_bounds() {
+        let corridor = CorridorFocus::new(vec![Vec2::ZERO, Vec2::new(100.0, 0.0)], 20.0);
+
+        let crossing = Aabb2d::new(Vec2::new(50.0, 0.0), Vec2::splat(5.0));
+        assert_eq!(corridor.distance_to_bounds(&crossing), 0.0);
+
+        let beside = Aabb2d::new(Vec2::new(50.0, 60.0), Vec2::splat(10.0));
+        assert!((corridor.distance_to_bounds(&beside) - 40.0).abs() < 1e-4);
+
+        let past_end = Aabb2d::new(Vec2::new(150.0, 0.0), Vec2::splat(10.0));
+        assert!((corridor.distance_to_bounds(&past_end) - 30.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_boost_region_subdivides_distant_area() {
+        let config = TerrainConfig::builder().render_distance(40).build();
+        let camera_pos = Vec3::new(0.0, 50.0, 0.0);
+        let target = Vec2::new(2600.0, 10.0);
+        let depth_at = |quadtree: &TerrainQuadtree| {
+            let selected = quadtree.collect_selected_nodes();
+            let node = selected
+                .iter()
+                .find(|node| node.bounds.closest_point(target) == target)
+                .expect("target should be covered by a selected node");
+            quadtree.find_node(node.id).unwrap().depth
+        };
+
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.update(camera_pos, &config, |_, _| 0.0);
+        let default_depth = depth_at(&quadtree);
+
+        quadtree.boost_region(target, 50.0, 2, 5.0);
+        quadtree.update(camera_pos, &config, |_, _| 0.0);
+        let boosted_depth = depth_at(&quadtree);
+        assert!(boosted_depth > default_depth);
+
+        // Boost expires and the region merges back (within hysteresis of the default)
+        quadtree.tick_boosts(5.0);
+        assert!(quadtree.boosts.is_empty());
+        quadtree.update(camera_pos, &config, |_, _| 0.0);
+        assert!(depth_at(&quadtree) < boosted_depth);
+    }
+
+    #[test]
+    fn test_min_mesh_chunk_size_adds_subdivisions() {
+        let camera_pos = Vec3::new(0.0, 10.0, 0.0);
+        let base = TerrainConfig::builder().render_distance(4).build();
+        let capped = TerrainConfig::builder()
+            .render_distance(4)
+            .min_mesh_chunk_size(200.0)
+            .build();
+
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.update(camera_pos, &base, |_, _| 0.0);
+        let uncapped = quadtree.collect_selected_nodes();
+
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.update(camera_pos, &capped, |_, _| 0.0);
+        let selected = quadtree.collect_selected_nodes();
+
+        // Fewer, larger entities, none smaller than the minimum
+        assert!(selected.len() < uncapped.len());
+        assert!(
+            selected
+                .iter()
+                .all(|node| node.bounds.half_size().x * 2.0 >= 200.0)
+        );
+
+        // The node under the camera makes up for its size with more subdivisions
+        let under_camera = |nodes: &[SelectedNode]| {
+            nodes
+                .iter()
+                .find(|node| node.bounds.closest_point(Vec2::ONE) == Vec2::ONE)
+                .cloned()
+                .unwrap()
+        };
+        let capped_node = under_camera(&selected);
+        let uncapped_node = under_camera(&uncapped);
+        assert!(capped_node.extra_detail > 0);
+        assert!(capped_node.subdivisions(&capped) > uncapped_node.subdivisions(&base));
+
+        // However large the nodes get, the extra detail stops at MAX_MESH_SUBDIVISIONS
+        let huge = TerrainConfig::builder()
+            .render_distance(4)
+            .min_mesh_chunk_size(100_000.0)
+            .build();
+        let mut quadtree = TerrainQuadtree::default();
+        quadtree.update(camera_pos, &huge, |_, _| 0.0);
+        let node = under_camera(&quadtree.collect_selected_nodes());
+        assert!(node.extra_detail > 0);
+        assert_eq!(node.subdivisions(&huge), MAX_MESH_SUBDIVISIONS);
+    }
+
+    #[test]
+    fn test_neighbor_lods_report_coarser_edges() {
+        let config = TerrainConfig::default();
+        let node = |id: u64, center: Vec2, half: f32, lod_level: u8| SelectedNode {
+            id,
+            bounds: Aabb2d::new(center, Vec2::splat(half)),
+            lod_level,
+            coords: IVec2::ZERO,
+            entity: None,
+            extra_detail: 0,
+        };
+
+        // A 100m LOD 0 node (64 subdivisions) with a same-size LOD 1 neighbor on +X,
+        // a 200m LOD 1 neighbor on +Z, and finer nodes on -X
+        let center = node(1, Vec2::ZERO, 50.0, 0);
+        let selected = vec![
+            center.clone(),
+            node(2, Vec2::new(100.0, 0.0), 50.0, 1),
+            node(3, Vec2::new(50.0, 150.0), 100.0, 1),
+            node(4, Vec2::new(-75.0, 25.0), 25.0, 0),
+            node(5, Vec2::new(-75.0, -25.0), 25.0, 0),
+        ];
+
+        let index = SelectedNodeIndex::new(&selected);
+        assert_eq!(center.neighbor_lods(&index, &config), [0, 1, 2, 0]);
+        assert_eq!(
+            index.node_at(Vec2::new(-60.0, 10.0)).map(|node| node.id),
+            Some(4)
+        );
+        assert!(index.node_at(Vec2::new(500.0, 500.0)).is_none());
+    }
+
+    #[test]
+    fn test_node_ids_unique_across_roots() {
+        fn collect_ids(node: &mut QuadtreeNode, depth: u8, ids: &mut Vec<u64>) {
+            ids.push(node.id);
+            if depth == 0 {
+                return;
+            }
+            let mut next_id = node.id * 4;
+            node.subdivide(&mut next_id);
+            let node_id = node.id;
+            for (child, expected) in node
+                .children
+                .as_mut()
+                .unwrap()
+                .iter_mut()
+                .zip(child_node_ids(node_id))
+            {
+                assert_eq!(child.id, expected);
+                assert_eq!(parent_node_id(child.id), Some(node_id));
+                collect_ids(child, depth - 1, ids);
+            }
+        }
+
+        let mut ids = Vec::new();
+        for z in -4..=4 {
+            for x in -4..=4 {
+                let coords = IVec2::new(x, z);
+                let bounds = Aabb2d::new(coords.as_vec2() * 800.0, Vec2::splat(400.0));
+                let mut root = QuadtreeNode::new(root_node_id(coords), bounds, 0, coords);
+                assert_eq!(parent_node_id(root.id), None);
+                collect_ids(&mut root, 5, &mut ids);
+            }
+        }
+
+        let unique: std::collections::HashSet<u64> = ids.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len());
+
+        // The deepest supported level still has room for child IDs
+        let far_root = root_node_id(IVec2::splat(-32767));
+        let deepest = (0..MAX_QUADTREE_DEPTH).fold(far_root, |id, _| child_node_ids(id)[3]);
+        assert!(
+            deepest
+                .checked_mul(4)
+                .and_then(|id| id.checked_add(4))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_wider_fov_selects_coarser_lod() {
+        let config = TerrainConfig::builder()
+            .lod_metric(LodMetric::ScreenSpaceError { pixel_error: 4.0 })
+            .build();
+        let camera_pos = Vec3::new(550.0, 0.0, 0.0);
+        let select_lod = |fov_degrees: f32| {
+            let bounds = Aabb2d::new(Vec2::ZERO, Vec2::splat(50.0));
+            let mut node = QuadtreeNode::new(1, bounds, 0, IVec2::ZERO);
+            let projection = LodProjection {
+                fov_y: fov_degrees.to_radians(),
+                viewport_height: 1080.0,
+            };
+            node.select_for_rendering(camera_pos, &config, |_, _| 0.0, 0, None, &[], &projection);
+            node.lod_level
+        };
+
+        let narrow = select_lod(30.0);
+        let wide = select_lod(100.0);
+        assert!(
+            wide > narrow,
+            "wide FOV lod {wide} should be coarser than {narrow}"
+        );
+
+        // A taller viewport needs finer detail at the same FOV and distance
+        let projection = LodProjection::default();
+        let short = LodProjection {
+            viewport_height: 480.0,
+            ..projection
+        };
+        assert!(short.projected_error(1.0, 500.0) < projection.projected_error(1.0, 500.0));
+    }
 }
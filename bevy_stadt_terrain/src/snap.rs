@@ -0,0 +1,239 @@
+//! Ground snapping for props placed on terrain
+//!
+//! Entities marked with [`SnapToTerrain`] are kept on the terrain surface when
+//! chunks re-mesh or the heightmap is replaced, so props don't float or sink
+//! after LOD changes and deformations.
+
+use crate::config::TerrainConfig;
+use crate::heightmap::HeightmapHandle;
+use crate::streaming::terrain_height_query;
+use crate::{Chunk, Terrain};
+use bevy::prelude::*;
+
+/// When a [`SnapToTerrain`] entity is re-snapped
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SnapMode {
+    /// Snap the first time the entity is seen, then remove the component
+    Once,
+    /// Snap when added, and again when chunks re-mesh or the heightmap changes
+    #[default]
+    OnChange,
+    /// Snap every time the terrain systems run (for moving entities)
+    Continuous,
+}
+
+/// Keep an entity on the terrain surface
+#[derive(Component, Clone, Debug, Default)]
+pub struct SnapToTerrain {
+    /// When to re-snap
+    pub mode: SnapMode,
+    /// Height above the surface to place the entity at
+    pub offset: f32,
+    /// Tilt the entity to follow the surface normal (keeping its yaw)
+    pub align_to_normal: bool,
+}
+
+impl SnapToTerrain {
+    /// Snap once at spawn and never again
+    pub fn once() -> Self {
+        Self {
+            mode: SnapMode::Once,
+            ..default()
+        }
+    }
+
+    /// Snap every frame
+    pub fn continuous() -> Self {
+        Self {
+            mode: SnapMode::Continuous,
+            ..default()
+        }
+    }
+
+    /// Set the height above the surface
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Tilt the entity to follow the surface normal
+    pub fn aligned(mut self) -> Self {
+        self.align_to_normal = true;
+        self
+    }
+}
+
+/// System: Move [`SnapToTerrain`] entities onto the current terrain surface
+///
+/// Heights are mapped into world space like [`TerrainHeightQuery`](crate::streaming::TerrainHeightQuery)
+/// does, so `base_elevation` and the terrain entity's transform are honored.
+#[allow(clippy::type_complexity)]
+pub fn snap_to_terrain(
+    mut commands: Commands,
+    config: Res<TerrainConfig>,
+    terrain_query: Query<(Option<Ref<HeightmapHandle>>, Option<&GlobalTransform>), With<Terrain>>,
+    new_chunks: Query<(), Added<Chunk>>,
+    mut snapped: Query<(Entity, Ref<SnapToTerrain>, &mut Transform, Option<&ChildOf>)>,
+    parents: Query<&GlobalTransform>,
+) {
+    let (heightmap, terrain_transform) = terrain_query.single().unwrap_or_default();
+    let terrain_changed =
+        heightmap.as_ref().is_some_and(|h| h.is_changed()) || !new_chunks.is_empty();
+    let query = terrain_height_query(
+        heightmap.as_deref(),
+        &config,
+        &terrain_transform.copied().unwrap_or_default(),
+    );
+
+    for (entity, snap, mut transform, parent) in &mut snapped {
+        let should_snap = match snap.mode {
+            SnapMode::Once => true,
+            SnapMode::OnChange => snap.is_added() || terrain_changed,
+            SnapMode::Continuous => true,
+        };
+        if !should_snap {
+            continue;
+        }
+
+        // Children are placed through their parent. The child's own global
+        // transform isn't propagated yet on the frame it spawns, so build the
+        // world position from the parent's instead.
+        let parent_global = parent.and_then(|parent| parents.get(parent.parent()).ok());
+        let world = parent_global.map_or(transform.translation, |global| {
+            global.transform_point(transform.translation)
+        });
+        let target = Vec3::new(
+            world.x,
+            query.get_height(world.x, world.z) + snap.offset,
+            world.z,
+        );
+        transform.translation = parent_global.map_or(target, |global| {
+            global.affine().inverse().transform_point3(target)
+        });
+
+        if snap.align_to_normal {
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            let normal = query.get_normal(world.x, world.z);
+            transform.rotation =
+                Quat::from_rotation_arc(Vec3::Y, normal) * Quat::from_rotation_y(yaw);
+        }
+
+        if snap.mode == SnapMode::Once {
+            commands.entity(entity).remove::<SnapToTerrain>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainBundle;
+
+    #[test]
+    fn test_snap_follows_deformed_terrain() {
+        let mut app = App::new();
+        app.insert_resource(TerrainConfig::default())
+            .add_systems(Update, snap_to_terrain);
+
+        let terrain = app
+            .world_mut()
+            .spawn(TerrainBundle::procedural(|_, _| 10.0))
+            .id();
+        let prop = app
+            .world_mut()
+            .spawn((
+                SnapToTerrain::default().with_offset(1.0),
+                Transform::from_xyz(5.0, 0.0, 5.0),
+            ))
+            .id();
+        let once = app
+            .world_mut()
+            .spawn((SnapToTerrain::once(), Transform::from_xyz(-5.0, 0.0, 5.0)))
+            .id();
+
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(prop).unwrap().translation.y,
+            11.0
+        );
+        assert_eq!(
+            app.world().get::<Transform>(once).unwrap().translation.y,
+            10.0
+        );
+        assert!(app.world().get::<SnapToTerrain>(once).is_none());
+
+        // Deform the terrain under the props
+        app.world_mut()
+            .entity_mut(terrain)
+            .insert(HeightmapHandle::Procedural(std::sync::Arc::new(
+                crate::heightmap::ProceduralHeightmap::new(|x, _| 20.0 + x),
+            )));
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Transform>(prop).unwrap().translation.y,
+            26.0
+        );
+        assert_eq!(
+            app.world().get::<Transform>(once).unwrap().translation.y,
+            10.0
+        );
+    }
+
+    #[test]
+    fn test_snap_honors_base_elevation_and_terrain_transform() {
+        let mut app = App::new();
+        app.insert_resource(TerrainConfig::builder().base_elevation(50.0).build())
+            .add_systems(Update, snap_to_terrain);
+
+        let placement = Transform::from_xyz(100.0, 5.0, 0.0).with_scale(Vec3::new(2.0, 3.0, 2.0));
+        app.world_mut()
+            .spawn(TerrainBundle::procedural(|x, _| x))
+            .insert((placement, GlobalTransform::from(placement)));
+        let prop = app
+            .world_mut()
+            .spawn((
+                SnapToTerrain::default().with_offset(1.0),
+                Transform::from_xyz(120.0, 0.0, 0.0),
+            ))
+            .id();
+
+        app.update();
+        // Local x = (120 - 100) / 2 = 10, so (10 + 50) * 3 + 5
+        assert_eq!(
+            app.world().get::<Transform>(prop).unwrap().translation.y,
+            186.0
+        );
+    }
+
+    #[test]
+    fn test_snap_parented_prop_on_spawn_frame() {
+        let mut app = App::new();
+        app.add_plugins(TransformPlugin)
+            .insert_resource(TerrainConfig::default())
+            .add_systems(Update, snap_to_terrain);
+        app.world_mut()
+            .spawn(TerrainBundle::procedural(|x, z| x + 2.0 * z));
+        let parent = app
+            .world_mut()
+            .spawn(Transform::from_xyz(100.0, 50.0, 10.0).with_scale(Vec3::splat(2.0)))
+            .id();
+        app.update();
+
+        // The child's global transform is still the identity when it is snapped
+        let prop = app
+            .world_mut()
+            .spawn((
+                SnapToTerrain::once().with_offset(1.0),
+                Transform::from_xyz(5.0, 0.0, -2.0),
+                ChildOf(parent),
+            ))
+            .id();
+        app.update();
+
+        // World (110, _, 6) sits on height 110 + 12, plus the offset
+        let global = app.world().get::<GlobalTransform>(prop).unwrap();
+        assert_eq!(global.translation(), Vec3::new(110.0, 123.0, 6.0));
+        assert!(app.world().get::<SnapToTerrain>(prop).is_none());
+    }
+}
@@ -3,19 +3,38 @@
 //! Manages the asynchronous generation of terrain meshes using Bevy's
 //! AsyncComputeTaskPool. Uses a priority queue to ensure nearby chunks
 //! are generated first.
+//!
+//! A node that stays selected but moves to another LOD is re-meshed in place:
+//! its chunk keeps showing until the replacement spawns, which then despawns
+//! it and sends [`TerrainChunkDespawned`] for the old entity.
 
-use crate::config::TerrainConfig;
-use crate::heightmap::{HeightmapHandle, TerrainNoise, sample_terrain_height};
-use crate::material::TerrainMaterialHandle;
-use crate::mesh::generate_chunk_mesh;
-use crate::quadtree::TerrainQuadtree;
-use crate::{Chunk, Terrain};
-use bevy::math::bounding::BoundingVolume;
+use crate::config::{GenerationMode, TerrainConfig};
+use crate::heightmap::{
+    HeightmapHandle, HeightmapSource, TerrainNoise, closest_surface_point, exaggerate,
+    raycast_heightfield, sample_terrain_height,
+};
+use crate::material::{ATTRIBUTE_QUANTIZED_POSITION, TerrainMaterial, TerrainMaterialHandle};
+use crate::mesh::{
+    Biome, ChunkSamples, GeneratedChunk, HoleMask, MeshGenOptions, PositionQuantization,
+    classify_biome, generate_chunk_mesh_from_source_with_samples, generate_chunk_mesh_with_samples,
+    quantize_mesh_positions,
+};
+use crate::placement::{ChunkFeatureSpawner, ChunkRng};
+use crate::quadtree::{
+    MAX_QUADTREE_DEPTH, SelectedNodeIndex, TerrainQuadtree, child_node_ids, parent_node_id,
+};
+use crate::{Chunk, Terrain, TerrainCamera};
+use bevy::asset::RenderAssetUsages;
+use bevy::camera::primitives::Aabb;
+use bevy::ecs::system::EntityCommands;
+use bevy::math::bounding::{Aabb2d, Aabb3d, BoundingCircle, BoundingVolume, IntersectsVolume};
+use bevy::mesh::{MeshTag, PrimitiveTopology};
 use bevy::prelude::*;
 use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use smallvec::SmallVec;
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
-use std::sync::Arc;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
 
 /// Request to generate a terrain mesh
 #[derive(Clone, Debug)]
@@ -27,15 +46,19 @@ pub struct MeshRequest {
     pub size: f32,
     /// LOD level for this mesh
     pub lod: u8,
-    /// Priority (lower = higher priority, based on distance)
+    /// Mesh subdivisions along each side
+    pub subdivisions: u32,
+    /// Priority (lower = higher priority): distance weighted by `lod_priority_bias`
     pub priority: f32,
     /// Grid coordinates
     pub coords: IVec2,
+    /// Edge levels toward coarser neighbors (see [`MeshGenOptions::neighbor_lods`])
+    pub neighbor_lods: [u8; 4],
 }
 
 impl PartialEq for MeshRequest {
     fn eq(&self, other: &Self) -> bool {
-        self.node_id == other.node_id
+        self.cmp(other) == std::cmp::Ordering::Equal
     }
 }
 
@@ -49,13 +72,27 @@ impl PartialOrd for MeshRequest {
 
 impl Ord for MeshRequest {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Compare by priority (lower is better, so we reverse)
+        // Compare by priority (lower is better, so we reverse); ties break on
+        // LOD and node ID so equidistant chunks always stream in the same order
         self.priority
-            .partial_cmp(&other.priority)
-            .unwrap_or(std::cmp::Ordering::Equal)
+            .total_cmp(&other.priority)
+            .then(self.lod.cmp(&other.lod))
+            .then(self.node_id.cmp(&other.node_id))
     }
 }
 
+/// Deferred insertion of analyzer output onto a spawned chunk entity
+pub type ChunkDataInserter = Box<dyn FnOnce(&mut EntityCommands) + Send + Sync>;
+
+/// Type-erased chunk analyzer run inside the mesh generation task
+pub type ChunkAnalyzerFn = Arc<dyn Fn(&ChunkSamples) -> ChunkDataInserter + Send + Sync>;
+
+/// Per-chunk data computed by a chunk analyzer during generation
+///
+/// Inserted on the chunk entity alongside [`Chunk`] when it spawns.
+#[derive(Component, Clone, Debug)]
+pub struct ChunkData<T>(pub T);
+
 /// Result of mesh generation
 pub struct MeshResult {
     pub node_id: u64,
@@ -63,44 +100,613 @@ pub struct MeshResult {
     pub center: Vec2,
     pub lod: u8,
     pub coords: IVec2,
+    /// World-space side length of the chunk
+    pub size: f32,
+    /// Mesh subdivisions along each side
+    pub subdivisions: u32,
+    /// Vertical extent of the mesh, for the chunk's culling bounds
+    pub min_y: f32,
+    pub max_y: f32,
+    /// Edge stitch levels the mesh was generated with
+    pub neighbor_lods: [u8; 4],
+    /// Analyzer output to attach to the chunk entity
+    pub chunk_data: Option<ChunkDataInserter>,
+    /// Reconstruction parameters when the mesh positions were quantized
+    pub quantization: Option<PositionQuantization>,
+    /// Already uploaded mesh from the [`MeshCache`]; `mesh` is empty when set
+    pub cached_mesh: Option<Handle<Mesh>>,
+    /// Height grid the mesh was built from, for [`TerrainMeshHeights`]
+    pub samples: Option<Arc<ChunkSamples>>,
+}
+
+/// A chunk mesh kept in the [`MeshCache`] with what's needed to spawn it again
+#[derive(Clone, Debug)]
+pub struct CachedMesh {
+    pub handle: Handle<Mesh>,
+    pub center: Vec2,
+    pub size: f32,
+    pub subdivisions: u32,
+    pub neighbor_lods: [u8; 4],
+    pub min_y: f32,
+    pub max_y: f32,
+    pub quantization: Option<PositionQuantization>,
+    pub samples: Option<Arc<ChunkSamples>>,
+}
+
+impl CachedMesh {
+    /// Whether this mesh was built for the same chunk geometry as `request`
+    fn matches(&self, request: &MeshRequest) -> bool {
+        self.center == request.center
+            && self.size == request.size
+            && self.subdivisions == request.subdivisions
+            && self.neighbor_lods == request.neighbor_lods
+    }
+}
+
+/// Least-recently-used chunk meshes keyed by `(coords, lod)`
+///
+/// Chunks hovering around a LOD boundary switch back and forth between the
+/// same meshes; the cache hands the previous mesh back instead of generating
+/// it again. Entries hold strong handles, so evicted meshes leave
+/// `Assets<Mesh>` once no chunk entity uses them anymore.
+#[derive(Default)]
+pub struct MeshCache {
+    entries: HashMap<(IVec2, u8), CachedMesh>,
+    /// Keys from least to most recently used
+    recency: VecDeque<(IVec2, u8)>,
+}
+
+impl MeshCache {
+    /// Cached mesh for `(coords, lod)`, marking it as recently used
+    pub fn get(&mut self, coords: IVec2, lod: u8) -> Option<&CachedMesh> {
+        let key = (coords, lod);
+        if self.entries.contains_key(&key) {
+            self.touch(key);
+        }
+        self.entries.get(&key)
+    }
+
+    /// Store a mesh, evicting the least recently used ones beyond `capacity`
+    pub fn insert(&mut self, coords: IVec2, lod: u8, mesh: CachedMesh, capacity: usize) {
+        let key = (coords, lod);
+        self.entries.insert(key, mesh);
+        self.touch(key);
+        while self.entries.len() > capacity {
+            let Some(oldest) = self.recency.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// Drop cached meshes of chunks overlapping `area`
+    pub fn invalidate_area(&mut self, area: BoundingCircle) {
+        self.entries.retain(|_, mesh| {
+            !Aabb2d::new(mesh.center, Vec2::splat(mesh.size / 2.0)).intersects(&area)
+        });
+        self.recency.retain(|key| self.entries.contains_key(key));
+    }
+
+    /// Drop the cached mesh for `(coords, lod)`
+    pub fn remove(&mut self, coords: IVec2, lod: u8) {
+        let key = (coords, lod);
+        if self.entries.remove(&key).is_some() {
+            self.recency.retain(|k| *k != key);
+        }
+    }
+
+    /// Drop all cached meshes
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn touch(&mut self, key: (IVec2, u8)) {
+        self.recency.retain(|k| *k != key);
+        self.recency.push_back(key);
+    }
+}
+
+/// Streaming queue depths for debug HUDs and health checks
+///
+/// Updated at the end of the streaming systems each run. `in_flight` staying
+/// above zero while `meshes_generated_total` stops growing means mesh tasks
+/// are stuck, e.g. a deadlocked task pool.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct TerrainStreamingMetrics {
+    /// Mesh requests waiting for a task slot
+    pub pending: usize,
+    /// Mesh tasks currently running
+    pub in_flight: usize,
+    /// Mesh tasks that finished during the last run
+    pub completed_this_frame: usize,
+    /// Chunks with a live entity
+    pub spawned: usize,
+    /// Meshes generated since startup
+    pub meshes_generated_total: u64,
+}
+
+/// Message asking the streaming systems to pre-generate chunks around a point
+///
+/// See [`TerrainStreaming::request_warmup`].
+#[derive(Message, Clone, Debug)]
+pub struct WarmupRequest {
+    /// World-space XZ center of the area
+    pub center: Vec2,
+    /// Radius of the area in world units
+    pub radius: f32,
+}
+
+/// Message written after the heightmap changed inside an area
+///
+/// Returned by [`EditableHeightmap::apply_brush`](crate::edit::EditableHeightmap::apply_brush);
+/// selected chunks overlapping the area are re-meshed in place.
+#[derive(Message, Clone, Debug)]
+pub struct TerrainEdited {
+    /// Terrain-local XZ center of the edited area
+    pub center: Vec2,
+    /// Radius of the edited area in world units
+    pub radius: f32,
+}
+
+/// Message asking the streaming systems to re-mesh one chunk from scratch
+///
+/// The chunk's entity is despawned right away and a new mesh is queued at
+/// the chunk's current LOD, ahead of camera-driven requests. Nodes without a
+/// spawned chunk, or no longer in the quadtree, are ignored.
+#[derive(Message, Clone, Debug)]
+pub struct RegenerateChunk {
+    pub node_id: u64,
+}
+
+/// Message written when a chunk entity is spawned
+///
+/// A refined or re-stitched chunk replaces the previous entity for the same
+/// node; its [`TerrainChunkDespawned`] is written first.
+#[derive(Message, Clone, Debug)]
+pub struct TerrainChunkSpawned {
+    pub entity: Entity,
+    pub node_id: u64,
+    pub coords: IVec2,
+    pub lod: u8,
+    /// World-space bounds of the chunk mesh
+    pub bounds: Aabb3d,
+}
+
+/// Message written when a chunk entity is despawned
+#[derive(Message, Clone, Debug)]
+pub struct TerrainChunkDespawned {
+    pub node_id: u64,
+    pub coords: IVec2,
+}
+
+/// Height grids of the spawned chunk meshes, keyed by quadtree cell
+///
+/// Maintained by [`spawn_chunk_entities`]. Clones share the same grids, so a
+/// [`TerrainHeightQuery::with_mesh_heights`] query stays current as chunks
+/// stream in and out. Each grid is placed where its chunk is drawn.
+#[derive(Resource, Clone, Default)]
+pub struct TerrainMeshHeights {
+    grids: Arc<RwLock<MeshHeightGrids>>,
+}
+
+/// Grids indexed like [`TerrainChunkIndex`]: by coordinates and side length
+#[derive(Default)]
+struct MeshHeightGrids {
+    /// Grids by side length (as bits) and grid coordinates
+    cells: HashMap<(u32, IVec2), ChunkSamples>,
+    /// Cell of each node's grid
+    nodes: HashMap<u64, (u32, IVec2)>,
+    /// Side lengths in use, finest first, with the coordinate offset of their cells
+    ///
+    /// Cells of a side length tile from the root corners, so a position's
+    /// coordinates are `floor(local / size + offset)`.
+    sizes: Vec<(f32, f32)>,
+}
+
+impl TerrainMeshHeights {
+    /// Surface height of the finest loaded chunk at a terrain-local XZ position
+    ///
+    /// Bilinearly interpolates the chunk's height grid; `None` where no chunk
+    /// is loaded. Only the cell containing the position is checked per
+    /// chunk size.
+    pub fn height_at(&self, local: Vec2) -> Option<f32> {
+        let grids = self.grids.read().unwrap();
+        let grid = grids.sizes.iter().find_map(|&(size, offset)| {
+            let coords = (local / size + Vec2::splat(offset)).floor().as_ivec2();
+            grids.cells.get(&(size.to_bits(), coords))
+        })?;
+
+        let last = grid.vertices_per_side - 1;
+        let cell = (local - grid.origin) / grid.step;
+        let x = (cell.x.floor() as u32).min(last.saturating_sub(1));
+        let z = (cell.y.floor() as u32).min(last.saturating_sub(1));
+        let (tx, tz) = (cell.x - x as f32, cell.y - z as f32);
+        let x1 = (x + 1).min(last);
+        let z1 = (z + 1).min(last);
+        let top = grid.height(x, z).lerp(grid.height(x1, z), tx);
+        let bottom = grid.height(x, z1).lerp(grid.height(x1, z1), tx);
+        Some(top.lerp(bottom, tz))
+    }
+
+    /// Number of chunks with a height grid
+    pub fn len(&self) -> usize {
+        self.grids.read().unwrap().nodes.len()
+    }
+
+    /// Whether no chunk has a height grid
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Store a chunk's grid, moved from its sampling origin to where it is drawn
+    fn insert(&self, node_id: u64, center: Vec2, size: f32, samples: &ChunkSamples) {
+        let origin = center - Vec2::splat(size / 2.0);
+        let mut grids = self.grids.write().unwrap();
+        grids.remove(node_id);
+        if !grids.sizes.iter().any(|&(known, _)| known == size) {
+            let offset = samples.coords.x as f32 - origin.x / size;
+            grids.sizes.push((size, offset));
+            grids.sizes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+
+        let key = (size.to_bits(), samples.coords);
+        let grid = ChunkSamples {
+            origin,
+            ..samples.clone()
+        };
+        grids.nodes.insert(node_id, key);
+        grids.cells.insert(key, grid);
+    }
+
+    fn remove(&self, node_id: u64) {
+        self.grids.write().unwrap().remove(node_id);
+    }
+}
+
+impl MeshHeightGrids {
+    fn remove(&mut self, node_id: u64) {
+        if let Some(key) = self.nodes.remove(&node_id) {
+            self.cells.remove(&key);
+        }
+    }
+}
+
+/// Spawned chunk entities by chunk grid coordinates
+///
+/// Maintained by [`spawn_chunk_entities`]. Coordinates are only unique per
+/// quadtree depth, so a key can hold chunks of several sizes.
+#[derive(Resource, Default)]
+pub struct TerrainChunkIndex {
+    chunks: HashMap<IVec2, SmallVec<[(Entity, f32); 2]>>,
+}
+
+impl TerrainChunkIndex {
+    /// Spawned chunks with the given grid coordinates, with their side lengths
+    pub fn get(&self, coords: IVec2) -> impl Iterator<Item = (Entity, f32)> + '_ {
+        self.chunks.get(&coords).into_iter().flatten().copied()
+    }
+
+    /// Finest spawned chunk containing a terrain-local XZ position
+    ///
+    /// `root_size` is the side length of the coarsest chunks, i.e. the
+    /// quadtree's [`root_size`](TerrainQuadtree::root_size). While a LOD
+    /// transition keeps both a parent and its children alive, the smaller
+    /// child chunk wins.
+    pub fn chunk_at(&self, local_pos: Vec2, root_size: f32) -> Option<Entity> {
+        (0..=MAX_QUADTREE_DEPTH).rev().find_map(|depth| {
+            let cells = (1u64 << depth) as f32;
+            let size = root_size / cells;
+            // Roots are centered on multiples of their size, children tile them from the corner
+            let coords = (local_pos / size + Vec2::splat(cells * 0.5))
+                .floor()
+                .as_ivec2();
+            self.get(coords)
+                .find(|&(_, chunk)| (chunk - size).abs() <= size * 1e-3)
+                .map(|(entity, _)| entity)
+        })
+    }
+
+    /// Number of indexed chunk entities
+    pub fn len(&self) -> usize {
+        self.chunks.values().map(SmallVec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    fn insert(&mut self, coords: IVec2, size: f32, entity: Entity) {
+        self.chunks.entry(coords).or_default().push((entity, size));
+    }
+
+    fn remove(&mut self, coords: IVec2, entity: Entity) {
+        if let Some(chunks) = self.chunks.get_mut(&coords) {
+            chunks.retain(|(chunk, _)| *chunk != entity);
+            if chunks.is_empty() {
+                self.chunks.remove(&coords);
+            }
+        }
+    }
+}
+
+/// A mesh generation task together with its projected vertex count
+struct InFlightMesh {
+    task: Task<MeshResult>,
+    vertices: usize,
 }
 
 /// Resource managing terrain chunk streaming
 #[derive(Resource, Default)]
 pub struct TerrainStreaming {
     /// Priority queue of pending mesh requests
-    pub pending: BinaryHeap<Reverse<MeshRequest>>,
+    pending: BinaryHeap<Reverse<MeshRequest>>,
+    /// Node IDs with a request in `pending`, so duplicate checks don't scan the heap
+    queued: HashSet<u64>,
     /// Currently in-flight mesh generation tasks
-    pub in_flight: HashMap<u64, Task<MeshResult>>,
+    in_flight: HashMap<u64, InFlightMesh>,
     /// Completed mesh results ready to be spawned
     pub completed: Vec<MeshResult>,
     /// Set of node IDs that already have entities
     pub spawned: HashMap<u64, Entity>,
     /// Parent node IDs waiting for their children to be spawned (subdivision case)
     /// Maps parent_id -> set of child_ids that need to be ready before despawning parent
-    pub waiting_for_children: HashMap<u64, std::collections::HashSet<u64>>,
+    pub waiting_for_children: HashMap<u64, HashSet<u64>>,
     /// Child node IDs waiting for their parent to be spawned (merge case)
     /// Maps child_id -> parent_id that needs to be ready before despawning child
     pub waiting_for_parent: HashMap<u64, u64>,
+    /// Optional analyzer deriving per-chunk data from the generated height samples
+    pub chunk_analyzer: Option<ChunkAnalyzerFn>,
+    /// Extra mesh generation options (e.g. a custom vertex color function)
+    pub mesh_options: MeshGenOptions,
+    /// Nodes streamed at a coarse LOD first, mapped to the LOD they refine to
+    pub refinements: HashMap<u64, u8>,
+    /// Edge stitch levels each spawned chunk was meshed with
+    pub seams: HashMap<u64, [u8; 4]>,
+    /// Node IDs selected by the last quadtree update (None = not tracked yet)
+    ///
+    /// In-flight tasks for nodes outside this set are cancelled.
+    pub selected_nodes: Option<HashSet<u64>>,
+    /// Nodes queued by [`Self::request_warmup`] that the camera hasn't selected yet,
+    /// with the seconds left before they expire (see `warmup_ttl_secs`)
+    ///
+    /// These are neither cancelled nor despawned while unselected, and their
+    /// chunks stay hidden until the camera selects them; remove entries to
+    /// let them unload early.
+    pub warmup: HashMap<u64, f32>,
+    /// Warmed nodes selected since the last spawn pass, whose chunks are shown again
+    revealed_warmup: Vec<u64>,
+    /// Spawned nodes meshed with an outdated config, re-meshed in place
+    pub stale: HashSet<u64>,
+    /// Recent chunk meshes reused instead of regenerating them
+    pub mesh_cache: MeshCache,
+    /// Meshes generated inline since the last poll (see [`GenerationMode::Synchronous`])
+    generated_inline: usize,
+    /// Seconds each out-of-view chunk has lingered (see `chunk_despawn_delay_secs`)
+    pub pending_despawn: HashMap<u64, f32>,
+    /// Selected nodes within `ready_radius` of the camera (None = no selection pass yet)
+    ready_nodes: Option<Vec<u64>>,
+    /// LOD and subdivisions each spawned chunk was meshed at
+    spawned_detail: HashMap<u64, (u8, u32)>,
+    /// Decoration hooks run on every newly spawned chunk
+    pub feature_spawners: Vec<Arc<dyn ChunkFeatureSpawner>>,
 }
 
 impl TerrainStreaming {
-    /// Queue a mesh request
-    pub fn queue_request(&mut self, request: MeshRequest) {
-        // Don't queue if already spawned or in flight
-        if !self.spawned.contains_key(&request.node_id)
-            && !self.in_flight.contains_key(&request.node_id)
+    /// Compute per-chunk data during generation and store it as [`ChunkData<T>`]
+    ///
+    /// The analyzer runs inside the async mesh task on the heights that were
+    /// already sampled for the mesh, so no extra sampling pass is needed.
+    pub fn set_chunk_analyzer<T, F>(&mut self, analyzer: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&ChunkSamples) -> T + Send + Sync + 'static,
+    {
+        self.chunk_analyzer = Some(Arc::new(move |samples: &ChunkSamples| {
+            let data = analyzer(samples);
+            Box::new(move |entity: &mut EntityCommands| {
+                entity.insert(ChunkData(data));
+            }) as ChunkDataInserter
+        }));
+    }
+
+    /// Run `spawner` on every chunk spawned from now on
+    ///
+    /// Spawners run in registration order, each with a fresh [`ChunkRng`] for
+    /// the chunk, so adding a spawner never shifts another one's placements.
+    pub fn add_feature_spawner(&mut self, spawner: impl ChunkFeatureSpawner) {
+        self.feature_spawners.push(Arc::new(spawner));
+    }
+
+    /// Whether every node near the camera has a chunk (see [`terrain_ready`])
+    pub fn is_ready(&self) -> bool {
+        let Some(nodes) = &self.ready_nodes else {
+            return false;
+        };
+        // Seam and LOD re-meshes still change what's on screen
+        let remeshing: HashSet<u64> = self
+            .pending_iter()
+            .map(|(id, ..)| id)
+            .chain(self.in_flight.keys().copied())
+            .chain(self.completed.iter().map(|result| result.node_id))
+            .collect();
+        nodes.iter().all(|id| {
+            self.spawned.contains_key(id)
+                && !self.refinements.contains_key(id)
+                && !remeshing.contains(id)
+        })
+    }
+
+    /// Terrain-local XZ bounds of every spawned chunk, joined through [`TerrainQuadtree::find_node`]
+    ///
+    /// Chunks whose node has already left the quadtree are skipped.
+    pub fn spawned_bounds<'a>(
+        &'a self,
+        quadtree: &'a TerrainQuadtree,
+    ) -> impl Iterator<Item = (u64, Aabb2d)> + 'a {
+        self.spawned
+            .keys()
+            .filter_map(|&id| quadtree.find_node(id).map(|node| (id, node.bounds)))
+    }
+
+    /// Iterate queued requests as `(node_id, coords, lod, priority)`, in no particular order
+    pub fn pending_iter(&self) -> impl Iterator<Item = (u64, IVec2, u8, f32)> + '_ {
+        self.pending
+            .iter()
+            .map(|Reverse(r)| (r.node_id, r.coords, r.lod, r.priority))
+    }
+
+    /// Iterate node IDs whose meshes are currently being generated
+    pub fn in_flight_node_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.in_flight.keys().copied()
+    }
+
+    /// Projected vertex total of all in-flight mesh tasks
+    pub fn in_flight_vertices(&self) -> usize {
+        self.in_flight.values().map(|mesh| mesh.vertices).sum()
+    }
+
+    /// Queue a re-mesh of an already spawned node (the old chunk is replaced on completion)
+    pub fn queue_refinement(&mut self, request: MeshRequest) {
+        if !self.is_queued(request.node_id) {
+            self.push_pending(request);
+        }
+    }
+
+    /// Whether a mesh for `node_id` is pending, in flight, or waiting to spawn
+    ///
+    /// Results held back by `max_spawns_per_frame` count too, so they aren't
+    /// generated a second time while they wait.
+    fn is_queued(&self, node_id: u64) -> bool {
+        self.queued.contains(&node_id)
+            || self.in_flight.contains_key(&node_id)
+            || self.completed.iter().any(|r| r.node_id == node_id)
+    }
+
+    fn push_pending(&mut self, request: MeshRequest) {
+        self.queued.insert(request.node_id);
+        self.pending.push(Reverse(request));
+    }
+
+    fn pop_pending(&mut self) -> Option<MeshRequest> {
+        let Reverse(request) = self.pending.pop()?;
+        self.queued.remove(&request.node_id);
+        Some(request)
+    }
+
+    /// Queue meshes for the nodes within `radius` of `center` ahead of the camera
+    ///
+    /// Call before teleporting the camera to hide pop-in while the quadtree
+    /// catches up. Nodes and LODs are chosen by a scratch quadtree with the
+    /// camera at ground level over `center`, so they match what the live
+    /// quadtree selects on arrival.
+    pub fn request_warmup(&mut self, center: Vec2, radius: f32, config: &TerrainConfig) {
+        let mut config = config.clone();
+        config.render_distance = config
+            .render_distance
+            .max((radius / config.chunk_size).ceil() as i32);
+
+        let camera_pos = Vec3::new(center.x, 0.0, center.y);
+        let mut quadtree = TerrainQuadtree::from_config(&config);
+        quadtree.update(camera_pos, &config, |_, _| 0.0);
+
+        let area = BoundingCircle::new(center, radius);
+        let selected = quadtree.collect_selected_nodes();
+        let index = SelectedNodeIndex::new(&selected);
+        for node in selected.iter().filter(|node| node.bounds.intersects(&area)) {
+            let neighbor_lods = node.neighbor_lods(&index, &config);
+
+            // Nodes the camera already selects stream and show as usual
+            if !self
+                .selected_nodes
+                .as_ref()
+                .is_some_and(|selected| selected.contains(&node.id))
+            {
+                self.warmup.insert(node.id, config.warmup_ttl_secs);
+            }
+            self.queue_request(MeshRequest {
+                node_id: node.id,
+                center: node.bounds.center(),
+                size: node.bounds.half_size().x * 2.0,
+                lod: node.lod_level,
+                subdivisions: node.subdivisions(&config),
+                priority: center.distance(node.bounds.center())
+                    * config.lod_priority_weight(node.lod_level),
+                coords: node.coords,
+                neighbor_lods,
+            });
+        }
+    }
+
+    /// Replace the holes cut out of the terrain and re-mesh every chunk
+    pub fn set_hole_mask(&mut self, holes: HoleMask) {
+        self.mesh_options.holes = holes;
+        self.rebuild_all();
+    }
+
+    /// Re-mesh every spawned chunk, e.g. after the config changed
+    ///
+    /// Queued and in-flight work is dropped since it was set up with the old
+    /// settings. Spawned chunks stay visible until their replacement is ready.
+    pub fn rebuild_all(&mut self) {
+        self.pending.clear();
+        self.queued.clear();
+        self.in_flight.clear();
+        self.completed.clear();
+        self.mesh_cache.clear();
+        self.stale = self.spawned.keys().copied().collect();
+    }
+
+    /// Re-mesh the selected chunks overlapping `area` after their heights changed
+    ///
+    /// Meshes already in flight for those nodes may have sampled the old
+    /// heights, so they are dropped and requested again.
+    pub fn rebuild_area(&mut self, area: BoundingCircle, quadtree: &TerrainQuadtree) {
+        self.mesh_cache.invalidate_area(area);
+        for node in quadtree
+            .collect_selected_nodes()
+            .iter()
+            .filter(|node| node.bounds.intersects(&area))
         {
-            // Check if not already in pending queue
-            let already_pending = self
-                .pending
-                .iter()
-                .any(|Reverse(r)| r.node_id == request.node_id);
-            if !already_pending {
-                self.pending.push(Reverse(request));
+            self.in_flight.remove(&node.id);
+            self.completed.retain(|result| result.node_id != node.id);
+            if self.spawned.contains_key(&node.id) {
+                self.stale.insert(node.id);
             }
         }
     }
+
+    /// Queue a mesh request
+    pub fn queue_request(&mut self, request: MeshRequest) {
+        // Don't queue if already spawned or on its way
+        if !self.spawned.contains_key(&request.node_id) && !self.is_queued(request.node_id) {
+            self.push_pending(request);
+        }
+    }
+}
+
+/// Map a terrain-local height into world space
+///
+/// Raises it by `base_elevation`, then applies the vertical scale and
+/// translation of the terrain entity's `transform`, if any.
+pub fn local_to_world_height(
+    height: f32,
+    base_elevation: f32,
+    transform: Option<&Transform>,
+) -> f32 {
+    let height = height + base_elevation;
+    match transform {
+        Some(t) => height * t.scale.y + t.translation.y,
+        None => height,
+    }
 }
 
 /// Resource for querying terrain height at any world position
@@ -108,6 +714,20 @@ impl TerrainStreaming {
 pub struct TerrainHeightQuery {
     noise: Arc<TerrainNoise>,
     config: TerrainConfig,
+    layers: Vec<HeightLayer>,
+    /// Replaces the noise as the base surface for closure- and image-based terrain
+    source: Option<Arc<dyn HeightmapSource>>,
+    /// Placement of the terrain entity; `None` samples in raw world coordinates
+    transform: Option<Transform>,
+    /// Height grids of the loaded chunk meshes
+    mesh_heights: Option<TerrainMeshHeights>,
+}
+
+/// An additional terrain surface stacked at a vertical offset
+struct HeightLayer {
+    noise: Arc<TerrainNoise>,
+    config: TerrainConfig,
+    offset: f32,
 }
 
 impl TerrainHeightQuery {
@@ -115,17 +735,203 @@ impl TerrainHeightQuery {
         Self {
             noise: Arc::new(noise),
             config,
+            layers: Vec::new(),
+            source: None,
+            transform: None,
+            mesh_heights: None,
+        }
+    }
+
+    /// Match queries to a terrain entity placed with `transform`
+    ///
+    /// Translation and scale are applied; rotation is ignored since a rotated
+    /// heightfield is no longer a function of (x, z).
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Answer [`Self::get_height_from_mesh`] from the loaded chunk meshes
+    pub fn with_mesh_heights(mut self, mesh_heights: TerrainMeshHeights) -> Self {
+        self.mesh_heights = Some(mesh_heights);
+        self
+    }
+
+    /// Map world (x, z) into the terrain's local sampling space
+    fn to_local(&self, x: f32, z: f32) -> Vec2 {
+        match &self.transform {
+            Some(t) => (Vec2::new(x, z) - t.translation.xz()) / t.scale.xz(),
+            None => Vec2::new(x, z),
+        }
+    }
+
+    /// Map a local surface height back into world space
+    fn to_world_height(&self, height: f32) -> f32 {
+        local_to_world_height(height, self.config.base_elevation, self.transform.as_ref())
+    }
+
+    /// Query terrain whose base surface comes from any height source (closures, images)
+    ///
+    /// Sources carry no moisture noise, so [`Self::get_moisture`] returns a neutral 0.5.
+    pub fn from_source(source: impl HeightmapSource, config: TerrainConfig) -> Self {
+        Self {
+            source: Some(exaggerate(Arc::new(source), config.vertical_exaggeration)),
+            ..Self::new(TerrainNoise::default(), config)
         }
     }
 
+    /// Register an additional terrain layer raised by `offset` (e.g. a floating island layer)
+    pub fn add_layer(&mut self, noise: TerrainNoise, config: TerrainConfig, offset: f32) {
+        self.layers.push(HeightLayer {
+            noise: Arc::new(noise),
+            config,
+            offset,
+        });
+    }
+
+    /// Builder-style variant of [`Self::add_layer`]
+    pub fn with_layer(mut self, noise: TerrainNoise, config: TerrainConfig, offset: f32) -> Self {
+        self.add_layer(noise, config, offset);
+        self
+    }
+
     /// Get terrain height at world position
+    ///
+    /// Outside [`TerrainConfig::world_bounds`] this is the configured `world_floor`.
     pub fn get_height(&self, x: f32, z: f32) -> f32 {
-        sample_terrain_height(x, z, &self.noise, &self.config)
+        let local = self.to_local(x, z);
+        self.to_world_height(self.local_height(local))
+    }
+
+    /// Terrain height at world position as the loaded chunk meshes show it
+    ///
+    /// Interpolates the heights the meshes were built from, so edits made
+    /// to the height source after this query was created still line up with
+    /// what's rendered. Falls back to [`Self::get_height`] where no chunk is
+    /// loaded or no [`TerrainMeshHeights`] were given.
+    pub fn get_height_from_mesh(&self, x: f32, z: f32) -> f32 {
+        let local = self.to_local(x, z);
+        match self
+            .mesh_heights
+            .as_ref()
+            .and_then(|mesh_heights| mesh_heights.height_at(local))
+        {
+            Some(height) => self.to_world_height(height),
+            None => self.get_height(x, z),
+        }
+    }
+
+    /// Base surface height in terrain-local space
+    fn local_height(&self, local: Vec2) -> f32 {
+        if self.out_of_bounds(local) {
+            return self.config.world_floor;
+        }
+        match &self.source {
+            Some(source) => source.sample(local.x, local.y),
+            None => sample_terrain_height(local.x, local.y, &self.noise, &self.config),
+        }
+    }
+
+    /// Whether a terrain-local position lies outside the configured world bounds
+    fn out_of_bounds(&self, local: Vec2) -> bool {
+        self.config
+            .world_bounds
+            .is_some_and(|bounds| !bounds.contains(local))
+    }
+
+    /// Get terrain heights at many (x, z) world positions
+    pub fn get_heights(&self, points: &[Vec2]) -> Vec<f32> {
+        let mut heights = Vec::with_capacity(points.len());
+        self.sample_many(points, &mut heights);
+        heights
+    }
+
+    /// Get every surface height along the vertical line at (x, z), sorted top to bottom
+    ///
+    /// Includes the base terrain and all registered layers. For a single solid
+    /// terrain this returns exactly one height.
+    pub fn heights_at(&self, x: f32, z: f32) -> SmallVec<[f32; 4]> {
+        let mut heights: SmallVec<[f32; 4]> = SmallVec::new();
+        heights.push(self.get_height(x, z));
+        let local = self.to_local(x, z);
+        if self.out_of_bounds(local) {
+            return heights;
+        }
+        for layer in &self.layers {
+            let height = sample_terrain_height(local.x, local.y, &layer.noise, &layer.config);
+            heights.push(self.to_world_height(height + layer.offset));
+        }
+        heights.sort_by(|a, b| b.total_cmp(a));
+        heights
+    }
+
+    /// World-space height of the water surface
+    ///
+    /// Terrain heights are shifted so `water_level` sits at local `y = 0`.
+    pub fn water_surface_y(&self) -> f32 {
+        self.to_world_height(0.0)
+    }
+
+    /// Depth of water above the terrain at world position (0 where terrain is above water)
+    pub fn water_depth(&self, x: f32, z: f32) -> f32 {
+        (self.water_surface_y() - self.get_height(x, z)).max(0.0)
+    }
+
+    /// Moisture (0 = arid, 1 = wet) at world position, as used for biome colors
+    pub fn get_moisture(&self, x: f32, z: f32) -> f32 {
+        if self.source.is_some() {
+            return 0.5;
+        }
+        let p = self.to_local(x, z) + self.config.noise_origin;
+        self.noise.sample_moisture(p.x, p.y)
+    }
+
+    /// Same as [`Self::get_moisture`]
+    pub fn sample_moisture(&self, x: f32, z: f32) -> f32 {
+        self.get_moisture(x, z)
+    }
+
+    /// Biome the terrain's vertex colors show at world position
+    ///
+    /// Classifies height, moisture, and slope with the thresholds of the
+    /// configured [`BiomePalette`](crate::config::BiomePalette); see [`classify_biome`].
+    pub fn get_biome(&self, x: f32, z: f32) -> Biome {
+        let height = self.local_height(self.to_local(x, z));
+        classify_biome(
+            height,
+            self.get_moisture(x, z),
+            self.get_normal(x, z),
+            &self.config,
+        )
     }
 
     /// Get surface normal at world position
     pub fn get_normal(&self, x: f32, z: f32) -> Vec3 {
-        let step = 1.0;
+        self.get_normal_with_step(x, z, 1.0)
+    }
+
+    /// Get surface normal using central differences `step` units apart
+    ///
+    /// Larger steps average out small bumps when classifying large areas.
+    /// Height sources with an analytic normal (see
+    /// [`ProceduralHeightmap::new_with_normal`](crate::heightmap::ProceduralHeightmap::new_with_normal))
+    /// are asked directly instead.
+    pub fn get_normal_with_step(&self, x: f32, z: f32, step: f32) -> Vec3 {
+        let local = self.to_local(x, z);
+        if let Some(source) = &self.source
+            && !self.out_of_bounds(local)
+        {
+            let scale = self.transform.map_or(Vec3::ONE, |t| t.scale);
+            let normal = source.sample_normal(local.x, local.y, step / scale.x);
+            // Stretching the surface scales its slopes by height over width
+            return Vec3::new(
+                normal.x * scale.y / scale.x,
+                normal.y,
+                normal.z * scale.y / scale.z,
+            )
+            .normalize();
+        }
+
         let left = self.get_height(x - step, z);
         let right = self.get_height(x + step, z);
         let down = self.get_height(x, z - step);
@@ -137,6 +943,41 @@ impl TerrainHeightQuery {
         Vec3::new(-dx, 1.0, -dz).normalize()
     }
 
+    /// Slope at world position in degrees (0 = flat, 90 = vertical)
+    pub fn get_slope_degrees(&self, x: f32, z: f32) -> f32 {
+        self.get_slope_degrees_with_step(x, z, 1.0)
+    }
+
+    /// [`Self::get_slope_degrees`] with the normal's finite-difference step
+    pub fn get_slope_degrees_with_step(&self, x: f32, z: f32, step: f32) -> f32 {
+        self.get_normal_with_step(x, z, step)
+            .y
+            .clamp(-1.0, 1.0)
+            .acos()
+            .to_degrees()
+    }
+
+    /// Whether the ground at world position is flat enough to build on
+    pub fn is_buildable(&self, x: f32, z: f32, max_slope_deg: f32) -> bool {
+        self.get_slope_degrees(x, z) <= max_slope_deg
+    }
+
+    /// Approximate closest terrain surface point to `point` (e.g. for spring-arm cameras)
+    ///
+    /// Unlike sampling straight down, this finds cliff walls beside the point.
+    /// See [`closest_surface_point`] for the search and its limits.
+    pub fn closest_surface_point(&self, point: Vec3) -> Vec3 {
+        closest_surface_point(point, |x, z| self.get_height(x, z))
+    }
+
+    /// Raycast against terrain, e.g. for click-to-place from an angled camera
+    ///
+    /// Marches `step` units at a time along `dir` for up to `max_dist`; see
+    /// [`raycast_heightfield`] for precision and limits.
+    pub fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32, step: f32) -> Option<Vec3> {
+        raycast_heightfield(origin, dir, max_dist, step, |x, z| self.get_height(x, z))
+    }
+
     /// Simple raycast against terrain (vertical ray only for now)
     pub fn raycast_vertical(&self, x: f32, z: f32, max_height: f32) -> Option<Vec3> {
         let height = self.get_height(x, z);
@@ -148,75 +989,265 @@ impl TerrainHeightQuery {
     }
 }
 
+impl HeightmapSource for TerrainHeightQuery {
+    fn sample(&self, x: f32, z: f32) -> f32 {
+        self.get_height(x, z)
+    }
+}
+
+/// Priority of [`RegenerateChunk`] requests, ahead of boosted nodes in (-1, 0)
+const REGENERATE_PRIORITY: f32 = -1.0;
+
+/// Priority added to refinement requests so coarse first-pass chunks always stream first
+pub const REFINEMENT_PRIORITY_OFFSET: f32 = 1.0e6;
+
+/// System: Rebuild the terrain when [`TerrainConfig`] changes at runtime
+///
+/// Only a config that actually differs from the last applied one triggers a
+/// rebuild, so touching the resource without changing it is free.
+pub fn rebuild_on_config_change(
+    config: Res<TerrainConfig>,
+    mut applied: Local<Option<TerrainConfig>>,
+    mut quadtree: ResMut<TerrainQuadtree>,
+    mut streaming: ResMut<TerrainStreaming>,
+) {
+    if !config.is_changed() {
+        return;
+    }
+    let previous = applied.replace(config.clone());
+    if previous.is_none_or(|previous| previous == *config) {
+        return;
+    }
+
+    // More or fewer LOD distances move the depth of the finest LOD
+    quadtree.max_depth = TerrainQuadtree::depth_for(&config);
+
+    // Keep the tree (and its hysteresis state) and re-mesh what it selects in
+    // place; new LOD thresholds take effect as the nodes update
+    streaming.rebuild_all();
+}
+
+/// System: Re-mesh every chunk when the terrain's heightmap is replaced or edited
+///
+/// Cached meshes and queued work sampled the old heights, so they are dropped
+/// along with the rest of [`TerrainStreaming::rebuild_all`]. Heightmaps are
+/// plain components rather than assets, so reloading one from disk means
+/// inserting a new [`HeightmapHandle`], which is caught here too. A newly
+/// spawned terrain has nothing to re-mesh yet, but still drops meshes cached
+/// from the heights of a previous one.
+pub fn rebuild_on_heightmap_change(
+    heightmaps: Query<Ref<HeightmapHandle>, With<Terrain>>,
+    mut streaming: ResMut<TerrainStreaming>,
+) {
+    for heightmap in &heightmaps {
+        if heightmap.is_added() {
+            streaming.mesh_cache.clear();
+        } else if heightmap.is_changed() {
+            streaming.rebuild_all();
+            return;
+        }
+    }
+}
+
 /// System: Update the quadtree based on camera position
+///
+/// The camera tagged [`TerrainCamera`] drives selection; without a tag the
+/// only camera in the world is used.
 pub fn update_quadtree(
-    camera_query: Query<&Transform, With<Camera>>,
+    camera_query: Query<(&Transform, &Camera, Option<&Projection>, Has<TerrainCamera>)>,
     config: Res<TerrainConfig>,
     terrain_query: Query<&HeightmapHandle, With<Terrain>>,
+    terrain_transform_query: Query<&GlobalTransform, With<Terrain>>,
     mut quadtree: ResMut<TerrainQuadtree>,
     mut streaming: ResMut<TerrainStreaming>,
+    time: Res<Time>,
 ) {
-    let Ok(camera_transform) = camera_query.single() else {
+    let Some((camera_transform, camera, projection, _)) = camera_query
+        .iter()
+        .find(|(.., tagged)| *tagged)
+        .or_else(|| camera_query.single().ok())
+    else {
         return;
     };
 
-    let camera_pos = camera_transform.translation;
+    // Select and sample in terrain-local space so a moved terrain streams around the camera
+    let terrain_transform = terrain_transform_query
+        .single()
+        .copied()
+        .unwrap_or_default();
+    let camera_pos = terrain_transform
+        .affine()
+        .inverse()
+        .transform_point3(camera_transform.translation);
+
+    // Screen-space error LOD needs the camera's FOV and viewport height
+    if let Some(Projection::Perspective(perspective)) = projection {
+        quadtree.projection.fov_y = perspective.fov;
+    }
+    if let Some(viewport) = camera.physical_viewport_size() {
+        quadtree.projection.viewport_height = viewport.y as f32;
+    }
 
     // Get heightmap from terrain entity, or use default noise
     let default_noise = TerrainNoise::default();
     let default_config = TerrainConfig::default();
 
+    let heightmap = terrain_query.single().ok();
+    let source = heightmap.and_then(|heightmap| heightmap.exaggerated_source(&config));
     let height_sampler = |x: f32, z: f32| -> f32 {
-        if let Ok(heightmap) = terrain_query.single() {
-            heightmap.sample(x, z)
-        } else {
-            sample_terrain_height(x, z, &default_noise, &default_config)
+        match (&source, heightmap) {
+            (Some(source), _) => source.sample(x, z),
+            (None, Some(heightmap)) => heightmap.sample(x, z),
+            (None, None) => sample_terrain_height(x, z, &default_noise, &default_config),
         }
     };
 
     // Update quadtree
+    quadtree.tick_boosts(time.delta_secs());
     quadtree.update(camera_pos, &config, height_sampler);
 
     // Collect selected nodes and queue mesh requests
     let selected = quadtree.collect_selected_nodes();
+    let index = SelectedNodeIndex::new(&selected);
+
+    let coarsest_lod = (config.lod_subdivisions.len() - 1) as u8;
+
+    for node in &selected {
+        // Finer LODs rank as if they were closer, so visible detail streams first
+        let mut distance = Vec2::new(camera_pos.x, camera_pos.z).distance(node.bounds.center())
+            * config.lod_priority_weight(node.lod_level);
+        // Boosted nodes map into (-1, 0) so they stream ahead of everything else
+        if quadtree.boost_levels(&node.bounds) > 0 {
+            distance = -1.0 / (1.0 + distance);
+        }
+
+        let neighbor_lods = node.neighbor_lods(&index, &config);
+
+        let request = MeshRequest {
+            node_id: node.id,
+            center: node.bounds.center(),
+            size: node.bounds.half_size().x * 2.0,
+            lod: node.lod_level,
+            subdivisions: node.subdivisions(&config),
+            priority: distance,
+            coords: node.coords,
+            neighbor_lods,
+        };
 
-    for node in selected {
         // Check if we need to spawn this node
         if !streaming.spawned.contains_key(&node.id) {
-            let distance = Vec2::new(camera_pos.x, camera_pos.z).distance(node.bounds.center());
-
-            let request = MeshRequest {
-                node_id: node.id,
-                center: node.bounds.center(),
-                size: node.bounds.half_size().x * 2.0,
-                lod: node.lod_level,
-                priority: distance,
-                coords: node.coords,
+            if config.progressive_refinement && node.lod_level < coarsest_lod {
+                // Stream a cheap coarse mesh first and refine once it is visible
+                streaming.refinements.insert(node.id, node.lod_level);
+                streaming.queue_request(MeshRequest {
+                    lod: coarsest_lod,
+                    subdivisions: config.lod_subdivisions[coarsest_lod as usize],
+                    ..request
+                });
+            } else {
+                streaming.queue_request(request);
+            }
+        } else if streaming.refinements.contains_key(&node.id) && node.lod_level < coarsest_lod {
+            // Refinements queue behind every first-pass request and step one LOD
+            // finer than the chunk on screen, so detail arrives gradually
+            streaming.refinements.insert(node.id, node.lod_level);
+            let shown_lod = streaming
+                .spawned_detail
+                .get(&node.id)
+                .map_or(coarsest_lod, |&(lod, _)| lod);
+            let lod = shown_lod.saturating_sub(1).max(node.lod_level);
+            let subdivisions = if lod == node.lod_level {
+                request.subdivisions
+            } else {
+                config.lod_subdivisions[lod as usize]
             };
-
-            streaming.queue_request(request);
+            streaming.queue_refinement(MeshRequest {
+                lod,
+                subdivisions,
+                priority: distance + REFINEMENT_PRIORITY_OFFSET,
+                ..request
+            });
+        } else if streaming.stale.contains(&node.id)
+            || streaming
+                .spawned_detail
+                .get(&node.id)
+                .is_some_and(|&detail| detail != (request.lod, request.subdivisions))
+        {
+            // Rebuild with the new config or the LOD or extra detail the node moved to,
+            // nearest chunks first
+            streaming.queue_refinement(request);
+        } else if streaming
+            .seams
+            .get(&node.id)
+            .is_some_and(|seams| *seams != neighbor_lods)
+        {
+            // A neighbor changed detail, so the stitched edges need re-meshing
+            streaming.queue_refinement(MeshRequest {
+                priority: distance + REFINEMENT_PRIORITY_OFFSET,
+                ..request
+            });
         }
     }
 
     // Mark nodes that are no longer selected for removal, but handle LOD transitions gracefully
-    let selected_ids: std::collections::HashSet<u64> = quadtree
+    let selected_ids: HashSet<u64> = quadtree
         .collect_selected_nodes()
         .iter()
         .map(|n| n.id)
         .collect();
+    streaming.selected_nodes = Some(selected_ids.clone());
+
+    let ready_area =
+        BoundingCircle::new(Vec2::new(camera_pos.x, camera_pos.z), config.ready_radius);
+    streaming.ready_nodes = Some(
+        selected
+            .iter()
+            .filter(|node| config.ready_radius <= 0.0 || node.bounds.intersects(&ready_area))
+            .map(|node| node.id)
+            .collect(),
+    );
+
+    // Warmed nodes the camera has reached stream normally from now on; the rest expire
+    let TerrainStreaming {
+        warmup,
+        revealed_warmup,
+        ..
+    } = &mut *streaming;
+    warmup.retain(|id, ttl| {
+        if selected_ids.contains(id) {
+            revealed_warmup.push(*id);
+            return false;
+        }
+        *ttl -= time.delta_secs();
+        *ttl > 0.0
+    });
+
+    // Drop refinements for nodes that left the selection
+    streaming
+        .refinements
+        .retain(|id, _| selected_ids.contains(id));
+
+    // Chunks back in view get a reprieve
+    let TerrainStreaming {
+        pending_despawn,
+        spawned,
+        spawned_detail,
+        ..
+    } = &mut *streaming;
+    pending_despawn.retain(|id, _| spawned.contains_key(id) && !selected_ids.contains(id));
+    spawned_detail.retain(|id, _| spawned.contains_key(id));
 
     // Find nodes that need to be removed (spawned but not selected)
     let spawned_not_selected: Vec<u64> = streaming
         .spawned
         .keys()
-        .filter(|id| !selected_ids.contains(id))
+        .filter(|id| !selected_ids.contains(id) && !streaming.warmup.contains_key(id))
         .cloned()
         .collect();
 
     for node_id in spawned_not_selected {
         // Case 1: Check if this node's CHILDREN are now selected (subdivision: parent -> children)
-        let child_ids: Vec<u64> = (1..=4).map(|i| node_id * 4 + i).collect();
-        let children_selected: Vec<u64> = child_ids
+        let children_selected: Vec<u64> = child_node_ids(node_id)
             .iter()
             .filter(|id| selected_ids.contains(id))
             .cloned()
@@ -231,10 +1262,12 @@ pub fn update_quadtree(
             if all_children_spawned {
                 // All children ready, safe to remove parent
                 streaming.spawned.remove(&node_id);
+                streaming.seams.remove(&node_id);
+                streaming.stale.remove(&node_id);
                 streaming.waiting_for_children.remove(&node_id);
             } else {
                 // Children not ready - keep parent visible
-                let pending_children: std::collections::HashSet<u64> = children_selected
+                let pending_children: HashSet<u64> = children_selected
                     .iter()
                     .filter(|id| !streaming.spawned.contains_key(id))
                     .cloned()
@@ -247,156 +1280,601 @@ pub fn update_quadtree(
         }
 
         // Case 2: Check if this node's PARENT is now selected (merge: children -> parent)
-        // Parent ID calculation: for child = parent*4 + offset (offset 1-4)
-        // So parent = (child - 1) / 4 (integer division) for child > 4
-        if node_id > 4 {
-            let parent_id = (node_id - 1) / 4;
-            if selected_ids.contains(&parent_id) {
-                // This is a child that should merge back into parent
-                let parent_spawned = streaming.spawned.contains_key(&parent_id);
-
-                if parent_spawned {
-                    // Parent is ready, safe to remove child
-                    streaming.spawned.remove(&node_id);
-                    streaming.waiting_for_parent.remove(&node_id);
-                } else {
-                    // Parent not ready - keep child visible
-                    streaming.waiting_for_parent.insert(node_id, parent_id);
-                }
-                continue;
+        if let Some(parent_id) = parent_node_id(node_id)
+            && selected_ids.contains(&parent_id)
+        {
+            // This is a child that should merge back into parent
+            let parent_spawned = streaming.spawned.contains_key(&parent_id);
+
+            if parent_spawned {
+                // Parent is ready, safe to remove child
+                streaming.spawned.remove(&node_id);
+                streaming.seams.remove(&node_id);
+                streaming.stale.remove(&node_id);
+                streaming.waiting_for_parent.remove(&node_id);
+            } else {
+                // Parent not ready - keep child visible
+                streaming.waiting_for_parent.insert(node_id, parent_id);
             }
+            continue;
         }
 
-        // Case 3: Node went out of view entirely (not LOD transition)
+        // Case 3: Node went out of view entirely (not LOD transition), after lingering
+        if config.chunk_despawn_delay_secs > 0.0 {
+            let lingered = streaming.pending_despawn.entry(node_id).or_default();
+            *lingered += time.delta_secs();
+            if *lingered < config.chunk_despawn_delay_secs {
+                continue;
+            }
+        }
+        streaming.pending_despawn.remove(&node_id);
         streaming.spawned.remove(&node_id);
+        streaming.seams.remove(&node_id);
+        streaming.stale.remove(&node_id);
         streaming.waiting_for_children.remove(&node_id);
         streaming.waiting_for_parent.remove(&node_id);
     }
 }
 
+/// Run condition: the terrain around the camera has finished streaming in
+///
+/// False until the first quadtree selection, then true once every selected
+/// node within `ready_radius` (or every selected node, with a radius of 0)
+/// has a chunk at its target LOD and no re-mesh queued. Hold a loading screen with
+/// `.run_if(not(terrain_ready))`.
+pub fn terrain_ready(streaming: Res<TerrainStreaming>) -> bool {
+    streaming.is_ready()
+}
+
+/// System: Queue warmup meshes for each [`WarmupRequest`]
+pub fn handle_warmup_requests(
+    mut requests: MessageReader<WarmupRequest>,
+    config: Res<TerrainConfig>,
+    mut streaming: ResMut<TerrainStreaming>,
+) {
+    for request in requests.read() {
+        streaming.request_warmup(request.center, request.radius, &config);
+    }
+}
+
+/// System: Despawn and re-queue the chunk named by each [`RegenerateChunk`]
+#[allow(clippy::too_many_arguments)]
+pub fn handle_regenerate_requests(
+    mut commands: Commands,
+    mut requests: MessageReader<RegenerateChunk>,
+    config: Res<TerrainConfig>,
+    quadtree: Res<TerrainQuadtree>,
+    chunks: Query<&Chunk>,
+    mut streaming: ResMut<TerrainStreaming>,
+    mut chunk_index: ResMut<TerrainChunkIndex>,
+    mesh_heights: Res<TerrainMeshHeights>,
+    mut despawned_messages: MessageWriter<TerrainChunkDespawned>,
+) {
+    for request in requests.read() {
+        let node_id = request.node_id;
+        let Some(&entity) = streaming.spawned.get(&node_id) else {
+            continue;
+        };
+        let (Ok(chunk), Some(node)) = (chunks.get(entity), quadtree.find_node(node_id)) else {
+            continue;
+        };
+        let spawned_lod = (chunk.current_lod as usize).min(config.lod_subdivisions.len() - 1) as u8;
+
+        commands.entity(entity).despawn();
+        chunk_index.remove(chunk.coords, entity);
+        mesh_heights.remove(node_id);
+        despawned_messages.write(TerrainChunkDespawned {
+            node_id,
+            coords: chunk.coords,
+        });
+
+        streaming.spawned.remove(&node_id);
+        streaming.stale.remove(&node_id);
+        streaming.in_flight.remove(&node_id);
+        streaming
+            .completed
+            .retain(|result| result.node_id != node_id);
+        streaming.mesh_cache.remove(chunk.coords, spawned_lod);
+        let neighbor_lods = streaming.seams.remove(&node_id).unwrap_or_default();
+
+        // Regenerate at the node's target detail, even if the chunk was still coarser
+        streaming.queue_request(MeshRequest {
+            node_id,
+            center: node.center(),
+            size: node.size(),
+            lod: node.lod_level,
+            subdivisions: node.subdivisions(&config),
+            priority: REGENERATE_PRIORITY,
+            coords: chunk.coords,
+            neighbor_lods,
+        });
+    }
+}
+
+/// System: Re-mesh the chunks under each [`TerrainEdited`] area
+pub fn handle_terrain_edits(
+    mut edits: MessageReader<TerrainEdited>,
+    quadtree: Res<TerrainQuadtree>,
+    mut streaming: ResMut<TerrainStreaming>,
+) {
+    for edit in edits.read() {
+        streaming.rebuild_area(BoundingCircle::new(edit.center, edit.radius), &quadtree);
+    }
+}
+
 /// System: Spawn async mesh generation tasks
+///
+/// With [`GenerationMode::Synchronous`] the meshes are generated right here
+/// instead, and the task pool is never touched.
 pub fn spawn_mesh_tasks(
     config: Res<TerrainConfig>,
     terrain_query: Query<&HeightmapHandle, With<Terrain>>,
     mut streaming: ResMut<TerrainStreaming>,
 ) {
-    let task_pool = AsyncComputeTaskPool::get();
+    let mut in_flight_vertices = streaming.in_flight_vertices();
+    let mut generated_inline = 0;
 
     // Limit concurrent tasks
     while streaming.in_flight.len() < config.max_concurrent_tasks {
-        let Some(Reverse(request)) = streaming.pending.pop() else {
+        let Some(Reverse(next)) = streaming.pending.peek() else {
             break;
         };
 
-        // Skip if already spawned (could have been spawned while in queue)
-        if streaming.spawned.contains_key(&request.node_id) {
-            continue;
+        // Respect the vertex budget, but always admit one task so large chunks can't stall
+        let vertices = estimated_chunk_vertices(next.subdivisions);
+        if let Some(budget) = config.max_concurrent_vertices
+            && !streaming.in_flight.is_empty()
+            && in_flight_vertices + vertices > budget
+        {
+            break;
         }
 
-        // Clone config for the async task
-        let config = config.clone();
-        let node_id = request.node_id;
-        let center = request.center;
-        let size = request.size;
-        let lod = request.lod;
-        let coords = request.coords;
-
-        // Get the noise from terrain entity or use default
-        let noise = if let Ok(heightmap) = terrain_query.single() {
-            match heightmap {
-                HeightmapHandle::Noise(noise, _) => (**noise).clone(),
-                _ => TerrainNoise::default(),
-            }
-        } else {
-            TerrainNoise::default()
+        let Some(request) = streaming.pop_pending() else {
+            break;
         };
 
-        let task = task_pool.spawn(async move {
-            // Calculate subdivisions based on LOD
-            let subdivisions = config.lod_subdivisions[lod as usize];
+        // Skip if already spawned (could have been spawned while in queue), unless refining,
+        // changing LOD or subdivisions, or re-stitching to changed neighbors
+        if streaming.spawned.contains_key(&request.node_id)
+            && !streaming.refinements.contains_key(&request.node_id)
+            && !streaming.stale.contains(&request.node_id)
+            && streaming.spawned_detail.get(&request.node_id)
+                == Some(&(request.lod, request.subdivisions))
+            && streaming
+                .seams
+                .get(&request.node_id)
+                .is_none_or(|seams| *seams == request.neighbor_lods)
+        {
+            continue;
+        }
+
+        // Reuse a cached mesh; analyzer output isn't cached, so those chunks regenerate
+        if streaming.chunk_analyzer.is_none()
+            && let Some(cached) = streaming
+                .mesh_cache
+                .get(request.coords, request.lod)
+                .filter(|cached| cached.matches(&request))
+                .cloned()
+        {
+            streaming.completed.push(MeshResult {
+                node_id: request.node_id,
+                mesh: Mesh::new(
+                    PrimitiveTopology::TriangleList,
+                    RenderAssetUsages::default(),
+                ),
+                center: request.center,
+                lod: request.lod,
+                coords: request.coords,
+                size: request.size,
+                subdivisions: request.subdivisions,
+                min_y: cached.min_y,
+                max_y: cached.max_y,
+                neighbor_lods: request.neighbor_lods,
+                chunk_data: None,
+                quantization: cached.quantization,
+                cached_mesh: Some(cached.handle),
+                samples: cached.samples,
+            });
+            continue;
+        }
 
-            // Generate mesh
-            let mesh = generate_chunk_mesh(coords, size, subdivisions, &noise, &config);
+        // Mesh from the terrain entity's heightmap, or default noise without one
+        let heightmap = match terrain_query.single() {
+            Ok(HeightmapHandle::Noise(noise, _)) => TaskHeightmap::Noise(noise.clone()),
+            Ok(handle) => handle.exaggerated_source(&config).map_or_else(
+                || TaskHeightmap::Noise(Box::default()),
+                TaskHeightmap::Source,
+            ),
+            Err(_) => TaskHeightmap::Noise(Box::default()),
+        };
 
-            MeshResult {
-                node_id,
-                mesh,
-                center,
-                lod,
-                coords,
+        let analyzer = streaming.chunk_analyzer.clone();
+        // Only ship the holes overlapping this chunk to the task
+        let chunk_bounds = Aabb2d::new(request.center, Vec2::splat(request.size / 2.0));
+        let holes = streaming.mesh_options.holes.clipped_to(chunk_bounds);
+        let options = streaming
+            .mesh_options
+            .clone()
+            .with_neighbor_lods(request.neighbor_lods)
+            .with_holes(holes);
+
+        if config.generation_mode == GenerationMode::Synchronous {
+            streaming.completed.push(generate_mesh_result(
+                &request, &heightmap, analyzer, &options, &config,
+            ));
+            streaming.generated_inline += 1;
+            generated_inline += 1;
+            if config.max_spawns_per_frame > 0 && generated_inline >= config.max_spawns_per_frame {
+                break;
             }
+            continue;
+        }
+
+        // Clone config for the async task
+        let config = config.clone();
+        let node_id = request.node_id;
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            generate_mesh_result(&request, &heightmap, analyzer, &options, &config)
         });
 
-        streaming.in_flight.insert(node_id, task);
+        in_flight_vertices += vertices;
+        streaming
+            .in_flight
+            .insert(node_id, InFlightMesh { task, vertices });
+    }
+}
+
+/// Generate the mesh for a request, with its analyzer output and quantization
+fn generate_mesh_result(
+    request: &MeshRequest,
+    heightmap: &TaskHeightmap,
+    analyzer: Option<ChunkAnalyzerFn>,
+    options: &MeshGenOptions,
+    config: &TerrainConfig,
+) -> MeshResult {
+    let (
+        GeneratedChunk {
+            mut mesh,
+            min_y,
+            max_y,
+        },
+        samples,
+    ) = match heightmap {
+        TaskHeightmap::Noise(noise) => generate_chunk_mesh_with_samples(
+            request.coords,
+            request.size,
+            request.subdivisions,
+            noise,
+            config,
+            options,
+        ),
+        TaskHeightmap::Source(source) => generate_chunk_mesh_from_source_with_samples(
+            request.coords,
+            request.size,
+            request.subdivisions,
+            source.as_ref(),
+            config,
+            options,
+        ),
+    };
+    let chunk_data = analyzer.map(|analyzer| analyzer(&samples));
+    let quantization = if config.quantize_positions {
+        quantize_mesh_positions(&mut mesh)
+    } else {
+        None
+    };
+
+    MeshResult {
+        node_id: request.node_id,
+        mesh,
+        center: request.center,
+        lod: request.lod,
+        coords: request.coords,
+        size: request.size,
+        subdivisions: request.subdivisions,
+        min_y,
+        max_y,
+        neighbor_lods: request.neighbor_lods,
+        chunk_data,
+        quantization,
+        cached_mesh: None,
+        samples: Some(Arc::new(samples)),
     }
 }
 
+/// Height data moved into an async mesh task
+enum TaskHeightmap {
+    Noise(Box<TerrainNoise>),
+    Source(Arc<dyn HeightmapSource>),
+}
+
+/// Vertex count of a chunk mesh with the given subdivisions, including skirts
+pub fn estimated_chunk_vertices(subdivisions: u32) -> usize {
+    let per_side = subdivisions as usize + 1;
+    per_side * per_side + per_side * 4
+}
+
 /// System: Poll mesh tasks for completion
-pub fn poll_mesh_tasks(mut streaming: ResMut<TerrainStreaming>) {
+pub fn poll_mesh_tasks(
+    mut streaming: ResMut<TerrainStreaming>,
+    mut metrics: ResMut<TerrainStreamingMetrics>,
+) {
+    // Cancel tasks for nodes deselected before their mesh finished (dropping a task cancels it)
+    let TerrainStreaming {
+        in_flight,
+        selected_nodes,
+        warmup,
+        ..
+    } = &mut *streaming;
+    if let Some(selected) = selected_nodes {
+        in_flight.retain(|node_id, _| selected.contains(node_id) || warmup.contains_key(node_id));
+    }
+
     // First, find which tasks are finished
     let finished_ids: Vec<u64> = streaming
         .in_flight
         .iter()
-        .filter(|(_, task)| task.is_finished())
+        .filter(|(_, mesh)| mesh.task.is_finished())
         .map(|(id, _)| *id)
         .collect();
 
     // Then remove and poll them
+    metrics.completed_this_frame = std::mem::take(&mut streaming.generated_inline);
     for id in finished_ids {
-        if let Some(mut task) = streaming.in_flight.remove(&id)
-            && let Some(result) = block_on(futures_lite::future::poll_once(&mut task))
+        if let Some(mut mesh) = streaming.in_flight.remove(&id)
+            && let Some(result) = block_on(futures_lite::future::poll_once(&mut mesh.task))
         {
             streaming.completed.push(result);
+            metrics.completed_this_frame += 1;
         }
     }
+    metrics.meshes_generated_total += metrics.completed_this_frame as u64;
+}
+
+/// System: Record queue depths into [`TerrainStreamingMetrics`]
+pub fn update_streaming_metrics(
+    streaming: Res<TerrainStreaming>,
+    mut metrics: ResMut<TerrainStreamingMetrics>,
+) {
+    metrics.pending = streaming.pending.len();
+    metrics.in_flight = streaming.in_flight.len();
+    metrics.spawned = streaming.spawned.len();
+}
+
+/// Height query over a terrain entity's source, placed by its transform
+///
+/// Used wherever a system needs world-space heights but the app may not have
+/// inserted a [`TerrainHeightQuery`] resource: feature spawners and ground
+/// snapping. Without a heightmap the default noise terrain is queried.
+pub(crate) fn terrain_height_query(
+    handle: Option<&HeightmapHandle>,
+    config: &TerrainConfig,
+    terrain_transform: &GlobalTransform,
+) -> TerrainHeightQuery {
+    let query = match handle {
+        Some(HeightmapHandle::Noise(noise, noise_config)) => {
+            TerrainHeightQuery::new((**noise).clone(), noise_config.clone())
+        }
+        Some(other) => TerrainHeightQuery {
+            source: other.exaggerated_source(config),
+            ..TerrainHeightQuery::new(TerrainNoise::default(), config.clone())
+        },
+        None => TerrainHeightQuery::new(TerrainNoise::default(), config.clone()),
+    };
+    query.with_transform(terrain_transform.compute_transform())
+}
+
+/// World-space bounds of a chunk on a (possibly moved or rotated) terrain
+fn chunk_world_bounds(
+    result: &MeshResult,
+    base_elevation: f32,
+    terrain_transform: &GlobalTransform,
+) -> Aabb3d {
+    let half = result.size / 2.0;
+    let heights = [result.min_y, result.max_y].map(|y| y + base_elevation);
+    let corners = [-half, half].into_iter().flat_map(|x| {
+        heights.into_iter().flat_map(move |y| {
+            [-half, half].into_iter().map(move |z| {
+                terrain_transform.transform_point(Vec3::new(
+                    result.center.x + x,
+                    y,
+                    result.center.y + z,
+                ))
+            })
+        })
+    });
+    Aabb3d::from_point_cloud(Isometry3d::IDENTITY, corners)
 }
 
 /// System: Spawn chunk entities from completed mesh results
+///
+/// Chunks are placed relative to the terrain entity's `GlobalTransform`.
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_chunk_entities(
     mut commands: Commands,
+    config: Res<TerrainConfig>,
+    terrain_query: Query<&GlobalTransform, With<Terrain>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
     material_handle: Res<TerrainMaterialHandle>,
     mut streaming: ResMut<TerrainStreaming>,
+    mut chunk_index: ResMut<TerrainChunkIndex>,
+    mesh_heights: Res<TerrainMeshHeights>,
+    height_query: Option<Res<TerrainHeightQuery>>,
+    heightmaps: Query<&HeightmapHandle, With<Terrain>>,
     existing_chunks: Query<(Entity, &Chunk)>,
+    mut spawned_messages: MessageWriter<TerrainChunkSpawned>,
+    mut despawned_messages: MessageWriter<TerrainChunkDespawned>,
 ) {
     let Some(material) = material_handle.handle.clone() else {
         return;
     };
 
-    // Drain completed results into a local vec to avoid borrow issues
-    let completed_results: Vec<MeshResult> = streaming.completed.drain(..).collect();
+    // Drain up to the per-frame cap into a local vec to avoid borrow issues;
+    // deferred results keep their place and parents keep waiting for them
+    let spawn_count = match config.max_spawns_per_frame {
+        0 => streaming.completed.len(),
+        max => max.min(streaming.completed.len()),
+    };
+    let completed_results: Vec<MeshResult> = streaming.completed.drain(..spawn_count).collect();
+    let terrain_transform = terrain_query.single().copied().unwrap_or_default();
+
+    // Feature spawners fall back to sampling the terrain entity's own source
+    let feature_spawners = streaming.feature_spawners.clone();
+    let fallback_query = if height_query.is_none()
+        && !feature_spawners.is_empty()
+        && !completed_results.is_empty()
+    {
+        heightmaps
+            .single()
+            .ok()
+            .map(|handle| terrain_height_query(Some(handle), &config, &terrain_transform))
+    } else {
+        None
+    };
+    let feature_query = height_query.as_deref().or(fallback_query.as_ref());
+
+    // Warmed chunks the camera reached become visible
+    for node_id in std::mem::take(&mut streaming.revealed_warmup) {
+        if let Some(&entity) = streaming.spawned.get(&node_id) {
+            commands.entity(entity).insert(Visibility::Inherited);
+        }
+    }
 
     // Spawn new chunks
-    for result in completed_results {
-        let mesh_handle = meshes.add(result.mesh);
-
-        let entity = commands
-            .spawn((
-                Mesh3d(mesh_handle),
-                MeshMaterial3d(material.clone()),
-                Transform::from_translation(Vec3::new(result.center.x, 0.0, result.center.y)),
-                Chunk {
-                    coords: result.coords,
-                    current_lod: result.lod as u32,
-                    node_id: result.node_id,
+    let mut replaced = HashSet::new();
+    for mut result in completed_results {
+        let bounds = chunk_world_bounds(&result, config.base_elevation, &terrain_transform);
+
+        // Quantized chunks need their own copy of the material for the reconstruction
+        // uniform; without one they fall back to the full-precision positions
+        let chunk_material = result.quantization.and_then(|quantization| {
+            let mut chunk_material = materials.get(&material).cloned()?;
+            chunk_material.extension.quantization = quantization;
+            Some(chunk_material)
+        });
+        if result.quantization.is_some() && chunk_material.is_none() {
+            let mesh = match &result.cached_mesh {
+                Some(handle) => meshes.get_mut(handle),
+                None => Some(&mut result.mesh),
+            };
+            if let Some(mesh) = mesh {
+                mesh.remove_attribute(ATTRIBUTE_QUANTIZED_POSITION);
+            }
+            result.quantization = None;
+        }
+
+        let mesh_handle = result
+            .cached_mesh
+            .unwrap_or_else(|| meshes.add(result.mesh));
+        if config.mesh_cache_size > 0 {
+            streaming.mesh_cache.insert(
+                result.coords,
+                result.lod,
+                CachedMesh {
+                    handle: mesh_handle.clone(),
+                    center: result.center,
+                    size: result.size,
+                    subdivisions: result.subdivisions,
+                    neighbor_lods: result.neighbor_lods,
+                    min_y: result.min_y,
+                    max_y: result.max_y,
+                    quantization: result.quantization,
+                    samples: result.samples.clone(),
                 },
-            ))
-            .id();
+                config.mesh_cache_size,
+            );
+        }
 
-        streaming.spawned.insert(result.node_id, entity);
+        let mut entity_commands = commands.spawn((
+            Mesh3d(mesh_handle),
+            MeshMaterial3d(material.clone()),
+            terrain_transform
+                .mul_transform(Transform::from_translation(Vec3::new(
+                    result.center.x,
+                    config.base_elevation,
+                    result.center.y,
+                )))
+                .compute_transform(),
+            Chunk {
+                coords: result.coords,
+                current_lod: result.lod as u32,
+                node_id: result.node_id,
+                size: result.size,
+            },
+            // Selects the chunk's geomorph band in the vertex shader
+            MeshTag(result.lod as u32),
+            Aabb::from_min_max(
+                Vec3::new(-result.size / 2.0, result.min_y, -result.size / 2.0),
+                Vec3::new(result.size / 2.0, result.max_y, result.size / 2.0),
+            ),
+        ));
+        if let Some(insert_data) = result.chunk_data {
+            insert_data(&mut entity_commands);
+        }
+        if let Some(chunk_material) = chunk_material {
+            entity_commands.insert(MeshMaterial3d(materials.add(chunk_material)));
+        }
+        // Warmed chunks wait out of sight until the camera selects them
+        if streaming.warmup.contains_key(&result.node_id) {
+            entity_commands.insert(Visibility::Hidden);
+        }
+        let entity = entity_commands.id();
+        chunk_index.insert(result.coords, result.size, entity);
+        if let Some(samples) = &result.samples {
+            mesh_heights.insert(result.node_id, result.center, result.size, samples);
+        }
 
-        // Case A: Check if this node's parent was waiting for it (we're a child being spawned)
-        // Parent ID is (child_id - 1) / 4
-        if result.node_id > 4 {
-            let parent_id = (result.node_id - 1) / 4;
+        // A refined mesh replaces the coarse chunk for the same node
+        if let Some(previous) = streaming.spawned.insert(result.node_id, entity) {
+            replaced.insert(previous);
+            commands.entity(previous).despawn();
+            chunk_index.remove(result.coords, previous);
+            despawned_messages.write(TerrainChunkDespawned {
+                node_id: result.node_id,
+                coords: result.coords,
+            });
+        }
+        spawned_messages.write(TerrainChunkSpawned {
+            entity,
+            node_id: result.node_id,
+            coords: result.coords,
+            lod: result.lod,
+            bounds,
+        });
+        if let Some(query) = feature_query {
+            for spawner in &feature_spawners {
+                let mut rng = ChunkRng::for_chunk(result.coords, result.size);
+                spawner.spawn(
+                    &mut commands,
+                    entity,
+                    result.coords,
+                    bounds,
+                    query,
+                    &mut rng,
+                );
+            }
+        }
+        streaming.seams.insert(result.node_id, result.neighbor_lods);
+        streaming
+            .spawned_detail
+            .insert(result.node_id, (result.lod, result.subdivisions));
+        streaming.stale.remove(&result.node_id);
+        if streaming.refinements.get(&result.node_id) == Some(&result.lod) {
+            streaming.refinements.remove(&result.node_id);
+        }
 
-            if let Some(waiting_children) = streaming.waiting_for_children.get_mut(&parent_id) {
-                waiting_children.remove(&result.node_id);
+        // Case A: Check if this node's parent was waiting for it (we're a child being spawned)
+        if let Some(parent_id) = parent_node_id(result.node_id)
+            && let Some(waiting_children) = streaming.waiting_for_children.get_mut(&parent_id)
+        {
+            waiting_children.remove(&result.node_id);
 
-                // If all children are now spawned, remove parent from spawned
-                if waiting_children.is_empty() {
-                    streaming.spawned.remove(&parent_id);
-                }
+            // If all children are now spawned, remove parent from spawned
+            if waiting_children.is_empty() {
+                streaming.spawned.remove(&parent_id);
+                streaming.seams.remove(&parent_id);
+                streaming.stale.remove(&parent_id);
             }
         }
 
@@ -411,6 +1889,8 @@ pub fn spawn_chunk_entities(
 
         for child_id in children_waiting {
             streaming.spawned.remove(&child_id);
+            streaming.seams.remove(&child_id);
+            streaming.stale.remove(&child_id);
             streaming.waiting_for_parent.remove(&child_id);
         }
     }
@@ -420,21 +1900,1804 @@ pub fn spawn_chunk_entities(
         .waiting_for_children
         .retain(|_, children| !children.is_empty());
 
-    // Despawn chunks that are no longer needed
-    let spawned_ids: std::collections::HashSet<u64> = streaming.spawned.keys().cloned().collect();
-
+    // Despawn chunks that are no longer needed, including ones whose node was
+    // dropped and re-spawned with a new entity this frame
     for (entity, chunk) in existing_chunks.iter() {
-        if !spawned_ids.contains(&chunk.node_id) {
-            commands.entity(entity).despawn();
+        let current = streaming.spawned.get(&chunk.node_id);
+        if current == Some(&entity) || replaced.contains(&entity) {
+            continue;
         }
+        commands.entity(entity).despawn();
+        chunk_index.remove(chunk.coords, entity);
+        if current.is_none() {
+            mesh_heights.remove(chunk.node_id);
+        }
+        despawned_messages.write(TerrainChunkDespawned {
+            node_id: chunk.node_id,
+            coords: chunk.coords,
+        });
     }
 }
 
-// Implement Clone for TerrainNoise so it can be sent to async tasks
-impl Clone for TerrainNoise {
-    fn clone(&self) -> Self {
-        // FastNoiseLite doesn't implement Clone, so we recreate with same settings
-        // This is a limitation - we use the default seed for now
-        TerrainNoise::default()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TerrainBundle;
+    use crate::edit::{EditableHeightmap, Falloff};
+    use crate::heightmap::ProceduralHeightmap;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    /// Headless app running the full streaming pipeline
+    fn test_app(config: &TerrainConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut()
+            .spawn(TerrainBundle::noise(TerrainNoise::default(), config));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 5000.0, 0.0)));
+        app
+    }
+
+    /// Step the app until streaming has nothing left pending or in flight
+    fn run_until_idle(app: &mut App) {
+        for _ in 0..500 {
+            app.update();
+            let streaming = app.world().resource::<TerrainStreaming>();
+            if !streaming.spawned.is_empty()
+                && streaming.pending_iter().next().is_none()
+                && streaming.in_flight_node_ids().next().is_none()
+                && streaming.completed.is_empty()
+            {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        panic!("streaming did not settle");
+    }
+
+    #[test]
+    fn test_skirted_chunks_mesh_against_coarser_neighbors() {
+        let config = TerrainConfig::default();
+        assert_eq!(config.seam_mode, crate::config::SeamMode::Skirts);
+        let mut app = test_app(&config);
+        let mut camera = app
+            .world_mut()
+            .query_filtered::<&mut Transform, With<Camera3d>>();
+        camera.single_mut(app.world_mut()).unwrap().translation.y = 2000.0;
+        run_until_idle(&mut app);
+
+        // Edge normals need the neighbor levels even without stitching
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert!(
+            streaming.seams.values().any(|seams| *seams != [0; 4]),
+            "no chunk was meshed against a coarser neighbor"
+        );
+    }
+
+    #[test]
+    fn test_spawned_bounds_cover_streamed_area() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = test_app(&config);
+        run_until_idle(&mut app);
+
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let quadtree = app.world().resource::<TerrainQuadtree>();
+        let bounds: Vec<(u64, Aabb2d)> = streaming.spawned_bounds(quadtree).collect();
+        assert_eq!(bounds.len(), streaming.spawned.len());
+
+        // The camera sits over the origin, so loaded terrain surrounds it
+        for x in -8..=8 {
+            for z in -8..=8 {
+                let point = Vec2::new(x as f32, z as f32) * 250.0;
+                assert!(
+                    bounds.iter().any(|(_, b)| b.closest_point(point) == point),
+                    "{point} not covered"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_metrics_track_generation() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = test_app(&config);
+
+        app.update();
+        let metrics = app.world().resource::<TerrainStreamingMetrics>();
+        assert!(metrics.pending + metrics.in_flight + metrics.spawned > 0);
+
+        run_until_idle(&mut app);
+        app.update();
+
+        let metrics = app.world().resource::<TerrainStreamingMetrics>();
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert_eq!(metrics.pending, 0);
+        assert_eq!(metrics.in_flight, 0);
+        assert_eq!(metrics.completed_this_frame, 0);
+        assert_eq!(metrics.spawned, streaming.spawned.len());
+        assert!(metrics.meshes_generated_total >= metrics.spawned as u64);
+    }
+
+    #[test]
+    fn test_extra_detail_change_remeshes_chunk() {
+        // Roots can't split, so approaching them adds mesh detail instead. Past the
+        // last LOD distance the LOD stays put while the extra detail changes.
+        let config = TerrainConfig::builder()
+            .render_distance(2)
+            .lod_subdivisions([8, 8, 4, 4])
+            .min_mesh_chunk_size(800.0)
+            .generation_mode(GenerationMode::Synchronous)
+            .build();
+        let mut app = test_app(&config);
+        let move_camera = |app: &mut App, y: f32| {
+            app.world_mut()
+                .query_filtered::<&mut Transform, With<Camera>>()
+                .single_mut(app.world_mut())
+                .unwrap()
+                .translation = Vec3::new(0.0, y, 0.0);
+        };
+        let under_camera = |app: &App| {
+            let node = app
+                .world()
+                .resource::<TerrainQuadtree>()
+                .collect_selected_nodes()
+                .into_iter()
+                .find(|node| node.bounds.closest_point(Vec2::ONE) == Vec2::ONE)
+                .unwrap();
+            let spawned = app
+                .world()
+                .resource::<TerrainStreaming>()
+                .spawned_detail
+                .get(&node.id)
+                .copied();
+            (node.id, node.lod_level, node.subdivisions(&config), spawned)
+        };
+
+        move_camera(&mut app, 6000.0);
+        run_until_idle(&mut app);
+        let (far_id, far_lod, far_subdivisions, spawned) = under_camera(&app);
+        assert_eq!(spawned, Some((far_lod, far_subdivisions)));
+
+        move_camera(&mut app, 3000.0);
+        run_until_idle(&mut app);
+        let (id, lod, subdivisions, spawned) = under_camera(&app);
+        assert_eq!((id, lod), (far_id, far_lod), "same node at the same LOD");
+        assert!(subdivisions > far_subdivisions);
+        assert_eq!(
+            spawned,
+            Some((lod, subdivisions)),
+            "re-meshed with the extra detail"
+        );
+    }
+
+    #[test]
+    fn test_held_back_results_are_not_generated_again() {
+        let config = TerrainConfig::builder()
+            .render_distance(4)
+            .max_spawns_per_frame(1)
+            .build();
+        let mut app = test_app(&config);
+        run_until_idle(&mut app);
+
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let metrics = app.world().resource::<TerrainStreamingMetrics>();
+        assert!(streaming.spawned.len() > 1);
+        assert_eq!(
+            metrics.meshes_generated_total,
+            streaming.spawned.len() as u64,
+            "every node is meshed exactly once"
+        );
+        let chunks = app.world_mut().query::<&Chunk>().iter(app.world()).count();
+        assert_eq!(
+            chunks,
+            app.world().resource::<TerrainStreaming>().spawned.len()
+        );
+
+        // Neither queue accepts a node whose result is still waiting to spawn
+        let mut streaming = app.world_mut().resource_mut::<TerrainStreaming>();
+        streaming.completed.push(mesh_result(u64::MAX));
+        let request = MeshRequest {
+            node_id: u64::MAX,
+            center: Vec2::ZERO,
+            size: 100.0,
+            lod: 0,
+            subdivisions: 8,
+            priority: 0.0,
+            coords: IVec2::ZERO,
+            neighbor_lods: [0; 4],
+        };
+        streaming.queue_request(request.clone());
+        streaming.queue_refinement(request);
+        assert!(streaming.pending_iter().next().is_none());
+    }
+
+    #[test]
+    fn test_terrain_ready_after_queued_chunks_spawn() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = test_app(&config);
+        let ready = |app: &mut App| app.world_mut().run_system_once(terrain_ready).unwrap();
+        assert!(!ready(&mut app));
+
+        let mut flipped = false;
+        for _ in 0..500 {
+            app.update();
+            if ready(&mut app) {
+                flipped = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(flipped);
+
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert!(streaming.pending_iter().next().is_none());
+        assert!(streaming.in_flight_node_ids().next().is_none());
+        assert!(streaming.completed.is_empty());
+        let selected = app
+            .world()
+            .resource::<TerrainQuadtree>()
+            .collect_selected_nodes();
+        assert!(
+            selected
+                .iter()
+                .all(|node| streaming.spawned.contains_key(&node.id))
+        );
+    }
+
+    #[test]
+    fn test_feature_spawner_runs_once_per_chunk() {
+        #[derive(Component)]
+        struct Decorated(u64);
+
+        #[derive(Default)]
+        struct TagChunks(Arc<RwLock<HashMap<Entity, usize>>>);
+
+        impl ChunkFeatureSpawner for TagChunks {
+            fn spawn(
+                &self,
+                commands: &mut Commands,
+                chunk_entity: Entity,
+                _coords: IVec2,
+                _bounds: Aabb3d,
+                height_query: &TerrainHeightQuery,
+                rng: &mut ChunkRng,
+            ) {
+                assert!(height_query.get_height(0.0, 0.0).is_finite());
+                *self.0.write().unwrap().entry(chunk_entity).or_default() += 1;
+                commands
+                    .entity(chunk_entity)
+                    .insert(Decorated(rng.next_u64()));
+            }
+        }
+
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = test_app(&config);
+        let spawner = TagChunks::default();
+        let calls = spawner.0.clone();
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .add_feature_spawner(spawner);
+        run_until_idle(&mut app);
+
+        let calls = calls.read().unwrap();
+        assert!(calls.values().all(|&count| count == 1));
+        let chunk_count = app.world_mut().query::<&Chunk>().iter(app.world()).count();
+        let mut chunks = app.world_mut().query::<(Entity, &Chunk, &Decorated)>();
+        let tagged: Vec<_> = chunks.iter(app.world()).collect();
+        assert_eq!(tagged.len(), chunk_count, "every chunk is decorated");
+        for (entity, chunk, decorated) in tagged {
+            assert!(calls.contains_key(&entity));
+            let expected = ChunkRng::for_chunk(chunk.coords, chunk.size).next_u64();
+            assert_eq!(decorated.0, expected, "seeded from the chunk coordinates");
+        }
+    }
+
+    #[test]
+    fn test_despawn_delay_keeps_reselected_chunks() {
+        let config = TerrainConfig::builder()
+            .render_distance(4)
+            .chunk_despawn_delay_secs(60.0)
+            .build();
+        let mut app = test_app(&config);
+        run_until_idle(&mut app);
+        let before = app.world().resource::<TerrainStreaming>().spawned.clone();
+
+        let set_camera_x = |app: &mut App, x: f32| {
+            let mut cameras = app
+                .world_mut()
+                .query_filtered::<&mut Transform, With<Camera3d>>();
+            cameras.single_mut(app.world_mut()).unwrap().translation.x = x;
+        };
+
+        // Look away long enough for the far view to stream in
+        set_camera_x(&mut app, 20_000.0);
+        run_until_idle(&mut app);
+        for entity in before.values() {
+            assert!(app.world().get_entity(*entity).is_ok());
+        }
+        assert!(
+            !app.world()
+                .resource::<TerrainStreaming>()
+                .pending_despawn
+                .is_empty()
+        );
+
+        // Coming back within the delay finds the same chunks
+        set_camera_x(&mut app, 0.0);
+        run_until_idle(&mut app);
+        let streaming = app.world().resource::<TerrainStreaming>();
+        for (node_id, entity) in &before {
+            assert_eq!(streaming.spawned.get(node_id), Some(entity));
+        }
+        assert!(
+            streaming
+                .pending_despawn
+                .keys()
+                .all(|id| !before.contains_key(id))
+        );
+    }
+
+    #[test]
+    fn test_synchronous_generation_without_task_pool() {
+        use bevy::app::PluginGroup;
+        use bevy::app::TaskPoolPlugin;
+
+        let config = TerrainConfig::builder()
+            .render_distance(4)
+            .generation_mode(GenerationMode::Synchronous)
+            .max_spawns_per_frame(2)
+            .collider_max_lod(u8::MAX)
+            .build();
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins.build().disable::<TaskPoolPlugin>(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<Mesh>()
+        .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut()
+            .spawn(TerrainBundle::noise(TerrainNoise::default(), &config));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 5000.0, 0.0)));
+
+        for _ in 0..500 {
+            app.update();
+            let streaming = app.world().resource::<TerrainStreaming>();
+            let metrics = app.world().resource::<TerrainStreamingMetrics>();
+            assert_eq!(streaming.in_flight_node_ids().count(), 0);
+            assert!(metrics.completed_this_frame <= 2);
+            if !streaming.spawned.is_empty()
+                && streaming.pending_iter().next().is_none()
+                && streaming.completed.is_empty()
+            {
+                break;
+            }
+        }
+
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let metrics = app.world().resource::<TerrainStreamingMetrics>();
+        assert!(streaming.pending_iter().next().is_none());
+        assert!(!streaming.spawned.is_empty());
+        assert!(metrics.meshes_generated_total >= streaming.spawned.len() as u64);
+
+        // Colliders are sampled inline too
+        #[cfg(any(feature = "rapier", feature = "avian"))]
+        {
+            app.update();
+            let colliders = app
+                .world_mut()
+                .query::<&crate::physics::TerrainCollider>()
+                .iter(app.world())
+                .count();
+            assert!(colliders > 0, "no colliders were inserted");
+        }
+    }
+
+    #[test]
+    fn test_equal_priority_requests_pop_in_stable_order() {
+        let request = |node_id, lod| MeshRequest {
+            node_id,
+            center: Vec2::ZERO,
+            size: 100.0,
+            lod,
+            subdivisions: 8,
+            priority: 50.0,
+            coords: IVec2::ZERO,
+            neighbor_lods: [0; 4],
+        };
+        let requests = [(7, 1), (3, 0), (9, 0), (2, 1), (5, 0)];
+
+        // Insertion order must not matter
+        for order in [
+            requests,
+            [
+                requests[4],
+                requests[2],
+                requests[0],
+                requests[3],
+                requests[1],
+            ],
+        ] {
+            let mut streaming = TerrainStreaming::default();
+            for (node_id, lod) in order {
+                streaming.queue_request(request(node_id, lod));
+            }
+            let popped: Vec<(u64, u8)> = std::iter::from_fn(|| streaming.pop_pending())
+                .map(|r| (r.node_id, r.lod))
+                .collect();
+            assert_eq!(popped, vec![(3, 0), (5, 0), (9, 0), (2, 1), (7, 1)]);
+        }
+    }
+
+    #[test]
+    fn test_queued_node_requeues_only_after_pop() {
+        let request = MeshRequest {
+            node_id: 4,
+            center: Vec2::ZERO,
+            size: 100.0,
+            lod: 0,
+            subdivisions: 8,
+            priority: 0.0,
+            coords: IVec2::ZERO,
+            neighbor_lods: [0; 4],
+        };
+        let mut streaming = TerrainStreaming::default();
+        streaming.queue_request(request.clone());
+        streaming.queue_refinement(request.clone());
+        assert_eq!(streaming.pending_iter().count(), 1);
+
+        assert!(streaming.pop_pending().is_some());
+        streaming.queue_request(request);
+        assert_eq!(streaming.pending_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_fine_lod_request_beats_nearer_coarse_one() {
+        let config = TerrainConfig::default();
+        let mut world = World::new();
+        world.insert_resource(config.clone());
+        world.insert_resource(TerrainQuadtree::from_config(&config));
+        world.init_resource::<TerrainStreaming>();
+        world.init_resource::<Time>();
+        // A cliff east of the camera keeps nearby chunks coarse while the
+        // flat ground to the west stays at full detail further out
+        world.spawn(TerrainBundle::procedural(|x, _| {
+            if x > 0.0 { 2000.0 } else { 0.0 }
+        }));
+        world.spawn((Camera3d::default(), Transform::from_xyz(0.0, 50.0, 0.0)));
+        world.run_system_once(update_quadtree).unwrap();
+
+        let mut streaming = world.remove_resource::<TerrainStreaming>().unwrap();
+        let quadtree = world.resource::<TerrainQuadtree>();
+        let popped: Vec<(u8, f32)> = std::iter::from_fn(|| streaming.pop_pending())
+            .map(|request| {
+                let node = quadtree.find_node(request.node_id).unwrap();
+                let distance = node.bounds.center().length();
+                // Queued with the camera distance weighted by the request's LOD
+                assert!(
+                    (request.priority - distance * config.lod_priority_weight(request.lod)).abs()
+                        < 1e-3
+                );
+                (request.lod, distance)
+            })
+            .collect();
+        assert!(popped.len() > 1);
+
+        // Some full-detail chunk streams before a coarser one that is nearer
+        assert!(popped.iter().enumerate().any(|(i, &(lod, distance))| {
+            popped[i + 1..]
+                .iter()
+                .any(|&(later_lod, later_distance)| later_lod > lod && later_distance < distance)
+        }));
+
+        // LODs past the configured weights reuse the last one
+        assert_eq!(config.lod_priority_weight(7), config.lod_priority_weight(3));
+    }
+
+    #[test]
+    fn test_warmup_queues_nodes_within_radius() {
+        let config = TerrainConfig::default();
+        let center = Vec2::new(5000.0, -3000.0);
+        let radius = 300.0;
+
+        // Same selection the camera would make at ground level over the center
+        let mut quadtree = TerrainQuadtree::from_config(&config);
+        quadtree.update(Vec3::new(center.x, 0.0, center.y), &config, |_, _| 0.0);
+        let area = BoundingCircle::new(center, radius);
+        let expected = quadtree
+            .collect_selected_nodes()
+            .iter()
+            .filter(|node| node.bounds.intersects(&area))
+            .count();
+        assert!(expected > 1);
+
+        let mut streaming = TerrainStreaming::default();
+        streaming.request_warmup(center, radius, &config);
+        assert_eq!(streaming.pending_iter().count(), expected);
+        assert_eq!(streaming.warmup.len(), expected);
+
+        // Repeated warmups dedup against the queue
+        streaming.request_warmup(center, radius, &config);
+        assert_eq!(streaming.pending_iter().count(), expected);
+
+        let mut wider = TerrainStreaming::default();
+        wider.request_warmup(center, radius * 4.0, &config);
+        assert!(wider.pending_iter().count() > expected);
+    }
+
+    #[test]
+    fn test_warmed_chunks_stay_hidden_until_selected() {
+        // Debug builds mesh slowly; don't let the warmup expire mid-test
+        let config = TerrainConfig::builder()
+            .render_distance(10)
+            .warmup_ttl_secs(600.0)
+            .build();
+        let mut app = test_app(&config);
+        run_until_idle(&mut app);
+
+        let center = Vec2::new(20_000.0, 20_000.0);
+        app.world_mut().write_message(WarmupRequest {
+            center,
+            radius: 50.0,
+        });
+        run_until_idle(&mut app);
+
+        let warmed: Vec<(u64, Entity)> = {
+            let streaming = app.world().resource::<TerrainStreaming>();
+            assert!(!streaming.warmup.is_empty());
+            streaming
+                .warmup
+                .keys()
+                .filter_map(|id| Some((*id, *streaming.spawned.get(id)?)))
+                .collect()
+        };
+        assert!(!warmed.is_empty());
+        for (_, entity) in &warmed {
+            assert_eq!(
+                app.world().get::<Visibility>(*entity),
+                Some(&Visibility::Hidden)
+            );
+        }
+
+        // Arriving over the warmed area shows the chunks the camera selects
+        app.world_mut()
+            .query_filtered::<&mut Transform, With<Camera3d>>()
+            .single_mut(app.world_mut())
+            .unwrap()
+            .translation = Vec3::new(center.x, 150.0, center.y);
+        app.update();
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let shown: Vec<Entity> = warmed
+            .iter()
+            .filter(|(id, entity)| streaming.spawned.get(id) == Some(entity))
+            .map(|(_, entity)| *entity)
+            .collect();
+        assert!(!shown.is_empty());
+        for entity in shown {
+            assert_eq!(
+                app.world().get::<Visibility>(entity),
+                Some(&Visibility::Inherited)
+            );
+        }
+    }
+
+    #[test]
+    fn test_unselected_warmup_expires() {
+        let config = TerrainConfig::builder()
+            .render_distance(10)
+            .warmup_ttl_secs(0.05)
+            .build();
+        let mut app = test_app(&config);
+        run_until_idle(&mut app);
+
+        app.world_mut().write_message(WarmupRequest {
+            center: Vec2::new(20_000.0, 20_000.0),
+            radius: 50.0,
+        });
+        app.update();
+        let warmed: Vec<u64> = app
+            .world()
+            .resource::<TerrainStreaming>()
+            .warmup
+            .keys()
+            .copied()
+            .collect();
+        assert!(!warmed.is_empty());
+
+        for _ in 0..500 {
+            app.update();
+            if app.world().resource::<TerrainStreaming>().warmup.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        run_until_idle(&mut app);
+
+        // Expired nodes unload like any other chunk the camera doesn't select
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert!(streaming.warmup.is_empty());
+        assert!(warmed.iter().all(|id| !streaming.spawned.contains_key(id)));
+    }
+
+    #[test]
+    fn test_tagged_camera_drives_lod() {
+        let config = TerrainConfig::default();
+        let mut world = World::new();
+        world.insert_resource(config.clone());
+        world.init_resource::<TerrainQuadtree>();
+        world.init_resource::<TerrainStreaming>();
+        world.init_resource::<Time>();
+
+        let far = Vec3::new(20_000.0, 50.0, -20_000.0);
+        world.spawn((Camera3d::default(), Transform::from_xyz(0.0, 50.0, 0.0)));
+        world.spawn((Camera3d::default(), Transform::from_translation(far)));
+
+        // Two untagged cameras are ambiguous, so nothing is selected
+        world.run_system_once(update_quadtree).unwrap();
+        assert!(world.resource::<TerrainQuadtree>().roots.is_empty());
+
+        world.spawn((
+            Camera3d::default(),
+            Transform::from_translation(far),
+            TerrainCamera,
+        ));
+        world.run_system_once(update_quadtree).unwrap();
+
+        // Roots are created around the tagged camera, not the one at the origin
+        let quadtree = world.resource::<TerrainQuadtree>();
+        let far_root = (far.xz() / quadtree.root_size).round().as_ivec2();
+        assert!(quadtree.roots.contains_key(&far_root));
+        assert!(!quadtree.roots.contains_key(&IVec2::ZERO));
+    }
+
+    #[test]
+    fn test_config_change_requeues_chunks() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = test_app(&config);
+        run_until_idle(&mut app);
+        let generated = |app: &App| {
+            app.world()
+                .resource::<TerrainStreamingMetrics>()
+                .meshes_generated_total
+        };
+
+        // Touching the config without changing it doesn't rebuild
+        app.world_mut()
+            .resource_mut::<TerrainConfig>()
+            .set_changed();
+        app.update();
+        assert!(app.world().resource::<TerrainStreaming>().stale.is_empty());
+
+        let before = generated(&app);
+        let spawned = app.world().resource::<TerrainStreaming>().spawned.clone();
+        let chunk_count =
+            |app: &mut App| app.world_mut().query::<&Chunk>().iter(app.world()).count();
+        let chunks_before = chunk_count(&mut app);
+        app.world_mut().resource_mut::<TerrainConfig>().max_height = 400.0;
+        app.update();
+
+        // Every selected node is requeued (or already replaced) and keeps its chunk meanwhile
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let selected = app
+            .world()
+            .resource::<TerrainQuadtree>()
+            .collect_selected_nodes();
+        assert_eq!(selected.len(), spawned.len());
+        for node in &selected {
+            let requeued = streaming.stale.contains(&node.id);
+            let replaced = streaming.spawned.get(&node.id) != spawned.get(&node.id);
+            assert!(spawned.contains_key(&node.id));
+            assert!(streaming.spawned.contains_key(&node.id));
+            assert!(requeued || replaced, "node {} was not requeued", node.id);
+        }
+        assert_eq!(chunk_count(&mut app), chunks_before);
+
+        run_until_idle(&mut app);
+        assert!(app.world().resource::<TerrainStreaming>().stale.is_empty());
+        assert!(generated(&app) >= before + spawned.len() as u64);
+    }
+
+    #[test]
+    fn test_terrain_edit_requeues_overlapping_chunks() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let terrain = Arc::new(EditableHeightmap::new(
+            ProceduralHeightmap::new(|_, _| 0.0),
+            1.0,
+        ));
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut()
+            .spawn(TerrainBundle::from_source(terrain.clone()));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 5000.0, 0.0)));
+        run_until_idle(&mut app);
+        let before = app
+            .world()
+            .resource::<TerrainStreamingMetrics>()
+            .meshes_generated_total;
+
+        let edit = terrain.apply_brush(Vec2::new(10.0, 10.0), 5.0, 20.0, Falloff::Smooth);
+        let area = BoundingCircle::new(edit.center, edit.radius);
+        app.world_mut().write_message(edit);
+        // Run the handler alone, the full pipeline may re-mesh flat chunks within the frame
+        app.world_mut()
+            .run_system_once(handle_terrain_edits)
+            .unwrap();
+
+        // Only chunks under the brush are rebuilt
+        let quadtree = app.world().resource::<TerrainQuadtree>();
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let selected = quadtree.collect_selected_nodes();
+        assert!(!streaming.stale.is_empty());
+        assert!(streaming.stale.len() < streaming.spawned.len());
+        for id in &streaming.stale {
+            let node = selected.iter().find(|node| node.id == *id).unwrap();
+            assert!(node.bounds.intersects(&area));
+        }
+
+        run_until_idle(&mut app);
+        assert!(app.world().resource::<TerrainStreaming>().stale.is_empty());
+        assert!(
+            app.world()
+                .resource::<TerrainStreamingMetrics>()
+                .meshes_generated_total
+                > before
+        );
+    }
+
+    #[test]
+    fn test_mesh_heights_prefer_finest_chunk() {
+        // Flat single-cell grid of a chunk
+        let grid = |coords, size, height| ChunkSamples {
+            coords,
+            origin: Vec2::ZERO,
+            step: size,
+            vertices_per_side: 2,
+            heights: vec![height; 4],
+        };
+        let heights = TerrainMeshHeights::default();
+        // Two roots and one child of the first, on the quadtree's cell layout
+        heights.insert(1, Vec2::ZERO, 800.0, &grid(IVec2::ZERO, 800.0, 1.0));
+        heights.insert(2, Vec2::new(800.0, 0.0), 800.0, &grid(IVec2::X, 800.0, 3.0));
+        heights.insert(
+            5,
+            Vec2::splat(-200.0),
+            400.0,
+            &grid(IVec2::ZERO, 400.0, 2.0),
+        );
+        assert_eq!(heights.len(), 3);
+
+        assert_eq!(heights.height_at(Vec2::splat(-100.0)), Some(2.0));
+        assert_eq!(heights.height_at(Vec2::splat(100.0)), Some(1.0));
+        assert_eq!(heights.height_at(Vec2::new(700.0, 0.0)), Some(3.0));
+        assert_eq!(heights.height_at(Vec2::splat(5000.0)), None);
+
+        heights.remove(5);
+        assert_eq!(heights.height_at(Vec2::splat(-100.0)), Some(1.0));
+    }
+
+    #[test]
+    fn test_mesh_height_query_follows_edits() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let terrain = Arc::new(EditableHeightmap::new(
+            ProceduralHeightmap::new(|_, _| 0.0),
+            1.0,
+        ));
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut()
+            .spawn(TerrainBundle::from_source(terrain.clone()));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 5000.0, 0.0)));
+        run_until_idle(&mut app);
+
+        // On a mesh vertex, so interpolation doesn't soften the brush
+        let center = Vec2::ZERO;
+        let edit = terrain.apply_brush(center, 100.0, 20.0, Falloff::Constant);
+        app.world_mut().write_message(edit);
+        run_until_idle(&mut app);
+
+        // The query's own source never saw the edit
+        let mesh_heights = app.world().resource::<TerrainMeshHeights>().clone();
+        assert!(!mesh_heights.is_empty());
+        let query = TerrainHeightQuery::from_source(ProceduralHeightmap::new(|_, _| 0.0), config)
+            .with_mesh_heights(mesh_heights);
+
+        assert_eq!(query.get_height(center.x, center.y), 0.0);
+        assert!((query.get_height_from_mesh(center.x, center.y) - 20.0).abs() < 1e-3);
+        // Outside the streamed area it falls back to the height source
+        assert_eq!(query.get_height_from_mesh(1.0e6, 1.0e6), 0.0);
+    }
+
+    #[test]
+    fn test_world_bounds_limit_spawned_chunks() {
+        let bounds = Rect::new(-1000.0, -1000.0, 1000.0, 1000.0);
+        let config = TerrainConfig::builder()
+            .render_distance(30)
+            .world_bounds(bounds)
+            .world_floor(-40.0)
+            .build();
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut().spawn(TerrainBundle::from_source(Arc::new(
+            ProceduralHeightmap::new(|_, _| 0.0),
+        )));
+        app.world_mut().spawn((
+            Camera3d::default(),
+            Transform::from_xyz(800.0, 5000.0, 800.0),
+        ));
+        run_until_idle(&mut app);
+
+        let mut chunks = app.world_mut().query::<(&crate::Chunk, &Transform)>();
+        let mut count = 0;
+        for (chunk, transform) in chunks.iter(app.world()) {
+            let rect = Rect::from_center_size(transform.translation.xz(), Vec2::splat(chunk.size));
+            assert!(
+                !bounds.intersect(rect).is_empty(),
+                "chunk {rect:?} outside the world"
+            );
+            count += 1;
+        }
+        assert!(count > 0);
+
+        let query = TerrainHeightQuery::from_source(ProceduralHeightmap::new(|_, _| 5.0), config);
+        assert_eq!(query.get_height(999.0, 0.0), 5.0);
+        assert_eq!(query.get_height(1001.0, 0.0), -40.0);
+    }
+
+    #[test]
+    fn test_chunk_analyzer_mean_height() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = test_app(&config);
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .set_chunk_analyzer(|samples: &ChunkSamples| {
+                samples.heights.iter().sum::<f32>() / samples.heights.len() as f32
+            });
+
+        run_until_idle(&mut app);
+
+        let mut query = app
+            .world_mut()
+            .query::<(&Chunk, &Mesh3d, &ChunkData<f32>)>();
+        let chunks: Vec<_> = query
+            .iter(app.world())
+            .map(|(chunk, mesh, data)| (chunk.current_lod, mesh.0.clone(), data.0))
+            .collect();
+        assert!(!chunks.is_empty());
+
+        let meshes = app.world().resource::<Assets<Mesh>>();
+        for (lod, handle, mean) in chunks {
+            let mesh = meshes.get(&handle).unwrap();
+            let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("mesh should have Float32x3 positions");
+            };
+            let vertices_per_side = config.lod_subdivisions[lod as usize] as usize + 1;
+            let surface = &positions[..vertices_per_side * vertices_per_side];
+            let expected = surface.iter().map(|p| p[1]).sum::<f32>() / surface.len() as f32;
+            assert!((mean - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_procedural_terrain_is_meshed_from_closure() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_plugins(crate::TerrainPlugin::new(config.clone()));
+        app.world_mut()
+            .spawn(TerrainBundle::procedural(|x, _| x * 0.1));
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(0.0, 5000.0, 0.0)));
+
+        run_until_idle(&mut app);
+
+        let mut query = app.world_mut().query::<(&Transform, &Mesh3d)>();
+        let chunks: Vec<_> = query
+            .iter(app.world())
+            .map(|(transform, mesh)| (transform.translation, mesh.0.clone()))
+            .collect();
+        assert!(!chunks.is_empty());
+
+        let meshes = app.world().resource::<Assets<Mesh>>();
+        for (origin, handle) in chunks {
+            let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) = meshes
+                .get(&handle)
+                .unwrap()
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("chunk mesh should have positions");
+            };
+            for position in positions {
+                let expected = (origin.x + position[0]) * 0.1;
+                // Surface vertices follow the closure; skirts hang below them
+                let on_surface = (position[1] - expected).abs() < 1e-3;
+                let on_skirt = (position[1] - (expected - config.skirt_depth)).abs() < 1e-3;
+                assert!(
+                    on_surface || on_skirt,
+                    "{position:?} doesn't follow x * 0.1"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_heights_matches_get_height() {
+        let query = TerrainHeightQuery::new(TerrainNoise::default(), TerrainConfig::default());
+        let points: Vec<Vec2> = (0..64)
+            .map(|i| Vec2::new(i as f32 * 53.1 - 1500.0, i as f32 * -27.4 + 800.0))
+            .collect();
+
+        let heights = query.get_heights(&points);
+        assert_eq!(heights.len(), points.len());
+        for (height, p) in heights.iter().zip(&points) {
+            assert_eq!(*height, query.get_height(p.x, p.y));
+        }
+    }
+
+    #[test]
+    fn test_raycast_hits_terrain_surface() {
+        let query = TerrainHeightQuery::new(TerrainNoise::default(), TerrainConfig::default());
+        let origin = Vec3::new(0.0, query.get_height(0.0, 0.0) + 300.0, 0.0);
+        let dir = Vec3::new(1.0, -0.5, 0.3);
+
+        let hit = query.raycast(origin, dir, 5000.0, 5.0).unwrap();
+        assert!((hit.y - query.get_height(hit.x, hit.z)).abs() < 1e-3);
+        // The hit lies on the ray
+        let along = (hit - origin).normalize();
+        assert!(along.dot(dir.normalize()) > 0.999);
+    }
+
+    #[test]
+    fn test_slope_on_30_degree_ramp() {
+        let rise = 30f32.to_radians().tan();
+        let query = TerrainHeightQuery::from_source(
+            crate::heightmap::ProceduralHeightmap::new(move |x, _| x * rise),
+            TerrainConfig::default(),
+        );
+
+        for (x, z) in [(0.0, 0.0), (-120.0, 45.0), (900.0, -3.0)] {
+            assert!((query.get_slope_degrees(x, z) - 30.0).abs() < 1e-3);
+            assert!((query.get_slope_degrees_with_step(x, z, 25.0) - 30.0).abs() < 1e-3);
+            assert!(query.is_buildable(x, z, 35.0));
+            assert!(!query.is_buildable(x, z, 25.0));
+        }
+    }
+
+    #[test]
+    fn test_get_height_with_transform() {
+        let config = TerrainConfig::default();
+        let untransformed = TerrainHeightQuery::new(TerrainNoise::default(), config.clone());
+        let offset = Vec3::new(100.0, 20.0, -50.0);
+        let moved = TerrainHeightQuery::new(TerrainNoise::default(), config.clone())
+            .with_transform(Transform::from_translation(offset));
+
+        for (x, z) in [(0.0, 0.0), (35.5, -12.0), (-800.0, 640.0)] {
+            assert_eq!(
+                moved.get_height(x + offset.x, z + offset.z),
+                untransformed.get_height(x, z) + offset.y
+            );
+        }
+
+        let scaled = TerrainHeightQuery::new(TerrainNoise::default(), config).with_transform(
+            Transform::from_translation(offset).with_scale(Vec3::new(2.0, 0.5, 2.0)),
+        );
+        let expected = untransformed.get_height(10.0, -4.0) * 0.5 + offset.y;
+        assert_eq!(scaled.get_height(120.0, -58.0), expected);
+    }
+
+    #[test]
+    fn test_heights_at_stacked_terrains() {
+        let config = TerrainConfig::default();
+        let query = TerrainHeightQuery::new(TerrainNoise::default(), config.clone());
+        assert_eq!(query.heights_at(120.0, -40.0).len(), 1);
+
+        let query = query.with_layer(TerrainNoise::with_seed(7), config.clone(), 500.0);
+        let heights = query.heights_at(120.0, -40.0);
+        let base = query.get_height(120.0, -40.0);
+        let upper =
+            sample_terrain_height(120.0, -40.0, &TerrainNoise::with_seed(7), &config) + 500.0;
+
+        assert_eq!(heights.as_slice(), &[upper, base]);
+    }
+
+    #[test]
+    fn test_water_depth_basin_and_land() {
+        let config = TerrainConfig::default();
+        let query = TerrainHeightQuery::new(TerrainNoise::default(), config.clone());
+
+        let points =
+            (-20..20).flat_map(|x| (-20..20).map(move |z| (x as f32 * 250.0, z as f32 * 250.0)));
+        let height_at = |&(x, z): &(f32, f32)| query.get_height(x, z);
+        let basin = points
+            .clone()
+            .min_by(|a, b| height_at(a).total_cmp(&height_at(b)))
+            .unwrap();
+        let land = points
+            .max_by(|a, b| height_at(a).total_cmp(&height_at(b)))
+            .unwrap();
+
+        let basin_depth = query.water_depth(basin.0, basin.1);
+        assert!(basin_depth > 0.0);
+        assert_eq!(basin_depth, -height_at(&basin));
+        assert_eq!(query.water_depth(land.0, land.1), 0.0);
+
+        // Low land above the water plane is dry, even below `water_level`
+        let raised = TerrainConfig::builder().base_elevation(10.0).build();
+        let shore = |height: f32| {
+            TerrainHeightQuery::from_source(
+                ProceduralHeightmap::new(move |_, _| height),
+                raised.clone(),
+            )
+        };
+        assert!(config.water_level > 5.0);
+        assert_eq!(shore(5.0).water_surface_y(), 10.0);
+        assert_eq!(shore(5.0).water_depth(0.0, 0.0), 0.0);
+        assert_eq!(shore(-3.0).water_depth(0.0, 0.0), 3.0);
+    }
+
+    #[test]
+    fn test_get_biome_from_height_moisture_and_slope() {
+        let config = TerrainConfig::default();
+        let range = config.max_height + config.water_level;
+        let biome_of = |f: fn(f32, f32, f32) -> f32| {
+            let (level, range) = (config.water_level, range);
+            TerrainHeightQuery::from_source(
+                ProceduralHeightmap::new(move |x, z| f(x, z, range) - level),
+                config.clone(),
+            )
+            .get_biome(10.0, 20.0)
+        };
+
+        assert_eq!(biome_of(|_, _, _| 0.0), Biome::DeepWater);
+        // Sources have a neutral moisture, which is grassland in the lowlands
+        assert_eq!(biome_of(|_, _, range| range * 0.2), Biome::Grass);
+        assert_eq!(biome_of(|_, _, range| range), Biome::Snow);
+        assert_eq!(biome_of(|x, _, range| range * 0.2 + x * 2.0), Biome::Rock);
+
+        let query = TerrainHeightQuery::new(TerrainNoise::default(), config.clone());
+        assert_eq!(
+            query.get_moisture(300.0, -50.0),
+            TerrainNoise::default().sample_moisture(300.0, -50.0)
+        );
+    }
+
+    /// Queue `count` requests at a single LOD and run one admission pass
+    fn admitted_tasks(config: &TerrainConfig, lod: u8, count: u64) -> usize {
+        AsyncComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let mut world = World::new();
+        world.insert_resource(config.clone());
+        let mut streaming = TerrainStreaming::default();
+        for node_id in 1..=count {
+            streaming.queue_request(MeshRequest {
+                node_id,
+                center: Vec2::ZERO,
+                size: 100.0,
+                lod,
+                subdivisions: config.lod_subdivisions[lod as usize],
+                priority: node_id as f32,
+                coords: IVec2::ZERO,
+                neighbor_lods: [0; 4],
+            });
+        }
+        world.insert_resource(streaming);
+
+        world.run_system_once(spawn_mesh_tasks).unwrap();
+        world.resource::<TerrainStreaming>().in_flight.len()
+    }
+
+    #[test]
+    fn test_deselected_task_is_cancelled() {
+        AsyncComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let config = TerrainConfig::default();
+        let mut world = World::new();
+        world.insert_resource(config.clone());
+        let mut streaming = TerrainStreaming::default();
+        streaming.queue_request(MeshRequest {
+            node_id: 7,
+            center: Vec2::ZERO,
+            size: 100.0,
+            lod: 3,
+            subdivisions: config.lod_subdivisions[3],
+            priority: 0.0,
+            coords: IVec2::ZERO,
+            neighbor_lods: [0; 4],
+        });
+        streaming.selected_nodes = Some(HashSet::from([7]));
+        world.insert_resource(streaming);
+        world.run_system_once(spawn_mesh_tasks).unwrap();
+
+        // Let the task finish while the node is still selected, then deselect it
+        while !world.resource::<TerrainStreaming>().in_flight[&7]
+            .task
+            .is_finished()
+        {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        world.resource_mut::<TerrainStreaming>().selected_nodes = Some(HashSet::new());
+        world.init_resource::<TerrainStreamingMetrics>();
+        world.run_system_once(poll_mesh_tasks).unwrap();
+
+        let streaming = world.resource::<TerrainStreaming>();
+        assert!(streaming.in_flight.is_empty());
+        assert!(streaming.completed.is_empty());
+    }
+
+    /// App running only chunk spawning, with a ready terrain material
+    fn spawn_test_app(config: TerrainConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<TerrainMaterial>()
+            .add_message::<TerrainChunkSpawned>()
+            .add_message::<TerrainChunkDespawned>()
+            .insert_resource(config)
+            .init_resource::<TerrainStreaming>()
+            .init_resource::<TerrainChunkIndex>()
+            .init_resource::<TerrainMeshHeights>()
+            .add_systems(Update, spawn_chunk_entities);
+
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<TerrainMaterial>>()
+            .add(TerrainMaterial::default());
+        app.insert_resource(TerrainMaterialHandle {
+            handle: Some(material),
+        });
+        app
+    }
+
+    /// Empty mesh result for a node
+    fn mesh_result(node_id: u64) -> MeshResult {
+        MeshResult {
+            node_id,
+            mesh: Mesh::new(
+                bevy::mesh::PrimitiveTopology::TriangleList,
+                bevy::asset::RenderAssetUsages::default(),
+            ),
+            center: Vec2::ZERO,
+            lod: 0,
+            coords: IVec2::ZERO,
+            size: 100.0,
+            subdivisions: 8,
+            min_y: 0.0,
+            max_y: 0.0,
+            neighbor_lods: [0; 4],
+            chunk_data: None,
+            quantization: None,
+            cached_mesh: None,
+            samples: None,
+        }
+    }
+
+    #[test]
+    fn test_chunks_tagged_with_their_morph_lod() {
+        let mut app = spawn_test_app(TerrainConfig::default());
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(MeshResult {
+                lod: 2,
+                ..mesh_result(1)
+            });
+        app.update();
+
+        let tag = app
+            .world_mut()
+            .query_filtered::<&MeshTag, With<Chunk>>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(tag.0, 2);
+    }
+
+    #[test]
+    fn test_quantized_chunk_without_material_keeps_full_positions() {
+        let mut app = spawn_test_app(TerrainConfig::default());
+        let quantized = || {
+            let mut mesh = crate::mesh::generate_chunk_mesh(
+                IVec2::ZERO,
+                100.0,
+                8,
+                &TerrainNoise::default(),
+                &TerrainConfig::default(),
+            )
+            .mesh;
+            let quantization = quantize_mesh_positions(&mut mesh);
+            MeshResult {
+                mesh,
+                quantization,
+                ..mesh_result(1)
+            }
+        };
+        let chunk_mesh = |app: &mut App| {
+            let (mesh, material) = app
+                .world_mut()
+                .query_filtered::<(&Mesh3d, &MeshMaterial3d<TerrainMaterial>), With<Chunk>>()
+                .single(app.world())
+                .unwrap();
+            let (mesh, material) = (mesh.0.clone(), material.0.clone());
+            let meshes = app.world().resource::<Assets<Mesh>>();
+            (meshes.get(&mesh).unwrap().clone(), material)
+        };
+        let shared = app
+            .world()
+            .resource::<TerrainMaterialHandle>()
+            .handle
+            .clone()
+            .unwrap();
+
+        // With the terrain material the chunk gets its own reconstruction uniform
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(quantized());
+        app.update();
+        let (mesh, material) = chunk_mesh(&mut app);
+        assert!(mesh.attribute(ATTRIBUTE_QUANTIZED_POSITION).is_some());
+        assert_ne!(material, shared);
+
+        // Without it the shared material's zero uniform would collapse the chunk
+        app.world_mut()
+            .resource_mut::<Assets<TerrainMaterial>>()
+            .remove(&shared);
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(quantized());
+        app.update();
+        let (mesh, material) = chunk_mesh(&mut app);
+        assert!(mesh.attribute(ATTRIBUTE_QUANTIZED_POSITION).is_none());
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_POSITION).is_some());
+        assert_eq!(material, shared);
+    }
+
+    #[test]
+    fn test_mesh_cache_reuses_handle_for_same_coords_and_lod() {
+        AsyncComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let mut app = spawn_test_app(TerrainConfig::builder().mesh_cache_size(1).build());
+        let chunk = || MeshResult {
+            coords: IVec2::new(3, 4),
+            lod: 1,
+            ..mesh_result(1)
+        };
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(chunk());
+        app.update();
+        let mesh_handle = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<&Mesh3d, With<Chunk>>()
+                .single(app.world())
+                .unwrap()
+                .0
+                .id()
+        };
+        let first = mesh_handle(&mut app);
+
+        // Unload the chunk, then request the same coords and LOD again
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .spawned
+            .clear();
+        app.update();
+        let result = chunk();
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .queue_request(MeshRequest {
+                node_id: result.node_id,
+                center: result.center,
+                size: result.size,
+                lod: result.lod,
+                subdivisions: result.subdivisions,
+                priority: 0.0,
+                coords: result.coords,
+                neighbor_lods: result.neighbor_lods,
+            });
+        app.world_mut().run_system_once(spawn_mesh_tasks).unwrap();
+        {
+            let streaming = app.world().resource::<TerrainStreaming>();
+            assert_eq!(streaming.in_flight_node_ids().count(), 0, "no regeneration");
+            assert_eq!(streaming.completed.len(), 1);
+        }
+        app.update();
+        assert_eq!(mesh_handle(&mut app), first);
+
+        // Caching another chunk evicts the first mesh from the assets
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(MeshResult {
+                coords: IVec2::new(9, 9),
+                ..mesh_result(2)
+            });
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .spawned
+            .remove(&1);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            app.world().resource::<TerrainStreaming>().mesh_cache.len(),
+            1
+        );
+        assert!(app.world().resource::<Assets<Mesh>>().get(first).is_none());
+    }
+
+    #[test]
+    fn test_heightmap_swap_clears_mesh_cache() {
+        AsyncComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let mut app = spawn_test_app(TerrainConfig::default());
+        app.add_systems(
+            Update,
+            rebuild_on_heightmap_change.before(spawn_chunk_entities),
+        );
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(mesh_result(1));
+        app.update();
+        assert_eq!(
+            app.world().resource::<TerrainStreaming>().mesh_cache.len(),
+            1
+        );
+
+        // A new terrain drops meshes cached from other heights without re-meshing
+        let terrain = app
+            .world_mut()
+            .spawn(crate::TerrainBundle::procedural(|_, _| 0.0))
+            .id();
+        app.update();
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert_eq!(streaming.mesh_cache.len(), 0);
+        assert!(streaming.stale.is_empty());
+
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(mesh_result(3));
+        app.update();
+        assert_eq!(
+            app.world().resource::<TerrainStreaming>().mesh_cache.len(),
+            1
+        );
+
+        // Swapping (or reloading) the heightmap invalidates the cached meshes
+        app.world_mut()
+            .entity_mut(terrain)
+            .insert(HeightmapHandle::Procedural(Arc::new(
+                crate::heightmap::ProceduralHeightmap::new(|_, _| 10.0),
+            )));
+        app.update();
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert_eq!(streaming.mesh_cache.len(), 0);
+        assert!(streaming.stale.contains(&1));
+        assert!(
+            streaming.spawned.contains_key(&1),
+            "chunk stays until re-meshed"
+        );
+
+        // Editing the heightmap in place does too
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(mesh_result(2));
+        app.update();
+        assert_eq!(
+            app.world().resource::<TerrainStreaming>().mesh_cache.len(),
+            1
+        );
+        app.world_mut()
+            .get_mut::<HeightmapHandle>(terrain)
+            .unwrap()
+            .set_changed();
+        app.update();
+        assert_eq!(
+            app.world().resource::<TerrainStreaming>().mesh_cache.len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_chunk_index_finds_chunk_at_position() {
+        let config = TerrainConfig::builder()
+            .render_distance(4)
+            .max_spawns_per_frame(0)
+            .build();
+        let mut quadtree = TerrainQuadtree::new(3, 800.0);
+        quadtree.update(Vec3::new(0.0, 20.0, 0.0), &config, |_, _| 0.0);
+        let nodes = quadtree.collect_selected_nodes();
+        assert!(nodes.iter().any(|node| node.bounds.half_size().x < 400.0));
+
+        let mut app = spawn_test_app(config);
+        for node in &nodes {
+            app.world_mut()
+                .resource_mut::<TerrainStreaming>()
+                .completed
+                .push(MeshResult {
+                    center: node.bounds.center(),
+                    coords: node.coords,
+                    size: node.bounds.half_size().x * 2.0,
+                    ..mesh_result(node.id)
+                });
+        }
+        app.update();
+
+        let chunks: Vec<(Entity, Vec2, f32)> = app
+            .world_mut()
+            .query::<(Entity, &Chunk, &Transform)>()
+            .iter(app.world())
+            .map(|(entity, chunk, transform)| (entity, transform.translation.xz(), chunk.size))
+            .collect();
+        let index = app.world().resource::<TerrainChunkIndex>();
+        assert_eq!(index.len(), nodes.len());
+        for &(entity, center, size) in &chunks {
+            // Anywhere inside the chunk, not just its center
+            let point = center + Vec2::new(0.3, -0.4) * size;
+            assert_eq!(index.chunk_at(point, 800.0), Some(entity));
+        }
+        assert_eq!(index.chunk_at(Vec2::splat(1.0e6), 800.0), None);
+    }
+
+    #[test]
+    fn test_base_elevation_raises_chunks_and_queries() {
+        let config = TerrainConfig::builder().base_elevation(100.0).build();
+        let mut app = spawn_test_app(config.clone());
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(MeshResult {
+                center: Vec2::new(50.0, -50.0),
+                ..mesh_result(1)
+            });
+        app.update();
+
+        let transform = *app
+            .world_mut()
+            .query_filtered::<&Transform, With<Chunk>>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(transform.translation, Vec3::new(50.0, 100.0, -50.0));
+
+        // A mesh vertex at local height 7 sits at transform Y + 7
+        let query = TerrainHeightQuery::from_source(ProceduralHeightmap::new(|_, _| 7.0), config);
+        assert_eq!(query.get_height(50.0, -50.0), transform.translation.y + 7.0);
+        assert_eq!(query.heights_at(0.0, 0.0).as_slice(), &[107.0]);
+    }
+
+    #[test]
+    fn test_regenerate_chunk_respawns_node() {
+        let config = TerrainConfig::builder().render_distance(4).build();
+        let mut quadtree = TerrainQuadtree::new(3, 800.0);
+        quadtree.update(Vec3::new(0.0, 20.0, 0.0), &config, |_, _| 0.0);
+        let node = quadtree
+            .collect_selected_nodes()
+            .into_iter()
+            .min_by_key(|node| node.lod_level)
+            .unwrap();
+        let subdivisions = node.subdivisions(&config);
+        // Spawned at a coarser first-pass LOD than the node targets
+        let coarse_lod = config.lod_subdivisions.len() as u8 - 1;
+        assert_ne!(node.lod_level, coarse_lod);
+
+        let mut app = spawn_test_app(config);
+        app.add_message::<RegenerateChunk>()
+            .insert_resource(quadtree);
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(MeshResult {
+                center: node.bounds.center(),
+                coords: node.coords,
+                size: node.bounds.half_size().x * 2.0,
+                lod: coarse_lod,
+                ..mesh_result(node.id)
+            });
+        app.update();
+        let old = app.world().resource::<TerrainStreaming>().spawned[&node.id];
+
+        app.world_mut()
+            .write_message(RegenerateChunk { node_id: node.id });
+        app.world_mut()
+            .run_system_once(handle_regenerate_requests)
+            .unwrap();
+
+        assert!(app.world().get_entity(old).is_err());
+        assert!(app.world().resource::<TerrainChunkIndex>().is_empty());
+        let streaming = app.world().resource::<TerrainStreaming>();
+        assert!(!streaming.spawned.contains_key(&node.id));
+        let queued: Vec<_> = streaming.pending_iter().collect();
+        assert_eq!(
+            queued,
+            vec![(node.id, node.coords, node.lod_level, REGENERATE_PRIORITY)]
+        );
+        assert_eq!(
+            streaming.pending.peek().unwrap().0.subdivisions,
+            subdivisions
+        );
+    }
+
+    #[test]
+    fn test_chunks_follow_terrain_transform() {
+        let mut app = spawn_test_app(TerrainConfig::default());
+        let terrain_transform = Transform::from_xyz(1000.0, 25.0, -500.0)
+            .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+        app.world_mut()
+            .spawn((Terrain, GlobalTransform::from(terrain_transform)));
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(MeshResult {
+                center: Vec2::new(100.0, 0.0),
+                ..mesh_result(1)
+            });
+
+        app.update();
+
+        let transform = *app
+            .world_mut()
+            .query_filtered::<&Transform, With<Chunk>>()
+            .single(app.world())
+            .unwrap();
+        // Local +X maps to world -Z under a quarter turn about Y
+        assert!(
+            transform
+                .translation
+                .abs_diff_eq(Vec3::new(1000.0, 25.0, -600.0), 1e-3)
+        );
+        assert!(
+            transform
+                .rotation
+                .abs_diff_eq(terrain_transform.rotation, 1e-5)
+        );
+
+        let messages = app.world().resource::<Messages<TerrainChunkSpawned>>();
+        let spawned = messages.get_cursor().read(messages).next().unwrap().clone();
+        assert!(
+            Vec3::from(spawned.bounds.center()).abs_diff_eq(Vec3::new(1000.0, 25.0, -600.0), 1e-3)
+        );
+    }
+
+    #[test]
+    fn test_spawns_capped_per_frame() {
+        let mut app = spawn_test_app(TerrainConfig::builder().max_spawns_per_frame(4).build());
+        let mut streaming = app.world_mut().resource_mut::<TerrainStreaming>();
+        streaming.completed.extend((1..=20).map(mesh_result));
+
+        app.update();
+        let chunk_count =
+            |app: &mut App| app.world_mut().query::<&Chunk>().iter(app.world()).count();
+        assert_eq!(chunk_count(&mut app), 4);
+        assert_eq!(
+            app.world().resource::<TerrainStreaming>().completed.len(),
+            16
+        );
+
+        // The backlog drains over the following frames
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(chunk_count(&mut app), 20);
+    }
+
+    #[test]
+    fn test_chunk_spawn_and_despawn_messages() {
+        let mut app = spawn_test_app(TerrainConfig::default());
+        let mut spawned_cursor = app
+            .world()
+            .resource::<Messages<TerrainChunkSpawned>>()
+            .get_cursor();
+        let mut despawned_cursor = app
+            .world()
+            .resource::<Messages<TerrainChunkDespawned>>()
+            .get_cursor();
+
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .completed
+            .push(MeshResult {
+                center: Vec2::new(250.0, -50.0),
+                coords: IVec2::new(2, -1),
+                lod: 1,
+                min_y: -5.0,
+                max_y: 40.0,
+                ..mesh_result(9)
+            });
+        app.update();
+
+        let spawned: Vec<_> = spawned_cursor
+            .read(app.world().resource::<Messages<TerrainChunkSpawned>>())
+            .cloned()
+            .collect();
+        assert_eq!(spawned.len(), 1);
+        let message = &spawned[0];
+        assert_eq!(message.node_id, 9);
+        assert_eq!(message.coords, IVec2::new(2, -1));
+        assert_eq!(message.lod, 1);
+        assert_eq!(message.bounds.min, Vec3A::new(200.0, -5.0, -100.0));
+        assert_eq!(message.bounds.max, Vec3A::new(300.0, 40.0, 0.0));
+        assert!(app.world().get::<Chunk>(message.entity).is_some());
+
+        // Deselecting the node despawns its chunk
+        app.world_mut()
+            .resource_mut::<TerrainStreaming>()
+            .spawned
+            .remove(&9);
+        app.update();
+
+        let despawned: Vec<_> = despawned_cursor
+            .read(app.world().resource::<Messages<TerrainChunkDespawned>>())
+            .map(|message| (message.node_id, message.coords))
+            .collect();
+        assert_eq!(despawned, vec![(9, IVec2::new(2, -1))]);
+        assert!(app.world().get_entity(message.entity).is_err());
+    }
+
+    #[test]
+    fn test_lod_change_remeshes_chunk_in_place() {
+        let config = TerrainConfig::builder()
+            .render_distance(1)
+            .generation_mode(GenerationMode::Synchronous)
+            .build();
+        let mut app = test_app(&config);
+        // A flat tree keeps each root a single node, so the camera only changes its LOD
+        app.insert_resource(TerrainQuadtree::new(0, 800.0));
+        run_until_idle(&mut app);
+
+        let root = crate::quadtree::root_node_id(IVec2::ZERO);
+        let root_chunks = |app: &mut App| {
+            app.world_mut()
+                .query::<(Entity, &Chunk)>()
+                .iter(app.world())
+                .filter(|(_, chunk)| chunk.node_id == root)
+                .map(|(entity, chunk)| (entity, chunk.current_lod))
+                .collect::<Vec<_>>()
+        };
+        let [(coarse, 3)] = root_chunks(&mut app)[..] else {
+            panic!("expected one coarse chunk for the root");
+        };
+        let mut despawned_cursor = app
+            .world()
+            .resource::<Messages<TerrainChunkDespawned>>()
+            .get_cursor();
+
+        let mut camera = app
+            .world_mut()
+            .query_filtered::<&mut Transform, With<Camera>>();
+        camera.single_mut(app.world_mut()).unwrap().translation = Vec3::new(0.0, 100.0, 0.0);
+        // The nearest chunk is meshed inline within the first frame
+        app.update();
+
+        // The same node now shows a full-detail chunk, and the coarse one is gone
+        let [(_, 0)] = root_chunks(&mut app)[..] else {
+            panic!("expected one full-detail chunk for the root");
+        };
+        assert!(app.world().get_entity(coarse).is_err());
+        let despawned: Vec<u64> = despawned_cursor
+            .read(app.world().resource::<Messages<TerrainChunkDespawned>>())
+            .map(|message| message.node_id)
+            .collect();
+        assert!(despawned.contains(&root));
+    }
+
+    #[test]
+    fn test_vertex_budget_limits_heavy_lods() {
+        let lod0_vertices = estimated_chunk_vertices(64);
+        let config = TerrainConfig::builder()
+            .max_concurrent_tasks(8)
+            .max_concurrent_vertices(lod0_vertices * 3)
+            .build();
+
+        let heavy = admitted_tasks(&config, 0, 12);
+        let light = admitted_tasks(&config, 3, 12);
+        assert_eq!(heavy, 3);
+        assert_eq!(light, 8);
+
+        // A single chunk over budget is still admitted on its own
+        let tiny_budget = TerrainConfig::builder().max_concurrent_vertices(1).build();
+        assert_eq!(admitted_tasks(&tiny_budget, 0, 4), 1);
+    }
+
+    #[test]
+    fn test_pending_iter_reports_requests() {
+        let mut streaming = TerrainStreaming::default();
+        for (node_id, priority) in [(5, 250.0), (1, 10.0), (9, 75.0)] {
+            streaming.queue_request(MeshRequest {
+                node_id,
+                center: Vec2::splat(node_id as f32),
+                size: 100.0,
+                lod: node_id as u8 % 4,
+                subdivisions: 8,
+                priority,
+                coords: IVec2::new(node_id as i32, -(node_id as i32)),
+                neighbor_lods: [0; 4],
+            });
+        }
+        // Duplicates are ignored
+        streaming.queue_request(MeshRequest {
+            node_id: 1,
+            center: Vec2::ZERO,
+            size: 100.0,
+            lod: 0,
+            subdivisions: 8,
+            priority: 0.0,
+            coords: IVec2::ZERO,
+            neighbor_lods: [0; 4],
+        });
+
+        let mut pending: Vec<_> = streaming.pending_iter().collect();
+        pending.sort_by_key(|(node_id, ..)| *node_id);
+        assert_eq!(
+            pending,
+            vec![
+                (1, IVec2::new(1, -1), 1, 10.0),
+                (5, IVec2::new(5, -5), 1, 250.0),
+                (9, IVec2::new(9, -9), 1, 75.0),
+            ]
+        );
+        assert_eq!(streaming.in_flight_node_ids().count(), 0);
+    }
+
+    #[test]
+    fn test_progressive_refinement_coarse_first() {
+        let config = TerrainConfig::builder()
+            .render_distance(4)
+            .progressive_refinement(true)
+            .build();
+        let mut app = test_app(&config);
+        // Keep the tree flat so the only thing that varies is each root's LOD
+        app.insert_resource(TerrainQuadtree::new(0, 800.0));
+        let mut camera = app
+            .world_mut()
+            .query_filtered::<&mut Transform, With<Camera>>();
+        camera.single_mut(app.world_mut()).unwrap().translation = Vec3::new(0.0, 100.0, 0.0);
+
+        let mut observed_lods = Vec::new();
+        for _ in 0..500 {
+            app.update();
+            let mut chunks = app.world_mut().query::<&Chunk>();
+            let lods: Vec<u32> = chunks
+                .iter(app.world())
+                .filter(|chunk| chunk.coords == IVec2::ZERO)
+                .map(|chunk| chunk.current_lod)
+                .collect();
+            assert!(
+                lods.len() <= 1,
+                "coarse and refined chunks should not coexist"
+            );
+            if let Some(&lod) = lods.first()
+                && observed_lods.last() != Some(&lod)
+            {
+                observed_lods.push(lod);
+            }
+            if observed_lods.last() == Some(&0) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        // One LOD per refinement pass
+        assert_eq!(observed_lods, vec![3, 2, 1, 0]);
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let root = streaming.spawned.keys().find(|id| {
+            app.world()
+                .resource::<TerrainQuadtree>()
+                .find_node(**id)
+                .is_some_and(|node| node.coords == IVec2::ZERO)
+        });
+        assert!(!streaming.refinements.contains_key(root.unwrap()));
     }
 }
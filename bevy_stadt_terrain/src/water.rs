@@ -0,0 +1,201 @@
+//! Water surface at sea level
+//!
+//! Terrain heights are shifted so [`TerrainConfig::water_level`] ends up at
+//! local `y = 0`, which `base_elevation` and the terrain entity's transform
+//! then place in the world. [`TerrainWaterPlugin`] draws a translucent plane there that
+//! follows the terrain camera in XZ and is scaled to cover the render
+//! distance. Change [`TerrainWaterSettings`] at runtime to recolor or move it.
+
+use crate::config::TerrainConfig;
+use crate::streaming::local_to_world_height;
+use crate::{Terrain, TerrainCamera};
+use bevy::prelude::*;
+
+/// Plugin adding the water surface
+#[derive(Default)]
+pub struct TerrainWaterPlugin {
+    /// Initial water settings
+    pub settings: TerrainWaterSettings,
+}
+
+impl Plugin for TerrainWaterPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.settings.clone())
+            .add_systems(Startup, spawn_water)
+            .add_systems(PostUpdate, (update_water_transform, update_water_material));
+    }
+}
+
+/// Runtime settings for the water surface
+#[derive(Resource, Clone, Debug)]
+pub struct TerrainWaterSettings {
+    /// World-space Y of the surface (0 is the terrain's water level)
+    ///
+    /// Measured from where `base_elevation` and the terrain transform place
+    /// the water level, in world units, so the terrain's vertical scale
+    /// doesn't apply to it.
+    pub level: f32,
+    /// Surface color; its alpha is replaced by `opacity`
+    pub color: Color,
+    /// Surface opacity (0-1)
+    pub opacity: f32,
+}
+
+impl Default for TerrainWaterSettings {
+    fn default() -> Self {
+        Self {
+            level: 0.0,
+            color: Color::srgb(0.1, 0.3, 0.55),
+            opacity: 0.7,
+        }
+    }
+}
+
+/// Marker for the water surface entity
+#[derive(Component)]
+pub struct TerrainWater;
+
+/// System: Spawn the water plane as a unit quad, scaled by [`update_water_transform`]
+pub fn spawn_water(
+    mut commands: Commands,
+    settings: Res<TerrainWaterSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        TerrainWater,
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(1.0, 1.0))),
+        MeshMaterial3d(materials.add(water_material(&settings))),
+        Transform::from_xyz(0.0, settings.level, 0.0),
+    ));
+}
+
+/// System: Keep the water under the camera and large enough to reach the render distance
+#[allow(clippy::type_complexity)]
+pub fn update_water_transform(
+    config: Res<TerrainConfig>,
+    settings: Res<TerrainWaterSettings>,
+    camera_query: Query<(&Transform, Has<TerrainCamera>), (With<Camera>, Without<TerrainWater>)>,
+    terrain_query: Query<&GlobalTransform, With<Terrain>>,
+    mut water_query: Query<&mut Transform, With<TerrainWater>>,
+) {
+    let camera_xz = camera_query
+        .iter()
+        .find(|(_, tagged)| *tagged)
+        .or_else(|| camera_query.single().ok())
+        .map_or(Vec2::ZERO, |(transform, _)| transform.translation.xz());
+    let extent = 2.0 * config.render_distance.max(1) as f32 * config.chunk_size;
+    let terrain_transform = terrain_query
+        .single()
+        .ok()
+        .map(GlobalTransform::compute_transform);
+    let level = settings.level
+        + local_to_world_height(0.0, config.base_elevation, terrain_transform.as_ref());
+
+    for mut transform in &mut water_query {
+        transform.translation = Vec3::new(camera_xz.x, level, camera_xz.y);
+        transform.scale = Vec3::new(extent, 1.0, extent);
+    }
+}
+
+/// System: Apply changed color or opacity to the water material
+pub fn update_water_material(
+    settings: Res<TerrainWaterSettings>,
+    water_query: Query<&MeshMaterial3d<StandardMaterial>, With<TerrainWater>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for handle in &water_query {
+        if let Some(material) = materials.get_mut(&handle.0) {
+            *material = water_material(&settings);
+        }
+    }
+}
+
+fn water_material(settings: &TerrainWaterSettings) -> StandardMaterial {
+    StandardMaterial {
+        base_color: settings.color.with_alpha(settings.opacity.clamp(0.0, 1.0)),
+        alpha_mode: AlphaMode::Blend,
+        perceptual_roughness: 0.1,
+        reflectance: 0.5,
+        ..default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_water_follows_camera_at_level() {
+        let config = TerrainConfig::builder()
+            .chunk_size(50.0)
+            .render_distance(8)
+            .build();
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>()
+            .insert_resource(config)
+            .add_plugins(TerrainWaterPlugin {
+                settings: TerrainWaterSettings {
+                    level: 3.0,
+                    ..default()
+                },
+            });
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(120.0, 80.0, -40.0)));
+        app.update();
+        app.update();
+
+        let transform = *app
+            .world_mut()
+            .query_filtered::<&Transform, With<TerrainWater>>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(transform.translation, Vec3::new(120.0, 3.0, -40.0));
+        assert_eq!(transform.scale, Vec3::new(800.0, 1.0, 800.0));
+
+        app.world_mut().resource_mut::<TerrainWaterSettings>().level = -2.0;
+        app.update();
+        let transform = app
+            .world_mut()
+            .query_filtered::<&Transform, With<TerrainWater>>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(transform.translation.y, -2.0);
+    }
+
+    #[test]
+    fn test_water_follows_base_elevation_and_terrain_transform() {
+        let config = TerrainConfig::builder().base_elevation(40.0).build();
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>()
+            .insert_resource(config)
+            .add_plugins(TerrainWaterPlugin {
+                settings: TerrainWaterSettings {
+                    level: 2.0,
+                    ..default()
+                },
+            });
+        app.world_mut().spawn((
+            Terrain,
+            GlobalTransform::from(
+                Transform::from_xyz(0.0, -10.0, 0.0).with_scale(Vec3::new(1.0, 0.5, 1.0)),
+            ),
+        ));
+        app.update();
+
+        let transform = *app
+            .world_mut()
+            .query_filtered::<&Transform, With<TerrainWater>>()
+            .single(app.world())
+            .unwrap();
+        // 40 * 0.5 - 10 + 2
+        assert_eq!(transform.translation.y, 12.0);
+    }
+}
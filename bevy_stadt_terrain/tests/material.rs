@@ -0,0 +1,51 @@
+//! Tests for the shared terrain material built at startup
+
+use bevy::prelude::*;
+use bevy_stadt_terrain::material::{MorphUniform, TerrainMaterialHandle};
+use bevy_stadt_terrain::prelude::*;
+
+fn terrain_material(config: TerrainConfig) -> TerrainMaterialExtension {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .add_plugins(TerrainPlugin::new(config));
+    app.update();
+
+    let handle = app
+        .world()
+        .resource::<TerrainMaterialHandle>()
+        .handle
+        .clone()
+        .expect("material should be created at startup");
+    app.world()
+        .resource::<Assets<TerrainMaterial>>()
+        .get(&handle)
+        .unwrap()
+        .extension
+        .clone()
+}
+
+#[test]
+fn morph_distances_follow_lod_distances() {
+    let lod_distances = [1200.0, 6000.0, 20000.0];
+    let config = TerrainConfig::builder()
+        .lod_distances(lod_distances)
+        .build();
+
+    let extension = terrain_material(config);
+    assert_eq!(
+        extension.morph,
+        MorphUniform::from_lod_distances(&lod_distances)
+    );
+    assert_eq!(
+        extension.morph.end[0].truncate(),
+        Vec3::from_array(lod_distances)
+    );
+    // Each band starts morphing before its LOD switch, after the previous one
+    for i in 0..3 {
+        let (start, end) = extension.morph.band(i);
+        assert_eq!(end, lod_distances[i]);
+        assert!(start < lod_distances[i]);
+        assert!(i == 0 || start > lod_distances[i - 1]);
+    }
+}
@@ -0,0 +1,154 @@
+//! End-to-end tests driving the full streaming pipeline in a headless app
+//!
+//! Covers `update_quadtree → spawn_mesh_tasks → poll_mesh_tasks → spawn_chunk_entities`
+//! and the bookkeeping that swaps parents and children during LOD transitions.
+
+use bevy::prelude::*;
+use bevy_stadt_terrain::Chunk;
+use bevy_stadt_terrain::heightmap::TerrainNoise;
+use bevy_stadt_terrain::prelude::*;
+use bevy_stadt_terrain::streaming::TerrainStreaming;
+use std::collections::HashMap;
+
+fn test_app(config: TerrainConfig, camera: Vec3) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .add_plugins(TerrainPlugin::new(config.clone()));
+    app.world_mut()
+        .spawn(TerrainBundle::noise(TerrainNoise::default(), &config));
+    app.world_mut()
+        .spawn((Camera3d::default(), Transform::from_translation(camera)));
+    app
+}
+
+fn move_camera(app: &mut App, position: Vec3) {
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&mut Transform, With<Camera>>();
+    query.single_mut(app.world_mut()).unwrap().translation = position;
+}
+
+/// Chunk entities keyed by node ID
+fn chunks(app: &mut App) -> HashMap<u64, (IVec2, u32)> {
+    let mut query = app.world_mut().query::<&Chunk>();
+    query
+        .iter(app.world())
+        .map(|chunk| (chunk.node_id, (chunk.coords, chunk.current_lod)))
+        .collect()
+}
+
+/// Step until the spawned chunks exactly match the quadtree selection and its LODs
+///
+/// Meshes are generated inline, so each frame makes progress without waiting
+/// on the task pool.
+fn settle(app: &mut App) {
+    for _ in 0..200 {
+        app.update();
+        let streaming = app.world().resource::<TerrainStreaming>();
+        let idle = streaming.pending_iter().next().is_none()
+            && streaming.in_flight_node_ids().next().is_none()
+            && streaming.completed.is_empty();
+        let selected: HashMap<u64, u32> = app
+            .world()
+            .resource::<TerrainQuadtree>()
+            .collect_selected_nodes()
+            .iter()
+            .map(|node| (node.id, node.lod_level as u32))
+            .collect();
+        let spawned: HashMap<u64, u32> = chunks(app)
+            .into_iter()
+            .map(|(id, (_, lod))| (id, lod))
+            .collect();
+        if idle && !selected.is_empty() && spawned == selected {
+            return;
+        }
+    }
+    panic!("streaming did not converge on the quadtree selection");
+}
+
+/// Every chunk entity matches its selected node's coords and LOD
+fn assert_chunks_match_selection(app: &mut App) {
+    let selected = app
+        .world()
+        .resource::<TerrainQuadtree>()
+        .collect_selected_nodes();
+    let chunks = chunks(app);
+    assert_eq!(chunks.len(), selected.len());
+    for node in &selected {
+        let (coords, lod) = chunks[&node.id];
+        assert_eq!(coords, node.coords);
+        assert_eq!(lod, node.lod_level as u32);
+    }
+}
+
+/// Depth of the deepest quadtree node with a chunk entity
+fn max_chunk_depth(app: &mut App) -> u8 {
+    let ids: Vec<u64> = chunks(app).into_keys().collect();
+    let quadtree = app.world().resource::<TerrainQuadtree>();
+    ids.iter()
+        .map(|id| quadtree.find_node(*id).unwrap().depth)
+        .max()
+        .unwrap()
+}
+
+fn config() -> TerrainConfig {
+    TerrainConfig::builder()
+        .render_distance(4)
+        .lod_subdivisions([8, 8, 4, 4])
+        .generation_mode(GenerationMode::Synchronous)
+        .build()
+}
+
+#[test]
+fn streams_chunks_for_initial_view() {
+    let mut app = test_app(config(), Vec3::new(0.0, 5000.0, 0.0));
+    settle(&mut app);
+
+    assert_chunks_match_selection(&mut app);
+    assert_eq!(max_chunk_depth(&mut app), 0);
+}
+
+#[test]
+fn subdivides_when_camera_approaches() {
+    let mut app = test_app(config(), Vec3::new(0.0, 5000.0, 0.0));
+    settle(&mut app);
+    let far_count = chunks(&mut app).len();
+
+    move_camera(&mut app, Vec3::new(0.0, 20.0, 0.0));
+    settle(&mut app);
+
+    assert_chunks_match_selection(&mut app);
+    assert!(chunks(&mut app).len() > far_count);
+    assert!(max_chunk_depth(&mut app) > 0);
+
+    // Near chunks are at full detail
+    let quadtree = app.world().resource::<TerrainQuadtree>();
+    let under_camera = quadtree
+        .collect_selected_nodes()
+        .into_iter()
+        .find(|node| node.bounds.closest_point(Vec2::ONE) == Vec2::ONE)
+        .unwrap();
+    assert_eq!(under_camera.lod_level, 0);
+}
+
+#[test]
+fn merges_when_camera_leaves() {
+    let mut app = test_app(config(), Vec3::new(0.0, 20.0, 0.0));
+    settle(&mut app);
+    let near_count = chunks(&mut app).len();
+    assert!(max_chunk_depth(&mut app) > 0);
+
+    // Far enough that even subdivided roots clear the hysteresis band and merge
+    move_camera(&mut app, Vec3::new(0.0, 8000.0, 0.0));
+    settle(&mut app);
+
+    assert_chunks_match_selection(&mut app);
+    assert!(chunks(&mut app).len() < near_count);
+    assert_eq!(max_chunk_depth(&mut app), 0);
+
+    // No stale children are left waiting on the merged parents
+    let streaming = app.world().resource::<TerrainStreaming>();
+    assert!(streaming.waiting_for_parent.is_empty());
+    assert!(streaming.waiting_for_children.is_empty());
+}
@@ -2,6 +2,7 @@ use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::input::mouse::AccumulatedMouseScroll;
 use bevy::pbr::{DistanceFog, FogFalloff};
 use bevy::prelude::*;
+use bevy_stadt_terrain::TerrainCamera;
 
 pub struct CameraPlugin;
 
@@ -17,6 +18,7 @@ fn setup_camera(mut commands: Commands) {
     commands.spawn((
         Camera3d::default(),
         Transform::from_xyz(0.0, 350.0, 150.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TerrainCamera,
         // Atmospheric fog for depth and to hide distant LOD artifacts
         DistanceFog {
             color: Color::srgba(0.7, 0.8, 0.9, 1.0),